@@ -0,0 +1,442 @@
+//! `stationxml` — convert, inspect, filter and check station metadata files.
+//!
+//! Built with the `cli` feature:
+//!
+//! ```text
+//! cargo install stationxml-rs --features cli
+//! stationxml convert station.xml --to sc3ml -o station.sc3ml
+//! stationxml info station.xml
+//! stationxml select station.xml --nslc 'XX.PBUMI.*.SH?' --start 2024-01-01
+//! stationxml validate *.xml
+//! stationxml diff old.xml new.xml
+//! stationxml merge operator-a.xml operator-b.sc3ml -o merged.xml
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use chrono::{DateTime, Utc};
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+
+use stationxml_rs::{
+    Fdsn, FdsnVersion, FdsnWriteOptions, Format, GeoJson, GeoJsonOptions, Inventory, Json,
+    JsonWriteOptions, ReadOptions, Sc3ml, Sc3mlWriteOptions, Selection, Severity, StationXmlError,
+    StationXmlFormat, XmlStyle, detect_format, parse_datetime,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "stationxml",
+    version,
+    about = "Convert, inspect and check FDSN StationXML, SC3ML and JSON inventories"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Skip networks, stations, channels and responses that fail to parse,
+    /// and drop unparsable optional dates, printing a warning for each,
+    /// instead of rejecting the file
+    #[arg(long, global = true)]
+    lenient: bool,
+    /// Read end dates at or after this time (e.g. `2500-01-01`) as open,
+    /// for inventories that mark running epochs with a far-future sentinel
+    #[arg(long, global = true, value_name = "TIME", value_parser = parse_time)]
+    open_end_after: Option<DateTime<Utc>>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert between FDSN StationXML, SC3ML and JSON
+    Convert {
+        /// Input file (format detected automatically)
+        input: PathBuf,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Show network, station and channel counts
+    Info {
+        /// Input files
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+    },
+    /// Keep only matching networks, stations and channels
+    Select {
+        /// Input file (format detected automatically)
+        input: PathBuf,
+        #[command(flatten)]
+        selection: SelectionArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Check files for parse errors and inconsistent metadata
+    Validate {
+        /// Input files
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+        /// Fail on warnings too
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Show added, removed and modified epochs between two files
+    Diff {
+        /// Old file
+        old: PathBuf,
+        /// New file
+        new: PathBuf,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Combine several files into one; later files win on conflicting epochs
+    Merge {
+        /// Input files
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Print the JSON Schema of the JSON format
+    Schema,
+}
+
+#[derive(Args)]
+struct OutputArgs {
+    /// Output file; standard output if omitted
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Output format. `convert` defaults to SC3ML for FDSN input and to
+    /// FDSN otherwise, `select` and `merge` to the (first) input format
+    #[arg(long, value_enum)]
+    to: Option<OutputFormat>,
+    /// FDSN StationXML schema version to write; only valid with FDSN output
+    #[arg(long, value_parser = parse_fdsn_version)]
+    fdsn_version: Option<FdsnVersion>,
+    /// Indent the output
+    #[arg(long)]
+    pretty: bool,
+    /// With `--to geojson`, one feature per channel epoch instead of per station
+    #[arg(long)]
+    geojson_channels: bool,
+}
+
+#[derive(Args)]
+struct SelectionArgs {
+    /// `NET.STA.LOC.CHA` pattern; overridden by the individual options
+    #[arg(long)]
+    nslc: Option<String>,
+    /// Network codes (comma-separated, `*`/`?` wildcards)
+    #[arg(short, long)]
+    network: Option<String>,
+    /// Station codes
+    #[arg(short, long)]
+    station: Option<String>,
+    /// Location codes (`--` for empty)
+    #[arg(short, long)]
+    location: Option<String>,
+    /// Channel codes
+    #[arg(short, long)]
+    channel: Option<String>,
+    /// Keep epochs active at or after this time (`2024-01-01` or RFC 3339)
+    #[arg(long, value_parser = parse_time)]
+    start: Option<DateTime<Utc>>,
+    /// Keep epochs active at or before this time
+    #[arg(long, value_parser = parse_time)]
+    end: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// FDSN StationXML
+    Fdsn,
+    /// SeisComP SC3ML
+    Sc3ml,
+    /// JSON encoding of the inventory model
+    Json,
+    /// GeoJSON map of stations (export only)
+    Geojson,
+}
+
+impl From<Format> for OutputFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Fdsn => Self::Fdsn,
+            Format::Sc3ml => Self::Sc3ml,
+            Format::Json => Self::Json,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut opts = ReadOptions::default().lenient(cli.lenient);
+    if let Some(threshold) = cli.open_end_after {
+        opts = opts.open_end_after(threshold);
+    }
+    // diff(1) reserves 1 for "different", so its errors exit with 2
+    let trouble = match cli.command {
+        Command::Diff { .. } => ExitCode::from(2),
+        _ => ExitCode::FAILURE,
+    };
+    let result = match cli.command {
+        Command::Convert { input, output } => convert(&opts, &input, &output),
+        Command::Info { inputs } => info(&opts, &inputs),
+        Command::Select {
+            input,
+            selection,
+            output,
+        } => select(&opts, &input, &selection, &output),
+        Command::Validate { inputs, strict } => validate(&opts, &inputs, strict),
+        Command::Diff { old, new, json } => diff(&opts, &old, &new, json),
+        Command::Merge { inputs, output } => merge(&opts, &inputs, &output),
+        Command::Schema => schema(),
+    };
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("stationxml: {err}");
+            trouble
+        }
+    }
+}
+
+// ─── Subcommands ────────────────────────────────────────────────────
+
+fn convert(opts: &ReadOptions, input: &Path, output: &OutputArgs) -> Result<ExitCode, String> {
+    let (inv, format) = read(opts, input)?;
+    let target = output.to.unwrap_or(match format {
+        Format::Fdsn => OutputFormat::Sc3ml,
+        Format::Sc3ml | Format::Json => OutputFormat::Fdsn,
+    });
+    write(&inv, target, output)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn info(opts: &ReadOptions, inputs: &[PathBuf]) -> Result<ExitCode, String> {
+    let mut out = io::stdout().lock();
+    for input in inputs {
+        let (inv, format) = read(opts, input)?;
+        let stations: usize = inv.networks.iter().map(|net| net.stations.len()).sum();
+        let channels: usize = inv
+            .networks
+            .iter()
+            .flat_map(|net| &net.stations)
+            .map(|sta| sta.channels.len())
+            .sum();
+        let version = inv.schema_version.as_deref().unwrap_or("?");
+        let format = match format {
+            Format::Fdsn => format!("FDSN StationXML {version}"),
+            Format::Sc3ml => format!("SC3ML {version}"),
+            // The inventory's schema version is that of the XML it came from
+            Format::Json => "JSON".to_string(),
+        };
+        writeln!(out, "{}: {format}", input.display()).map_err(io_error)?;
+        if !inv.source.is_empty() {
+            writeln!(out, "  source:   {}", inv.source).map_err(io_error)?;
+        }
+        writeln!(
+            out,
+            "  {} networks, {stations} stations, {channels} channels",
+            inv.networks.len()
+        )
+        .map_err(io_error)?;
+        for net in &inv.networks {
+            let channels: usize = net.stations.iter().map(|sta| sta.channels.len()).sum();
+            writeln!(
+                out,
+                "  {:<4} {:>5} stations {:>6} channels  {}",
+                net.code,
+                net.stations.len(),
+                channels,
+                net.description.as_deref().unwrap_or("")
+            )
+            .map_err(io_error)?;
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn select(
+    opts: &ReadOptions,
+    input: &Path,
+    args: &SelectionArgs,
+    output: &OutputArgs,
+) -> Result<ExitCode, String> {
+    let (inv, format) = read(opts, input)?;
+    let mut selection = args
+        .nslc
+        .as_deref()
+        .map(Selection::from_nslc)
+        .unwrap_or_default();
+    for (field, value) in [
+        (&mut selection.network, &args.network),
+        (&mut selection.station, &args.station),
+        (&mut selection.location, &args.location),
+        (&mut selection.channel, &args.channel),
+    ] {
+        if value.is_some() {
+            field.clone_from(value);
+        }
+    }
+    let selection = selection.time_window(args.start, args.end);
+    let target = output.to.unwrap_or(format.into());
+    write(&inv.select(&selection), target, output)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn validate(opts: &ReadOptions, inputs: &[PathBuf], strict: bool) -> Result<ExitCode, String> {
+    let mut failed = false;
+    for input in inputs {
+        let inv = match read(opts, input) {
+            Ok((inv, _)) => inv,
+            Err(err) => {
+                println!("{err}");
+                failed = true;
+                continue;
+            }
+        };
+        let issues = inv.validate();
+        for issue in &issues {
+            println!("{}: {issue}", input.display());
+        }
+        failed |= issues
+            .iter()
+            .any(|issue| strict || issue.severity == Severity::Error);
+        if issues.is_empty() {
+            println!("{}: ok", input.display());
+        }
+    }
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn diff(opts: &ReadOptions, old: &Path, new: &Path, json: bool) -> Result<ExitCode, String> {
+    let (old, _) = read(opts, old)?;
+    let (new, _) = read(opts, new)?;
+    let diff = old.diff(&new);
+    let mut out = io::stdout().lock();
+    if json {
+        serde_json::to_writer_pretty(&mut out, &diff).map_err(|e| e.to_string())?;
+        writeln!(out).map_err(io_error)?;
+    } else {
+        write!(out, "{diff}").map_err(io_error)?;
+    }
+    // Like diff(1): 0 if identical, 1 if different, 2 on errors
+    Ok(if diff.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn merge(opts: &ReadOptions, inputs: &[PathBuf], output: &OutputArgs) -> Result<ExitCode, String> {
+    let (mut merged, format) = read(opts, &inputs[0])?;
+    for input in &inputs[1..] {
+        let (inv, _) = read(opts, input)?;
+        for path in merged.merge(inv) {
+            eprintln!("stationxml: {}: replaces {path}", input.display());
+        }
+    }
+    write(&merged, output.to.unwrap_or(format.into()), output)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn schema() -> Result<ExitCode, String> {
+    let schema = serde_json::to_string_pretty(&Json::schema()).map_err(|e| e.to_string())?;
+    println!("{schema}");
+    Ok(ExitCode::SUCCESS)
+}
+
+// ─── Helpers ────────────────────────────────────────────────────────
+
+/// Read a file in whichever format it is in, printing lenient-mode warnings.
+fn read(opts: &ReadOptions, path: &Path) -> Result<(Inventory, Format), String> {
+    let located = |err: StationXmlError| format!("{}: {err}", path.display());
+    let xml = std::fs::read_to_string(path).map_err(|e| located(e.into()))?;
+    let format = detect_format(&xml).ok_or_else(|| located(StationXmlError::UnknownFormat))?;
+    let outcome = match format {
+        Format::Fdsn => Fdsn::read_from_str_with(&xml, opts),
+        Format::Sc3ml => Sc3ml::read_from_str_with(&xml, opts),
+        Format::Json => Json::read_from_str_with(&xml, opts),
+    }
+    .map_err(located)?;
+    for warning in &outcome.warnings {
+        eprintln!("stationxml: {}: skipped {warning}", path.display());
+    }
+    Ok((outcome.inventory, format))
+}
+
+fn write(inv: &Inventory, format: OutputFormat, args: &OutputArgs) -> Result<(), String> {
+    // A usage error, reported before the output file is created
+    if args.fdsn_version.is_some() && format != OutputFormat::Fdsn {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--fdsn-version only applies to FDSN StationXML output (--to fdsn)",
+            )
+            .exit();
+    }
+    let style = if args.pretty {
+        XmlStyle::pretty()
+    } else {
+        XmlStyle::default()
+    };
+    let sink: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(File::create(path).map_err(|e| format!("{}: {e}", path.display()))?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut sink = BufWriter::new(sink);
+    match format {
+        OutputFormat::Fdsn => {
+            let mut options = FdsnWriteOptions::default()
+                .module(concat!("stationxml-rs ", env!("CARGO_PKG_VERSION")))
+                .module_uri(env!("CARGO_PKG_REPOSITORY"))
+                .style(style);
+            if let Some(version) = args.fdsn_version {
+                options = options.version(version);
+            }
+            Fdsn::write_to_writer_with(inv, &mut sink, &options).map_err(|e| e.to_string())?;
+        }
+        OutputFormat::Sc3ml => {
+            let options = Sc3mlWriteOptions::default().style(style);
+            let xml = Sc3ml::write_to_string_with(inv, &options).map_err(|e| e.to_string())?;
+            sink.write_all(xml.as_bytes()).map_err(io_error)?;
+        }
+        OutputFormat::Json => {
+            let options = JsonWriteOptions::default().pretty(args.pretty);
+            let json = Json::write_to_string_with(inv, &options).map_err(|e| e.to_string())?;
+            sink.write_all(json.as_bytes()).map_err(io_error)?;
+        }
+        OutputFormat::Geojson => {
+            let options = GeoJsonOptions::default()
+                .channels(args.geojson_channels)
+                .pretty(args.pretty);
+            let json = GeoJson::write_to_string_with(inv, &options).map_err(|e| e.to_string())?;
+            sink.write_all(json.as_bytes()).map_err(io_error)?;
+        }
+    }
+    sink.write_all(b"\n")
+        .and_then(|()| sink.flush())
+        .map_err(io_error)
+}
+
+fn io_error(err: io::Error) -> String {
+    err.to_string()
+}
+
+fn parse_fdsn_version(s: &str) -> Result<FdsnVersion, String> {
+    FdsnVersion::parse(s).ok_or_else(|| format!("unknown FDSN StationXML version '{s}'"))
+}
+
+/// Accept the datetimes the readers accept: RFC 3339, `YYYY-MM-DDTHH:MM:SS`
+/// (UTC) or a bare date.
+fn parse_time(s: &str) -> Result<DateTime<Utc>, String> {
+    parse_datetime(s).map_err(|_| {
+        format!("invalid time '{s}', expected e.g. 2024-01-01 or 2024-01-01T12:00:00Z")
+    })
+}
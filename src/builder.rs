@@ -38,6 +38,8 @@ pub struct InventoryBuilder {
     source: String,
     sender: Option<String>,
     created: Option<DateTime<Utc>>,
+    module: Option<String>,
+    module_uri: Option<String>,
     networks: Vec<Network>,
 }
 
@@ -48,6 +50,8 @@ impl Inventory {
             source: String::new(),
             sender: None,
             created: None,
+            module: None,
+            module_uri: None,
             networks: vec![],
         }
     }
@@ -72,6 +76,18 @@ impl InventoryBuilder {
         self
     }
 
+    /// Set the name of the generating software module.
+    pub fn module(mut self, module: impl Into<String>) -> Self {
+        self.module = Some(module.into());
+        self
+    }
+
+    /// Set the URI of the generating software module.
+    pub fn module_uri(mut self, uri: impl Into<String>) -> Self {
+        self.module_uri = Some(uri.into());
+        self
+    }
+
     /// Add a network using a closure-based builder.
     pub fn network(
         mut self,
@@ -89,6 +105,10 @@ impl InventoryBuilder {
             source: self.source,
             sender: self.sender,
             created: self.created,
+            module: self.module,
+            module_uri: self.module_uri,
+            schema_version: None,
+            extensions: Extensions::default(),
             networks: self.networks,
         }
     }
@@ -151,6 +171,14 @@ impl NetworkBuilder {
             description: self.description,
             start_date: self.start_date,
             end_date: self.end_date,
+            restricted_status: None,
+            network_type: None,
+            network_class: None,
+            archive: None,
+            shared: None,
+            public_id: None,
+            comments: vec![],
+            extensions: Extensions::default(),
             stations: self.stations,
         }
     }
@@ -250,13 +278,30 @@ impl StationBuilder {
             latitude: self.latitude,
             longitude: self.longitude,
             elevation: self.elevation,
+            latitude_uncertainty: None,
+            longitude_uncertainty: None,
+            elevation_uncertainty: None,
             site: Site {
                 name: self.site_name,
                 ..Default::default()
             },
+            water_level: None,
+            vault: None,
+            geology: None,
+            equipment: vec![],
+            external_references: vec![],
             start_date: self.start_date,
             end_date: self.end_date,
             creation_date: self.creation_date,
+            restricted_status: None,
+            station_type: None,
+            affiliation: None,
+            archive: None,
+            archive_network_code: None,
+            shared: None,
+            public_id: None,
+            comments: vec![],
+            extensions: Extensions::default(),
             channels: self.channels,
         }
     }
@@ -378,11 +423,32 @@ impl ChannelBuilder {
             depth: self.depth,
             azimuth: self.azimuth,
             dip: self.dip,
+            latitude_uncertainty: None,
+            longitude_uncertainty: None,
+            elevation_uncertainty: None,
+            depth_uncertainty: None,
+            azimuth_uncertainty: None,
+            dip_uncertainty: None,
             sample_rate: self.sample_rate,
+            channel_types: vec![],
+            storage_format: None,
+            clock_drift: None,
+            calibration_units: None,
+            water_level: None,
             start_date: self.start_date,
             end_date: self.end_date,
+            restricted_status: None,
+            shared: None,
+            sensor_location_id: None,
+            comments: vec![],
+            sensor_location_comments: vec![],
             sensor: self.sensor,
+            pre_amplifier: None,
             data_logger: self.data_logger,
+            equipment: vec![],
+            aux_device: None,
+            aux_source: None,
+            extensions: Extensions::default(),
             response: self.response,
         }
     }
@@ -2,7 +2,7 @@
 //!
 //! Used by both FDSN and SC3ML backends for ISO 8601 datetime handling.
 
-use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
 
 use crate::error::{Result, StationXmlError};
 
@@ -14,7 +14,20 @@ use crate::error::{Result, StationXmlError};
 /// - `2026-02-20T00:00:00+00:00` (with offset)
 /// - `2026-02-20T00:00:00` (no timezone — assume UTC)
 /// - `2026-02-20T00:00:00.0000Z` (microsecond precision)
+/// - `2026-02-20` (date only — midnight UTC)
+///
+/// Surrounding whitespace is ignored.
+///
+/// ```
+/// use stationxml_rs::parse_datetime;
+///
+/// assert_eq!(
+///     parse_datetime("2026-02-20").unwrap(),
+///     parse_datetime("2026-02-20T00:00:00Z").unwrap()
+/// );
+/// ```
 pub fn parse_datetime(s: &str) -> Result<DateTime<Utc>> {
+    let s = s.trim();
     // Try RFC3339 first (with timezone info)
     if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
         return Ok(dt.with_timezone(&Utc));
@@ -27,22 +40,27 @@ pub fn parse_datetime(s: &str) -> Result<DateTime<Utc>> {
     if let Ok(naive) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f") {
         return Ok(naive.and_utc());
     }
+    // Date only (midnight UTC)
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(date.and_time(Default::default()).and_utc());
+    }
     Err(StationXmlError::InvalidData(format!(
         "cannot parse datetime: '{s}'"
     )))
 }
 
-/// Parse an optional datetime string.
+/// Parse an optional datetime string; empty or blank strings are `None`.
 pub fn parse_datetime_opt(s: &Option<String>) -> Result<Option<DateTime<Utc>>> {
     match s {
-        Some(s) if !s.is_empty() => Ok(Some(parse_datetime(s)?)),
+        Some(s) if !s.trim().is_empty() => Ok(Some(parse_datetime(s)?)),
         _ => Ok(None),
     }
 }
 
-/// Format a DateTime<Utc> to RFC3339 with second precision.
+/// Format a DateTime<Utc> to RFC3339, with as many fractional digits as
+/// needed (0, 3, 6 or 9) so that parsing it back is lossless.
 pub fn format_datetime(dt: &DateTime<Utc>) -> String {
-    dt.to_rfc3339_opts(SecondsFormat::Secs, true)
+    dt.to_rfc3339_opts(SecondsFormat::AutoSi, true)
 }
 
 /// Format an optional DateTime<Utc>.
@@ -92,6 +110,20 @@ mod tests {
         assert_eq!(dt.hour(), 12);
     }
 
+    #[test]
+    fn parse_date_only() {
+        let dt = parse_datetime("3000-01-01").unwrap();
+        assert_eq!(dt, parse_datetime("3000-01-01T00:00:00Z").unwrap());
+        assert!(parse_datetime("3000-13-01").is_err());
+    }
+
+    #[test]
+    fn parse_sentinel_without_timezone() {
+        let dt = parse_datetime(" 2599-12-31T23:59:59 ").unwrap();
+        assert_eq!(dt.year(), 2599);
+        assert_eq!(dt.second(), 59);
+    }
+
     #[test]
     fn parse_invalid() {
         assert!(parse_datetime("not-a-date").is_err());
@@ -105,6 +137,7 @@ mod tests {
     #[test]
     fn parse_opt_empty() {
         assert_eq!(parse_datetime_opt(&Some("".into())).unwrap(), None);
+        assert_eq!(parse_datetime_opt(&Some("  ".into())).unwrap(), None);
     }
 
     #[test]
@@ -114,6 +147,13 @@ mod tests {
         assert_eq!(s, "2026-02-20T12:30:45Z");
     }
 
+    #[test]
+    fn format_keeps_fraction() {
+        let dt = parse_datetime("2020-01-01T00:00:00.5Z").unwrap();
+        assert_eq!(format_datetime(&dt), "2020-01-01T00:00:00.500Z");
+        assert_eq!(parse_datetime(&format_datetime(&dt)).unwrap(), dt);
+    }
+
     #[test]
     fn format_opt_none() {
         assert_eq!(format_datetime_opt(&None), None);
@@ -0,0 +1,411 @@
+//! Comparison of two inventories, epoch by epoch.
+//!
+//! Networks, stations and channels are paired by code (plus location code
+//! for channels) and start date. Paired epochs are compared field by field
+//! through their serialized form, so every attribute of the model —
+//! including equipment and response stages — is covered. Lists are compared
+//! position by position, except channel types, whose order carries no
+//! meaning.
+//!
+//! ```
+//! use stationxml_rs::{ChangeKind, Inventory};
+//!
+//! let old = Inventory::builder()
+//!     .network("XX", |net| net.station("PBUMI", |sta| sta.channel("SHZ", "00", |ch| ch.sample_rate(100.0))))
+//!     .build();
+//! let new = Inventory::builder()
+//!     .network("XX", |net| net.station("PBUMI", |sta| sta.channel("SHZ", "00", |ch| ch.sample_rate(200.0))))
+//!     .build();
+//!
+//! let diff = old.diff(&new);
+//! assert_eq!(diff.changes[0].kind, ChangeKind::Modified);
+//! assert_eq!(diff.changes[0].nslc, "XX.PBUMI.00.SHZ");
+//! assert_eq!(diff.changes[0].fields[0].field, "sample_rate");
+//! ```
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::datetime::format_datetime_opt;
+use crate::inventory::*;
+
+/// Differences between two inventories, from [`Inventory::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct InventoryDiff {
+    /// Changed epochs, in document order
+    pub changes: Vec<EpochChange>,
+}
+
+impl InventoryDiff {
+    /// True if the inventories have the same networks, stations and channels.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// What happened to an epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Only in the new inventory
+    Added,
+    /// Only in the old inventory
+    Removed,
+    /// In both, with different content
+    Modified,
+}
+
+/// One network, station or channel epoch that differs.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EpochChange {
+    /// Kind of change
+    pub kind: ChangeKind,
+    /// `NET`, `NET.STA` or `NET.STA.LOC.CHA`
+    pub nslc: String,
+    /// Start of the epoch
+    pub start: Option<DateTime<Utc>>,
+    /// Changed fields (for [`ChangeKind::Modified`] only)
+    pub fields: Vec<FieldChange>,
+}
+
+/// A changed field of a modified epoch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    /// Field path, e.g. `sensor.model` or `response.stages[2].stage_gain.value`
+    pub field: String,
+    /// Old value (`null` if absent)
+    pub old: Value,
+    /// New value (`null` if absent)
+    pub new: Value,
+}
+
+impl Inventory {
+    /// Compare this inventory (old) with `other` (new).
+    ///
+    /// Document headers (source, creation time, …) are not compared. Child
+    /// epochs of added or removed networks and stations are not listed
+    /// individually.
+    pub fn diff(&self, other: &Inventory) -> InventoryDiff {
+        let mut changes = Vec::new();
+        for pair in pair_epochs(&self.networks, &other.networks, |net| {
+            (net.code.clone(), net.start_date)
+        }) {
+            match pair {
+                Paired::Both(old, new) => {
+                    let path = old.code.clone();
+                    compare(
+                        &mut changes,
+                        &path,
+                        old.start_date,
+                        &old.without_stations(),
+                        &new.without_stations(),
+                    );
+                    diff_stations(&mut changes, &path, &old.stations, &new.stations);
+                }
+                Paired::Old(net) => changes.push(change(
+                    ChangeKind::Removed,
+                    net.code.clone(),
+                    net.start_date,
+                )),
+                Paired::New(net) => {
+                    changes.push(change(ChangeKind::Added, net.code.clone(), net.start_date))
+                }
+            }
+        }
+        InventoryDiff { changes }
+    }
+}
+
+fn diff_stations(changes: &mut Vec<EpochChange>, net: &str, old: &[Station], new: &[Station]) {
+    for pair in pair_epochs(old, new, |sta| (sta.code.clone(), sta.start_date)) {
+        match pair {
+            Paired::Both(old, new) => {
+                let path = format!("{net}.{}", old.code);
+                compare(
+                    changes,
+                    &path,
+                    old.start_date,
+                    &old.without_channels(),
+                    &new.without_channels(),
+                );
+                diff_channels(changes, &path, &old.channels, &new.channels);
+            }
+            Paired::Old(sta) => changes.push(change(
+                ChangeKind::Removed,
+                format!("{net}.{}", sta.code),
+                sta.start_date,
+            )),
+            Paired::New(sta) => changes.push(change(
+                ChangeKind::Added,
+                format!("{net}.{}", sta.code),
+                sta.start_date,
+            )),
+        }
+    }
+}
+
+fn diff_channels(changes: &mut Vec<EpochChange>, sta: &str, old: &[Channel], new: &[Channel]) {
+    let key = |ch: &Channel| (format!("{}.{}", ch.location_code, ch.code), ch.start_date);
+    for pair in pair_epochs(old, new, key) {
+        match pair {
+            Paired::Both(old, new) => {
+                let path = format!("{sta}.{}.{}", old.location_code, old.code);
+                compare(changes, &path, old.start_date, old, new);
+            }
+            Paired::Old(ch) => changes.push(change(
+                ChangeKind::Removed,
+                format!("{sta}.{}.{}", ch.location_code, ch.code),
+                ch.start_date,
+            )),
+            Paired::New(ch) => changes.push(change(
+                ChangeKind::Added,
+                format!("{sta}.{}.{}", ch.location_code, ch.code),
+                ch.start_date,
+            )),
+        }
+    }
+}
+
+fn change(kind: ChangeKind, nslc: String, start: Option<DateTime<Utc>>) -> EpochChange {
+    EpochChange {
+        kind,
+        nslc,
+        start,
+        fields: Vec::new(),
+    }
+}
+
+/// Record a `Modified` change if `old` and `new` differ in any field.
+fn compare<T: Serialize + PartialEq>(
+    changes: &mut Vec<EpochChange>,
+    path: &str,
+    start: Option<DateTime<Utc>>,
+    old: &T,
+    new: &T,
+) {
+    if old == new {
+        return;
+    }
+    let mut fields = Vec::new();
+    // The model serializes infallibly (no maps with non-string keys)
+    let old = serde_json::to_value(old).unwrap_or(Value::Null);
+    let new = serde_json::to_value(new).unwrap_or(Value::Null);
+    diff_values("", &old, &new, &mut fields);
+    if !fields.is_empty() {
+        changes.push(EpochChange {
+            kind: ChangeKind::Modified,
+            nslc: path.to_string(),
+            start,
+            fields,
+        });
+    }
+}
+
+/// Fields holding unordered sets: compared by membership, reported whole.
+const SET_FIELDS: &[&str] = &["channel_types"];
+
+fn diff_values(path: &str, old: &Value, new: &Value, out: &mut Vec<FieldChange>) {
+    let child = |key: &str| match path {
+        "" => key.to_string(),
+        _ => format!("{path}.{key}"),
+    };
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys = a.keys().chain(b.keys().filter(|key| !a.contains_key(*key)));
+            for key in keys {
+                let (x, y) = (a.get(key), b.get(key));
+                diff_values(
+                    &child(key),
+                    x.unwrap_or(&Value::Null),
+                    y.unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if SET_FIELDS.contains(&path) => {
+            let same = a.iter().all(|v| b.contains(v)) && b.iter().all(|v| a.contains(v));
+            if !same {
+                out.push(FieldChange {
+                    field: path.to_string(),
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                diff_values(
+                    &format!("{path}[{i}]"),
+                    a.get(i).unwrap_or(&Value::Null),
+                    b.get(i).unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+        }
+        _ if old != new => out.push(FieldChange {
+            field: path.to_string(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+enum Paired<'a, T> {
+    Both(&'a T, &'a T),
+    Old(&'a T),
+    New(&'a T),
+}
+
+/// Pair up epochs with equal keys, in order of `old` then the unpaired rest of `new`.
+fn pair_epochs<'a, T, K: PartialEq>(
+    old: &'a [T],
+    new: &'a [T],
+    key: impl Fn(&T) -> K,
+) -> Vec<Paired<'a, T>> {
+    let new_keys: Vec<K> = new.iter().map(&key).collect();
+    let mut used = vec![false; new.len()];
+    let mut pairs = Vec::new();
+    for item in old {
+        let k = key(item);
+        match (0..new.len()).find(|&i| !used[i] && new_keys[i] == k) {
+            Some(i) => {
+                used[i] = true;
+                pairs.push(Paired::Both(item, &new[i]));
+            }
+            None => pairs.push(Paired::Old(item)),
+        }
+    }
+    pairs.extend(
+        new.iter()
+            .zip(used)
+            .filter(|(_, used)| !used)
+            .map(|(item, _)| Paired::New(item)),
+    );
+    pairs
+}
+
+impl fmt::Display for InventoryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            let sign = match change.kind {
+                ChangeKind::Added => '+',
+                ChangeKind::Removed => '-',
+                ChangeKind::Modified => '~',
+            };
+            let start = format_datetime_opt(&change.start).unwrap_or_else(|| "-".into());
+            writeln!(f, "{sign} {} [{start}]", change.nslc)?;
+            for field in &change.fields {
+                writeln!(f, "    {}: {} -> {}", field.field, field.old, field.new)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(year: i32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    fn inventory() -> Inventory {
+        Inventory::builder()
+            .network("XX", |net| {
+                net.station("PBUMI", |sta| {
+                    sta.channel("SHZ", "00", |ch| {
+                        ch.start_date(date(2020)).sample_rate(100.0)
+                    })
+                    .channel("SHN", "00", |ch| {
+                        ch.start_date(date(2020)).sample_rate(100.0)
+                    })
+                })
+            })
+            .build()
+    }
+
+    #[test]
+    fn identical_inventories() {
+        let inv = inventory();
+        assert!(inv.diff(&inv.clone()).is_empty());
+    }
+
+    #[test]
+    fn reports_added_removed_and_modified() {
+        let old = inventory();
+        let mut new = inventory();
+        let sta = &mut new.networks[0].stations[0];
+        sta.site.name = "New site".into();
+        sta.channels.remove(1);
+        sta.channels[0].dip = -90.0;
+        let mut added = sta.channels[0].clone();
+        added.code = "SHE".into();
+        sta.channels.push(added);
+
+        let diff = old.diff(&new);
+        let summary: Vec<(ChangeKind, &str)> = diff
+            .changes
+            .iter()
+            .map(|c| (c.kind, c.nslc.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Modified, "XX.PBUMI"),
+                (ChangeKind::Modified, "XX.PBUMI.00.SHZ"),
+                (ChangeKind::Removed, "XX.PBUMI.00.SHN"),
+                (ChangeKind::Added, "XX.PBUMI.00.SHE"),
+            ]
+        );
+        assert_eq!(diff.changes[0].fields[0].field, "site.name");
+        assert_eq!(diff.changes[1].fields[0].new, serde_json::json!(-90.0));
+
+        let text = diff.to_string();
+        assert!(text.contains("~ XX.PBUMI.00.SHZ [2020-01-01T00:00:00Z]\n    dip: 0.0 -> -90.0"));
+        assert!(text.contains("- XX.PBUMI.00.SHN"));
+    }
+
+    #[test]
+    fn epochs_paired_by_start_date() {
+        let old = inventory();
+        let mut new = inventory();
+        new.networks[0].stations[0].channels[0].start_date = Some(date(2021));
+        let diff = old.diff(&new);
+        let kinds: Vec<ChangeKind> = diff.changes.iter().map(|c| c.kind).collect();
+        assert_eq!(kinds, vec![ChangeKind::Removed, ChangeKind::Added]);
+    }
+
+    #[test]
+    fn channel_types_compared_as_sets() {
+        let mut old = inventory();
+        let mut new = inventory();
+        old.networks[0].stations[0].channels[0].channel_types =
+            vec![ChannelType::Continuous, ChannelType::Geophysical];
+        new.networks[0].stations[0].channels[0].channel_types =
+            vec![ChannelType::Geophysical, ChannelType::Continuous];
+        assert!(old.diff(&new).is_empty());
+
+        new.networks[0].stations[0].channels[0].channel_types = vec![ChannelType::Geophysical];
+        let diff = old.diff(&new);
+        let fields = &diff.changes[0].fields;
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].field, "channel_types");
+        assert_eq!(fields[0].new, serde_json::json!(["Geophysical"]));
+    }
+
+    #[test]
+    fn serializes_to_json() {
+        let old = inventory();
+        let mut new = inventory();
+        new.networks[0].stations[0].channels[0].sample_rate = 50.0;
+        let json = serde_json::to_value(old.diff(&new)).unwrap();
+        assert_eq!(json["changes"][0]["kind"], "modified");
+        assert_eq!(json["changes"][0]["fields"][0]["old"], 100.0);
+    }
+}
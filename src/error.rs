@@ -1,5 +1,7 @@
 //! Error types for stationxml-rs.
 
+use std::fmt;
+
 use thiserror::Error;
 
 /// All errors that can occur when reading/writing station metadata.
@@ -13,7 +15,7 @@ pub enum StationXmlError {
     #[error("XML serialization error: {0}")]
     XmlSerialize(#[from] quick_xml::SeError),
 
-    /// Failed to parse JSON (sensor library)
+    /// Failed to parse or write JSON (JSON backend, sensor library)
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
@@ -32,6 +34,123 @@ pub enum StationXmlError {
     /// A required field is missing from the input
     #[error("missing required field: {0}")]
     MissingField(String),
+
+    /// An error tied to a place in the input document
+    #[error("{}: {source}", context(path.as_deref(), *location))]
+    Located {
+        /// Logical path of the element, e.g. `IU.ANMO.00.BHZ stage 3`
+        path: Option<String>,
+        /// Position in the document, if known
+        location: Option<Location>,
+        /// The underlying error
+        source: Box<StationXmlError>,
+    },
+}
+
+/// Position in an input document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    /// Byte offset from the start of the document
+    pub offset: usize,
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column in characters, starting at 1
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+fn context(path: Option<&str>, location: Option<Location>) -> String {
+    match (path, location) {
+        (Some(path), Some(location)) => format!("{path} ({location})"),
+        (Some(path), None) => path.to_string(),
+        (None, Some(location)) => location.to_string(),
+        (None, None) => "unknown location".to_string(),
+    }
+}
+
+impl StationXmlError {
+    /// Position in the input document where the error occurred, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Self::Located { location, .. } => *location,
+            _ => None,
+        }
+    }
+
+    /// Logical path (`NET.STA.LOC.CHA`, optionally followed by `stage N`) of
+    /// the element where the error occurred, if known.
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            Self::Located { path, .. } => path.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The error without its location context.
+    ///
+    /// Readers wrap their errors in [`Located`](Self::Located), so match on
+    /// this rather than on the error itself:
+    ///
+    /// ```
+    /// use stationxml_rs::{Fdsn, StationXmlError, StationXmlFormat};
+    ///
+    /// let err = Fdsn::read_from_str("<FDSNStationXML>").unwrap_err();
+    /// assert!(matches!(err, StationXmlError::Located { .. }));
+    /// assert!(matches!(err.root_cause(), StationXmlError::XmlParse(_)));
+    /// ```
+    pub fn root_cause(&self) -> &StationXmlError {
+        match self {
+            Self::Located { source, .. } => source.root_cause(),
+            other => other,
+        }
+    }
+
+    /// Attach a logical path, unless a more specific one is already set.
+    pub(crate) fn at_path(self, path: &str) -> Self {
+        match self {
+            Self::Located {
+                path: None,
+                location,
+                source,
+            } => Self::Located {
+                path: Some(path.to_string()),
+                location,
+                source,
+            },
+            located @ Self::Located { .. } => located,
+            other => Self::Located {
+                path: Some(path.to_string()),
+                location: None,
+                source: Box::new(other),
+            },
+        }
+    }
+
+    /// Attach a document position, unless one is already set.
+    pub(crate) fn at_location(self, location: Location) -> Self {
+        match self {
+            Self::Located {
+                path,
+                location: None,
+                source,
+            } => Self::Located {
+                path,
+                location: Some(location),
+                source,
+            },
+            located @ Self::Located { .. } => located,
+            other => Self::Located {
+                path: None,
+                location: Some(location),
+                source: Box::new(other),
+            },
+        }
+    }
 }
 
 /// Convenience alias used throughout the crate.
@@ -59,6 +178,26 @@ mod tests {
         assert!(err.to_string().contains("Latitude"));
     }
 
+    #[test]
+    fn located_error_display() {
+        let location = Location {
+            offset: 120,
+            line: 7,
+            column: 9,
+        };
+        let err = StationXmlError::InvalidData("unknown FIR symmetry: 'X'".into())
+            .at_path("IU.ANMO.00.BHZ stage 3")
+            .at_path("IU.ANMO")
+            .at_location(location);
+        assert_eq!(
+            err.to_string(),
+            "IU.ANMO.00.BHZ stage 3 (line 7, column 9): invalid data: unknown FIR symmetry: 'X'"
+        );
+        assert_eq!(err.path(), Some("IU.ANMO.00.BHZ stage 3"));
+        assert_eq!(err.location(), Some(location));
+        assert!(matches!(err.root_cause(), StationXmlError::InvalidData(_)));
+    }
+
     #[test]
     fn error_from_io() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
@@ -0,0 +1,759 @@
+//! Capture and re-emission of foreign-namespace content (vendor extensions).
+//!
+//! The serde types in [`super::types`] silently skip anything they do not
+//! model. Extensions are therefore handled in a separate pass over the raw
+//! XML: the reader scans the document for attributes and child elements from
+//! other namespaces at any depth, files them under the nearest root,
+//! `Network`, `Station` or `Channel` node with their relative path and
+//! position among FDSN siblings, and the writer splices them back into the
+//! serialized output at the same place.
+//!
+//! quick-xml's serde support ignores namespace prefixes, so the serde pass
+//! would read `<qc:Description>` as an FDSN `<Description>`. The foreign
+//! content is therefore blanked out of the document it deserializes.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::ResolveResult;
+use quick_xml::{NsReader, Reader, Writer};
+
+use crate::error::{Result, StationXmlError};
+use crate::inventory::*;
+use crate::locate;
+
+/// Namespace shared by all FDSN StationXML 1.x versions.
+const FDSN_NS: &[u8] = b"http://www.fdsn.org/xml/station/1";
+
+/// Position of a node that owns [`Extensions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum NodePath {
+    Root,
+    Network(usize),
+    Station(usize, usize),
+    Channel(usize, usize, usize),
+}
+
+impl NodePath {
+    /// Path of the `index`-th child named `local`, if that child owns extensions.
+    fn child(self, local: &[u8], index: usize) -> Option<NodePath> {
+        match (self, local) {
+            (NodePath::Root, b"Network") => Some(NodePath::Network(index)),
+            (NodePath::Network(n), b"Station") => Some(NodePath::Station(n, index)),
+            (NodePath::Station(n, s), b"Channel") => Some(NodePath::Channel(n, s, index)),
+            _ => None,
+        }
+    }
+}
+
+/// An open FDSN element while walking a document.
+struct Frame<'a> {
+    /// Node whose extensions hold this element's content
+    owner: NodePath,
+    /// Element path relative to `owner`, empty for the owner itself
+    path: String,
+    /// FDSN children seen so far, per local name
+    counts: Vec<(Vec<u8>, usize)>,
+    /// FDSN children seen so far
+    children: usize,
+    /// Fragments still to be written into this element (writer only)
+    pending: Vec<&'a ExtensionElement>,
+}
+
+impl<'a> Frame<'a> {
+    fn new(owner: NodePath, path: String) -> Self {
+        Self {
+            owner,
+            path,
+            counts: Vec::new(),
+            children: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Frame of the next FDSN child, named `local`.
+    fn child(&mut self, local: &[u8]) -> Frame<'a> {
+        let index = match self.counts.iter_mut().find(|(name, _)| name == local) {
+            Some((_, count)) => {
+                *count += 1;
+                *count - 1
+            }
+            None => {
+                self.counts.push((local.to_vec(), 1));
+                0
+            }
+        };
+        self.children += 1;
+
+        let node = if self.path.is_empty() {
+            self.owner.child(local, index)
+        } else {
+            None
+        };
+        match node {
+            Some(node) => Frame::new(node, String::new()),
+            None => {
+                let segment = format!("{}[{index}]", String::from_utf8_lossy(local));
+                let path = if self.path.is_empty() {
+                    segment
+                } else {
+                    format!("{}/{segment}", self.path)
+                };
+                Frame::new(self.owner, path)
+            }
+        }
+    }
+
+    /// Queue the fragments of `ext` that belong inside this element.
+    fn with_fragments(mut self, ext: Option<&'a Extensions>) -> Self {
+        if let Some(ext) = ext {
+            self.pending = ext
+                .elements
+                .iter()
+                .filter(|el| el.parent == self.path)
+                .collect();
+        }
+        self
+    }
+
+    /// Write the queued fragments anchored before the next FDSN child.
+    fn write_anchored(&mut self, writer: &mut Writer<Vec<u8>>) {
+        let children = self.children;
+        self.pending.retain(|el| {
+            let ready = el.position.is_some_and(|p| p <= children);
+            if ready {
+                writer.get_mut().extend_from_slice(el.xml.as_bytes());
+            }
+            !ready
+        });
+    }
+
+    /// Write all remaining fragments, before the element's end tag.
+    fn write_remaining(&mut self, writer: &mut Writer<Vec<u8>>) {
+        for el in self.pending.drain(..) {
+            writer.get_mut().extend_from_slice(el.xml.as_bytes());
+        }
+    }
+}
+
+fn is_foreign(ns: &ResolveResult) -> bool {
+    match ns {
+        ResolveResult::Bound(ns) => ns.as_ref() != FDSN_NS,
+        ResolveResult::Unknown(_) => true,
+        ResolveResult::Unbound => false,
+    }
+}
+
+/// True if `xml` declares a namespace other than FDSN's. Foreign content
+/// cannot exist without one, so the extension pass is skipped otherwise.
+fn declares_foreign_namespace(xml: &str) -> bool {
+    xml.match_indices("xmlns").any(|(i, _)| {
+        let rest = &xml[i + "xmlns".len()..];
+        let Some(eq) = rest.find('=') else {
+            return false;
+        };
+        let name = rest[..eq].trim_end();
+        if !(name.is_empty() || name.starts_with(':')) {
+            return false;
+        }
+        let value = rest[eq + 1..].trim_start();
+        let Some(quote) = value.chars().next().filter(|c| matches!(c, '"' | '\'')) else {
+            return false;
+        };
+        value[1..]
+            .split(quote)
+            .next()
+            .is_some_and(|ns| ns.as_bytes() != FDSN_NS)
+    })
+}
+
+fn node_extensions(inv: &mut Inventory, path: NodePath) -> Option<&mut Extensions> {
+    Some(match path {
+        NodePath::Root => &mut inv.extensions,
+        NodePath::Network(n) => &mut inv.networks.get_mut(n)?.extensions,
+        NodePath::Station(n, s) => &mut inv.networks.get_mut(n)?.stations.get_mut(s)?.extensions,
+        NodePath::Channel(n, s, c) => {
+            &mut inv
+                .networks
+                .get_mut(n)?
+                .stations
+                .get_mut(s)?
+                .channels
+                .get_mut(c)?
+                .extensions
+        }
+    })
+}
+
+fn node_extensions_ref(inv: &Inventory, path: NodePath) -> Option<&Extensions> {
+    Some(match path {
+        NodePath::Root => &inv.extensions,
+        NodePath::Network(n) => &inv.networks.get(n)?.extensions,
+        NodePath::Station(n, s) => &inv.networks.get(n)?.stations.get(s)?.extensions,
+        NodePath::Channel(n, s, c) => {
+            &inv.networks
+                .get(n)?
+                .stations
+                .get(s)?
+                .channels
+                .get(c)?
+                .extensions
+        }
+    })
+}
+
+/// Foreign-namespace content found in a document, to be attached to the
+/// inventory read from it.
+#[derive(Default)]
+pub(crate) struct ForeignContent {
+    extensions: HashMap<NodePath, Extensions>,
+    /// Byte ranges of the foreign elements and attributes
+    spans: Vec<Range<usize>>,
+}
+
+impl ForeignContent {
+    /// `xml` without the foreign elements and attributes, for the serde pass.
+    /// Namespace declarations are kept; offsets and line numbers are unchanged.
+    pub(crate) fn strip<'a>(&self, xml: &'a str) -> Cow<'a, str> {
+        if self.spans.is_empty() {
+            return Cow::Borrowed(xml);
+        }
+        let mut stripped = xml.to_string();
+        for span in &self.spans {
+            locate::blank(&mut stripped, span.clone());
+        }
+        Cow::Owned(stripped)
+    }
+
+    /// Attach the extensions to `inv`, which must have been converted from
+    /// the same document, so node indices line up.
+    pub(crate) fn attach(self, inv: &mut Inventory) {
+        for (path, found) in self.extensions {
+            if let Some(ext) = node_extensions(inv, path) {
+                ext.attributes.extend(found.attributes);
+                ext.elements.extend(found.elements);
+                ext.nested_attributes.extend(found.nested_attributes);
+            }
+        }
+    }
+}
+
+/// Scan `xml` for foreign-namespace content.
+pub(crate) fn read_extensions(xml: &str) -> Result<ForeignContent> {
+    let mut foreign = ForeignContent::default();
+    if !declares_foreign_namespace(xml) {
+        return Ok(foreign);
+    }
+
+    let mut reader = NsReader::from_str(xml);
+    let mut stack: Vec<Frame> = Vec::new();
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let (ns, event) = reader.read_resolved_event().map_err(xml_error)?;
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let is_empty = matches!(event, Event::Empty(_));
+
+                if is_foreign(&ns) {
+                    if !is_empty {
+                        let end = e.to_end().into_owned();
+                        reader.read_to_end(end.name()).map_err(xml_error)?;
+                    }
+                    let span = start..reader.buffer_position() as usize;
+                    if let Some(parent) = stack.last() {
+                        let element = ExtensionElement {
+                            parent: parent.path.clone(),
+                            position: Some(parent.children),
+                            xml: xml[span.clone()].to_string(),
+                        };
+                        let ext = foreign.extensions.entry(parent.owner).or_default();
+                        ext.elements.push(element);
+                    }
+                    foreign.spans.push(span);
+                    continue;
+                }
+
+                let frame = match stack.last_mut() {
+                    Some(parent) => parent.child(e.local_name().as_ref()),
+                    None => Frame::new(NodePath::Root, String::new()),
+                };
+                let attributes = foreign_attributes(&reader, e)?;
+                if !attributes.is_empty() {
+                    let tag = start..reader.buffer_position() as usize;
+                    foreign.spans.extend(
+                        attribute_spans(&xml[tag.clone()])
+                            .into_iter()
+                            .filter(|(name, _)| {
+                                !name.starts_with("xmlns")
+                                    && attributes.iter().any(|(key, _)| key == name)
+                            })
+                            .map(|(_, span)| tag.start + span.start..tag.start + span.end),
+                    );
+                    let ext = foreign.extensions.entry(frame.owner).or_default();
+                    if frame.path.is_empty() {
+                        ext.attributes.extend(attributes);
+                    } else {
+                        ext.nested_attributes.extend(attributes.into_iter().map(
+                            |(name, value)| ExtensionAttribute {
+                                element: frame.path.clone(),
+                                name,
+                                value,
+                            },
+                        ));
+                    }
+                }
+                if !is_empty {
+                    stack.push(frame);
+                }
+            }
+            Event::End(_) => {
+                stack.pop();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(foreign)
+}
+
+/// Names and byte ranges of the attributes in the start tag `tag`.
+fn attribute_spans(tag: &str) -> Vec<(&str, Range<usize>)> {
+    let bytes = tag.as_bytes();
+    let skip = |mut i: usize, while_: fn(u8) -> bool| {
+        while i < bytes.len() && while_(bytes[i]) {
+            i += 1;
+        }
+        i
+    };
+    let mut spans = Vec::new();
+    // Past `<` and the element name
+    let mut i = skip(1, |b| !b.is_ascii_whitespace() && b != b'/' && b != b'>');
+    loop {
+        let start = skip(i, |b| b.is_ascii_whitespace());
+        let name_end = skip(start, |b| {
+            !b.is_ascii_whitespace() && b != b'=' && b != b'/' && b != b'>'
+        });
+        if name_end == start {
+            return spans;
+        }
+        let eq = skip(name_end, |b| b.is_ascii_whitespace());
+        let quote_at = skip(eq + 1, |b| b.is_ascii_whitespace());
+        let Some(&quote) = bytes.get(quote_at) else {
+            return spans;
+        };
+        let Some(len) = tag[quote_at + 1..].find(quote as char) else {
+            return spans;
+        };
+        i = quote_at + 1 + len + 1;
+        spans.push((&tag[start..name_end], start..i));
+    }
+}
+
+/// Collect `xmlns:*` declarations and attributes bound to other namespaces.
+fn foreign_attributes(reader: &NsReader<&[u8]>, e: &BytesStart) -> Result<Vec<(String, String)>> {
+    let mut out = Vec::new();
+    for attr in e.attributes() {
+        let attr = attr.map_err(|err| xml_error(err.into()))?;
+        let keep = match attr.key.as_namespace_binding() {
+            Some(quick_xml::name::PrefixDeclaration::Named(_)) => true,
+            Some(quick_xml::name::PrefixDeclaration::Default) => false,
+            None => is_foreign(&reader.resolve_attribute(attr.key).0),
+        };
+        if keep {
+            let key = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+            let value = attr.unescape_value().map_err(xml_error)?.into_owned();
+            out.push((key, value));
+        }
+    }
+    Ok(out)
+}
+
+/// Splice extension attributes and elements from `inv` into FDSN `xml`
+/// produced by the serializer.
+pub(crate) fn write_extensions(xml: String, inv: &Inventory) -> Result<String> {
+    if !has_extensions(inv) {
+        return Ok(xml);
+    }
+    splice_extensions(&xml, NodePath::Root, |path| node_extensions_ref(inv, path))
+}
+
+/// Splice the extensions of `sta` and its channels into a serialized
+/// `<Station>` fragment.
+pub(crate) fn write_station_extensions(xml: String, sta: &Station) -> Result<String> {
+    if sta.extensions.is_empty() && sta.channels.iter().all(|ch| ch.extensions.is_empty()) {
+        return Ok(xml);
+    }
+    splice_extensions(&xml, NodePath::Station(0, 0), |path| match path {
+        NodePath::Station(..) => Some(&sta.extensions),
+        NodePath::Channel(_, _, c) => sta.channels.get(c).map(|ch| &ch.extensions),
+        _ => None,
+    })
+}
+
+/// Splice `ext` into the serialized header of a streamed root or
+/// `<Network>` element, which lacks the child nodes written later.
+///
+/// Returns the header and the fragments anchored among those later
+/// children, with positions counted from the first of them.
+pub(crate) fn write_header_extensions(
+    xml: String,
+    ext: &Extensions,
+) -> Result<(String, Vec<ExtensionElement>)> {
+    if ext.is_empty() {
+        return Ok((xml, Vec::new()));
+    }
+    let children = child_count(&xml)?;
+    let (trailing, elements): (Vec<_>, Vec<_>) = ext
+        .elements
+        .iter()
+        .cloned()
+        .partition(|el| el.parent.is_empty() && el.position.is_none_or(|p| p > children));
+    let header = Extensions {
+        attributes: ext.attributes.clone(),
+        elements,
+        nested_attributes: ext.nested_attributes.clone(),
+    };
+    let xml = splice_extensions(&xml, NodePath::Root, |path| {
+        (path == NodePath::Root).then_some(&header)
+    })?;
+    let trailing = trailing
+        .into_iter()
+        .map(|el| ExtensionElement {
+            position: el.position.map(|p| p - children),
+            ..el
+        })
+        .collect();
+    Ok((xml, trailing))
+}
+
+/// Number of child elements of the top element of `xml`.
+fn child_count(xml: &str) -> Result<usize> {
+    let mut reader = Reader::from_str(xml);
+    let mut depth = 0usize;
+    let mut count = 0;
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(_) => {
+                count += usize::from(depth == 1);
+                depth += 1;
+            }
+            Event::Empty(_) => count += usize::from(depth == 1),
+            Event::End(_) => depth -= 1,
+            Event::Eof => return Ok(count),
+            _ => {}
+        }
+    }
+}
+
+/// Walk `xml`, whose top element sits at `root`, and splice in the
+/// extensions `lookup` returns for each owning node.
+fn splice_extensions<'a>(
+    xml: &str,
+    root: NodePath,
+    lookup: impl Fn(NodePath) -> Option<&'a Extensions>,
+) -> Result<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut writer = Writer::new(Vec::with_capacity(xml.len() + 256));
+    let mut stack: Vec<Frame<'a>> = Vec::new();
+
+    loop {
+        let event = reader.read_event().map_err(xml_error)?;
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let is_empty = matches!(event, Event::Empty(_));
+                let frame = match stack.last_mut() {
+                    Some(parent) => {
+                        parent.write_anchored(&mut writer);
+                        parent.child(e.local_name().as_ref())
+                    }
+                    None => Frame::new(root, String::new()),
+                };
+                let ext = lookup(frame.owner);
+                let mut frame = frame.with_fragments(ext);
+
+                let mut start = e.to_owned();
+                if let Some(ext) = ext {
+                    if frame.path.is_empty() {
+                        for (key, value) in &ext.attributes {
+                            start.push_attribute((key.as_str(), value.as_str()));
+                        }
+                    }
+                    for attr in &ext.nested_attributes {
+                        if attr.element == frame.path {
+                            start.push_attribute((attr.name.as_str(), attr.value.as_str()));
+                        }
+                    }
+                }
+
+                if !is_empty {
+                    writer.write_event(Event::Start(start))?;
+                    stack.push(frame);
+                } else if frame.pending.is_empty() {
+                    writer.write_event(Event::Empty(start))?;
+                } else {
+                    let end = start.to_end().into_owned();
+                    writer.write_event(Event::Start(start))?;
+                    frame.write_remaining(&mut writer);
+                    writer.write_event(Event::End(end))?;
+                }
+            }
+            Event::End(ref e) => {
+                if let Some(mut frame) = stack.pop() {
+                    frame.write_remaining(&mut writer);
+                }
+                writer.write_event(Event::End(e.borrow()))?;
+            }
+            Event::Eof => break,
+            other => writer.write_event(other)?,
+        }
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|e| StationXmlError::InvalidData(e.to_string()))
+}
+
+fn has_extensions(inv: &Inventory) -> bool {
+    !inv.extensions.is_empty()
+        || inv.networks.iter().any(|net| {
+            !net.extensions.is_empty()
+                || net.stations.iter().any(|sta| {
+                    !sta.extensions.is_empty()
+                        || sta.channels.iter().any(|ch| !ch.extensions.is_empty())
+                })
+        })
+}
+
+fn xml_error(e: quick_xml::Error) -> StationXmlError {
+    StationXmlError::XmlParse(e.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{attribute_spans, declares_foreign_namespace};
+    use crate::fdsn::FdsnWriteOptions;
+    use crate::fdsn::reader::read_from_str;
+    use crate::fdsn::writer::write_to_string_with;
+    use crate::inventory::{ExtensionAttribute, ExtensionElement};
+
+    const XML: &str = r#"<FDSNStationXML xmlns="http://www.fdsn.org/xml/station/1" xmlns:qc="https://example.org/qc" schemaVersion="1.2">
+  <Source>Test</Source>
+  <Created>2026-01-01T00:00:00Z</Created>
+  <Network code="XX" qc:owner="ops &amp; maintenance">
+    <Description>Test</Description>
+    <Station code="TEST">
+      <Latitude>0.0</Latitude>
+      <Longitude>0.0</Longitude>
+      <Elevation>0.0</Elevation>
+      <Site qc:verified="yes"><Name>Test Site</Name><qc:Vault>B</qc:Vault></Site>
+      <Channel code="HHZ" locationCode="00">
+        <Latitude>0.0</Latitude>
+        <Longitude>0.0</Longitude>
+        <Elevation>0.0</Elevation>
+        <Depth>0.0</Depth>
+        <Azimuth>0.0</Azimuth>
+        <Dip>-90.0</Dip>
+        <SampleRate>100.0</SampleRate>
+        <qc:Flag level="2">noisy</qc:Flag>
+        <qc:Checked/>
+        <Response>
+          <Stage number="1">
+            <qc:Note>swapped 2025</qc:Note>
+            <StageGain><Value>1.0</Value><Frequency>1.0</Frequency></StageGain>
+          </Stage>
+        </Response>
+      </Channel>
+      <Channel code="HHN" locationCode="00">
+        <Latitude>0.0</Latitude>
+        <Longitude>0.0</Longitude>
+        <Elevation>0.0</Elevation>
+        <Depth>0.0</Depth>
+        <Azimuth>0.0</Azimuth>
+        <Dip>0.0</Dip>
+        <SampleRate>100.0</SampleRate>
+      </Channel>
+      <qc:Score><qc:Value>0.9</qc:Value></qc:Score>
+    </Station>
+  </Network>
+</FDSNStationXML>"#;
+
+    #[test]
+    fn read_foreign_content() {
+        let inv = read_from_str(XML).unwrap();
+        assert_eq!(
+            inv.extensions.attributes,
+            vec![("xmlns:qc".to_string(), "https://example.org/qc".to_string())]
+        );
+        let net = &inv.networks[0];
+        assert_eq!(
+            net.extensions.attributes,
+            vec![("qc:owner".to_string(), "ops & maintenance".to_string())]
+        );
+        let sta = &net.stations[0];
+        assert_eq!(
+            sta.extensions.elements,
+            vec![
+                ExtensionElement {
+                    parent: "Site[0]".into(),
+                    position: Some(1),
+                    xml: "<qc:Vault>B</qc:Vault>".into(),
+                },
+                ExtensionElement {
+                    parent: String::new(),
+                    position: Some(6),
+                    xml: "<qc:Score><qc:Value>0.9</qc:Value></qc:Score>".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            sta.extensions.nested_attributes,
+            vec![ExtensionAttribute {
+                element: "Site[0]".into(),
+                name: "qc:verified".into(),
+                value: "yes".into(),
+            }]
+        );
+        let elements: Vec<_> = sta.channels[0]
+            .extensions
+            .elements
+            .iter()
+            .map(|el| (el.parent.as_str(), el.position, el.xml.as_str()))
+            .collect();
+        assert_eq!(
+            elements,
+            vec![
+                ("", Some(7), r#"<qc:Flag level="2">noisy</qc:Flag>"#),
+                ("", Some(7), "<qc:Checked/>"),
+                (
+                    "Response[0]/Stage[0]",
+                    Some(0),
+                    "<qc:Note>swapped 2025</qc:Note>"
+                ),
+            ]
+        );
+        assert!(sta.channels[1].extensions.is_empty());
+    }
+
+    #[test]
+    fn read_without_foreign_content() {
+        let xml = XML
+            .replace(r#" xmlns:qc="https://example.org/qc""#, "")
+            .replace(r#" qc:owner="ops &amp; maintenance""#, "")
+            .replace(r#" qc:verified="yes""#, "")
+            .replace("<qc:Vault>B</qc:Vault>", "")
+            .replace(r#"<qc:Flag level="2">noisy</qc:Flag>"#, "")
+            .replace("<qc:Checked/>", "")
+            .replace("<qc:Note>swapped 2025</qc:Note>", "")
+            .replace("<qc:Score><qc:Value>0.9</qc:Value></qc:Score>", "");
+        assert!(!declares_foreign_namespace(&xml));
+        let inv = read_from_str(&xml).unwrap();
+        assert!(inv.extensions.is_empty());
+        assert!(inv.networks[0].extensions.is_empty());
+        assert!(inv.networks[0].stations[0].extensions.is_empty());
+    }
+
+    #[test]
+    fn write_roundtrip() {
+        let inv = read_from_str(XML).unwrap();
+        let xml = write_to_string_with(&inv, &FdsnWriteOptions::default()).unwrap();
+        assert!(xml.contains(r#"xmlns:qc="https://example.org/qc""#));
+        assert!(xml.contains(r#"qc:owner="ops &amp; maintenance""#));
+        assert!(xml.contains(
+            r#"<Site qc:verified="yes"><Name>Test Site</Name><qc:Vault>B</qc:Vault></Site>"#
+        ));
+        assert!(xml.contains(r#"<qc:Flag level="2">noisy</qc:Flag><qc:Checked/><Response>"#));
+        assert!(xml.contains(r#"<Stage number="1"><qc:Note>swapped 2025</qc:Note><StageGain>"#));
+        assert!(xml.contains("<qc:Score><qc:Value>0.9</qc:Value></qc:Score></Station>"));
+
+        let inv2 = read_from_str(&xml).unwrap();
+        assert_eq!(inv, inv2);
+    }
+
+    #[test]
+    fn write_keeps_position_among_siblings() {
+        let xml = XML.replace(
+            "<Description>Test</Description>",
+            "<qc:Before/><Description>Test</Description>",
+        );
+        let inv = read_from_str(&xml).unwrap();
+        let out = write_to_string_with(&inv, &FdsnWriteOptions::default()).unwrap();
+        assert!(out.contains(r#"><qc:Before/><Description>Test</Description>"#));
+    }
+
+    #[test]
+    fn foreign_elements_named_like_fdsn_ones_stay_foreign() {
+        let xml = XML.replace(
+            "<Elevation>0.0</Elevation>\n      <Site",
+            "<Elevation>0.0</Elevation>\n      <qc:Description>vendor</qc:Description><qc:Elevation>3</qc:Elevation>\n      <Site",
+        );
+        let inv = read_from_str(&xml).unwrap();
+        let sta = &inv.networks[0].stations[0];
+        assert_eq!(sta.description, None);
+        assert_eq!(sta.elevation, 0.0);
+        let foreign: Vec<&str> = sta
+            .extensions
+            .elements
+            .iter()
+            .map(|el| el.xml.as_str())
+            .collect();
+        assert_eq!(
+            foreign[..2],
+            [
+                "<qc:Description>vendor</qc:Description>",
+                "<qc:Elevation>3</qc:Elevation>"
+            ]
+        );
+
+        let out = write_to_string_with(&inv, &FdsnWriteOptions::default()).unwrap();
+        assert_eq!(out.matches("vendor").count(), 1, "{out}");
+        assert!(!out.contains("<Description>vendor"), "{out}");
+        assert_eq!(read_from_str(&out).unwrap(), inv);
+    }
+
+    #[test]
+    fn foreign_attributes_named_like_fdsn_ones_stay_foreign() {
+        let xml = XML.replace(
+            r#"<Channel code="HHN" locationCode="00">"#,
+            r#"<Channel qc:code="X" code="HHN" locationCode="00">"#,
+        );
+        let inv = read_from_str(&xml).unwrap();
+        let ch = &inv.networks[0].stations[0].channels[1];
+        assert_eq!(ch.code, "HHN");
+        assert_eq!(
+            ch.extensions.attributes,
+            vec![("qc:code".to_string(), "X".to_string())]
+        );
+    }
+
+    #[test]
+    fn finds_attribute_spans() {
+        let tag = r#"<a x="1" qc:y = 'a>b' z="">"#;
+        let spans: Vec<(&str, &str)> = attribute_spans(tag)
+            .into_iter()
+            .map(|(name, span)| (name, &tag[span]))
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                ("x", r#"x="1""#),
+                ("qc:y", "qc:y = 'a>b'"),
+                ("z", r#"z="""#)
+            ]
+        );
+        assert!(attribute_spans("<a/>").is_empty());
+    }
+
+    #[test]
+    fn detects_foreign_namespace_declarations() {
+        assert!(!declares_foreign_namespace(
+            r#"<FDSNStationXML xmlns="http://www.fdsn.org/xml/station/1"/>"#
+        ));
+        assert!(declares_foreign_namespace(
+            r#"<FDSNStationXML xmlns="http://www.fdsn.org/xml/station/1" xmlns:qc='urn:qc'/>"#
+        ));
+        assert!(declares_foreign_namespace(
+            r#"<Channel><Flag xmlns="urn:qc"/></Channel>"#
+        ));
+    }
+}
@@ -1,17 +1,24 @@
-//! FDSN StationXML 1.2 format backend.
+//! FDSN StationXML 1.x format backend.
 //!
-//! Implements [`StationXmlFormat`] for reading and writing
-//! FDSN StationXML 1.2 documents.
+//! Implements [`StationXmlFormat`] for reading FDSN StationXML 1.0, 1.1 and
+//! 1.2 documents and writing any of those versions (1.2 by default).
 
+pub(crate) mod extensions;
 pub(crate) mod reader;
+mod stream;
 pub(crate) mod types;
 pub(crate) mod writer;
 
+use chrono::{DateTime, Utc};
+
 use crate::error::Result;
-use crate::format::StationXmlFormat;
+use crate::format::{ReadOptions, ReadOutcome, StationXmlFormat};
 use crate::inventory::Inventory;
+use crate::style::XmlStyle;
+
+pub use stream::FdsnStreamWriter;
 
-/// FDSN StationXML 1.2 format marker.
+/// FDSN StationXML format marker.
 ///
 /// Use this with [`StationXmlFormat`] methods to read/write FDSN StationXML.
 ///
@@ -23,6 +30,134 @@ use crate::inventory::Inventory;
 /// ```
 pub struct Fdsn;
 
+impl Fdsn {
+    /// Serialize an [`Inventory`] to an FDSN StationXML string with explicit options.
+    ///
+    /// ```
+    /// use stationxml_rs::{Fdsn, FdsnWriteOptions, Inventory};
+    ///
+    /// let inv = Inventory::builder().source("Test").build();
+    /// let opts = FdsnWriteOptions::default().module("my-tool 0.3");
+    /// let xml = Fdsn::write_to_string_with(&inv, &opts).unwrap();
+    /// assert!(xml.contains("<Module>my-tool 0.3</Module>"));
+    /// ```
+    pub fn write_to_string_with(
+        inventory: &Inventory,
+        options: &FdsnWriteOptions,
+    ) -> Result<String> {
+        writer::write_to_string_with(inventory, options)
+    }
+
+    /// Serialize an [`Inventory`] to an [`io::Write`](std::io::Write) with
+    /// explicit options, one station at a time.
+    ///
+    /// Produces the same bytes as [`write_to_string_with`](Self::write_to_string_with)
+    /// without holding the whole document in memory. See [`FdsnStreamWriter`]
+    /// to write networks and stations as they are produced.
+    pub fn write_to_writer_with<W: std::io::Write>(
+        inventory: &Inventory,
+        writer: W,
+        options: &FdsnWriteOptions,
+    ) -> Result<()> {
+        let mut stream = FdsnStreamWriter::new(writer, inventory, options)?;
+        for net in &inventory.networks {
+            stream.write_network(net)?;
+        }
+        stream.finish()?;
+        Ok(())
+    }
+}
+
+/// FDSN StationXML schema version.
+///
+/// All 1.x versions share the `http://www.fdsn.org/xml/station/1` namespace
+/// and differ only in a handful of elements; the version is carried in the
+/// root `schemaVersion` attribute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FdsnVersion {
+    /// StationXML 1.0
+    V1_0,
+    /// StationXML 1.1 — adds `WaterLevel`, `Comment.subject` and repeatable
+    /// `Equipment`, drops `Channel.StorageFormat`, `Station.CreationDate` optional
+    V1_1,
+    /// StationXML 1.2
+    #[default]
+    V1_2,
+}
+
+impl FdsnVersion {
+    /// The `schemaVersion` attribute value, e.g. `"1.2"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V1_0 => "1.0",
+            Self::V1_1 => "1.1",
+            Self::V1_2 => "1.2",
+        }
+    }
+
+    /// Parse a `schemaVersion` attribute value. Returns `None` for unknown versions.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim() {
+            "1" | "1.0" => Some(Self::V1_0),
+            "1.1" => Some(Self::V1_1),
+            "1.2" => Some(Self::V1_2),
+            _ => None,
+        }
+    }
+}
+
+/// Options controlling how FDSN StationXML is written.
+///
+/// `module` and `module_uri` stamp the generating software into the document.
+/// When unset, the values carried on the [`Inventory`] (e.g. from a previously
+/// read document) are written instead.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FdsnWriteOptions {
+    /// Target schema version (default 1.2)
+    pub version: FdsnVersion,
+    /// Overrides [`Inventory::module`] when set
+    pub module: Option<String>,
+    /// Overrides [`Inventory::module_uri`] when set
+    pub module_uri: Option<String>,
+    /// Indentation, float and attribute formatting (default compact)
+    pub style: XmlStyle,
+    /// End date written for open network, station and channel epochs, for
+    /// consumers that expect a far-future sentinel; omitted when unset
+    pub open_end: Option<DateTime<Utc>>,
+}
+
+impl FdsnWriteOptions {
+    /// Set the target schema version.
+    pub fn version(mut self, version: FdsnVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Set the `<Module>` written to the document.
+    pub fn module(mut self, module: impl Into<String>) -> Self {
+        self.module = Some(module.into());
+        self
+    }
+
+    /// Set the `<ModuleURI>` written to the document.
+    pub fn module_uri(mut self, uri: impl Into<String>) -> Self {
+        self.module_uri = Some(uri.into());
+        self
+    }
+
+    /// Set the output formatting, e.g. [`XmlStyle::pretty`].
+    pub fn style(mut self, style: XmlStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Write `sentinel` as the end date of open epochs.
+    pub fn open_end(mut self, sentinel: DateTime<Utc>) -> Self {
+        self.open_end = Some(sentinel);
+        self
+    }
+}
+
 impl StationXmlFormat for Fdsn {
     fn read_from_str(xml: &str) -> Result<Inventory> {
         reader::read_from_str(xml)
@@ -32,7 +167,15 @@ impl StationXmlFormat for Fdsn {
         reader::read_from_bytes(bytes)
     }
 
+    fn read_from_str_with(xml: &str, options: &ReadOptions) -> Result<ReadOutcome> {
+        reader::read_from_str_with(xml, options)
+    }
+
     fn write_to_string(inventory: &Inventory) -> Result<String> {
-        writer::write_to_string(inventory)
+        writer::write_to_string_with(inventory, &FdsnWriteOptions::default())
+    }
+
+    fn write_to_writer<W: std::io::Write>(inventory: &Inventory, writer: W) -> Result<()> {
+        Self::write_to_writer_with(inventory, writer, &FdsnWriteOptions::default())
     }
 }
@@ -1,15 +1,56 @@
 //! FDSN StationXML reader: XML → fdsn types → Inventory.
 
-use crate::datetime::parse_datetime_opt;
+use quick_xml::events::BytesStart;
+
+use crate::datetime::{parse_datetime, parse_datetime_opt};
 use crate::error::{Result, StationXmlError};
+use crate::format::{ReadOptions, ReadOutcome};
 use crate::inventory::*;
+use crate::lenient::{self, Rereading, Warnings};
+use crate::locate::{self, attribute};
 
+use super::extensions::read_extensions;
 use super::types::*;
 
 /// Parse FDSN StationXML string into an [`Inventory`].
+///
+/// Accepts schema versions 1.0, 1.1 and 1.2. Unknown 1.x minor versions are
+/// read on a best-effort basis; other major versions are rejected.
+///
+/// Errors carry the line/column and `NET.STA.LOC.CHA` path of the offending
+/// element where they can be determined.
 pub(crate) fn read_from_str(xml: &str) -> Result<Inventory> {
-    let fdsn: FdsnStationXml = quick_xml::de::from_str(xml)?;
-    fdsn_to_inventory(fdsn)
+    let mut warnings = Warnings::strict();
+    read_document(xml, &mut warnings).map_err(|e| warnings.locate(e, xml, path_segment))
+}
+
+/// Parse FDSN StationXML with explicit options, skipping failed items in
+/// lenient mode and normalizing sentinel end dates.
+pub(crate) fn read_from_str_with(xml: &str, options: &ReadOptions) -> Result<ReadOutcome> {
+    let outcome = if options.lenient {
+        // Skipped elements are blanked so extensions stay aligned
+        lenient::read(xml, path_segment, Rereading::Blank, read_document)?
+    } else {
+        ReadOutcome {
+            inventory: read_from_str(xml)?,
+            warnings: Vec::new(),
+        }
+    };
+    Ok(options.normalize(outcome))
+}
+
+fn read_document(xml: &str, warnings: &mut Warnings) -> Result<Inventory> {
+    // Malformed XML is reported by the serde pass, with its location
+    let foreign = read_extensions(xml);
+    let stripped = match &foreign {
+        Ok(foreign) => foreign.strip(xml),
+        Err(_) => xml.into(),
+    };
+    let fdsn: FdsnStationXml = locate::deserialize(&stripped, path_segment)?;
+    check_schema_version(&fdsn.schema_version)?;
+    let mut inv = fdsn_to_inventory(fdsn, warnings)?;
+    foreign?.attach(&mut inv);
+    Ok(inv)
 }
 
 /// Parse FDSN StationXML bytes into an [`Inventory`].
@@ -19,42 +60,92 @@ pub(crate) fn read_from_bytes(bytes: &[u8]) -> Result<Inventory> {
     read_from_str(xml)
 }
 
+/// Reject documents whose `schemaVersion` is not a 1.x version.
+fn check_schema_version(version: &str) -> Result<()> {
+    if version.trim().split('.').next() == Some("1") {
+        Ok(())
+    } else {
+        Err(StationXmlError::InvalidData(format!(
+            "unsupported FDSN StationXML schemaVersion: '{version}'"
+        )))
+    }
+}
+
+/// Path segment an FDSN element contributes to error paths, matching the
+/// paths attached during conversion.
+fn path_segment(e: &BytesStart<'_>) -> Option<String> {
+    match e.local_name().as_ref() {
+        b"Network" => attribute(e, b"code"),
+        b"Station" => attribute(e, b"code").map(|code| format!(".{code}")),
+        b"Channel" => Some(format!(
+            ".{}.{}",
+            attribute(e, b"locationCode").unwrap_or_default(),
+            attribute(e, b"code")?
+        )),
+        // Dropped as a whole in lenient mode, adding nothing to the path
+        b"Response" => Some(String::new()),
+        b"Stage" => attribute(e, b"number").map(|n| format!(" stage {n}")),
+        _ => None,
+    }
+}
+
 // ─── Conversion functions ───────────────────────────────────────────
 
-fn fdsn_to_inventory(fdsn: FdsnStationXml) -> Result<Inventory> {
+fn fdsn_to_inventory(fdsn: FdsnStationXml, w: &mut Warnings) -> Result<Inventory> {
     Ok(Inventory {
         source: fdsn.source,
         sender: fdsn.sender,
         created: parse_datetime_opt(&Some(fdsn.created))?,
-        networks: fdsn
-            .networks
-            .into_iter()
-            .map(convert_network)
-            .collect::<Result<Vec<_>>>()?,
+        module: fdsn.module,
+        module_uri: fdsn.module_uri,
+        schema_version: Some(fdsn.schema_version),
+        extensions: Extensions::default(),
+        networks: w.collect(fdsn.networks, |net, w| {
+            let path = net.code.clone();
+            convert_network(net, &path, w).map_err(|e| e.at_path(&path))
+        })?,
     })
 }
 
-fn convert_network(net: FdsnNetwork) -> Result<Network> {
+fn convert_network(net: FdsnNetwork, path: &str, w: &mut Warnings) -> Result<Network> {
     Ok(Network {
         code: net.code,
         description: net.description,
-        start_date: parse_datetime_opt(&net.start_date)?,
-        end_date: parse_datetime_opt(&net.end_date)?,
-        stations: net
-            .stations
+        start_date: w.optional(path, parse_datetime_opt(&net.start_date))?,
+        end_date: w.optional(path, parse_datetime_opt(&net.end_date))?,
+        restricted_status: net
+            .restricted_status
+            .as_deref()
+            .map(parse_restricted_status)
+            .transpose()?,
+        network_type: None,
+        network_class: None,
+        archive: None,
+        shared: None,
+        public_id: None,
+        comments: net
+            .comments
             .into_iter()
-            .map(convert_station)
+            .map(|c| convert_comment(c, path, w))
             .collect::<Result<Vec<_>>>()?,
+        extensions: Extensions::default(),
+        stations: w.collect(net.stations, |sta, w| {
+            let path = format!("{path}.{}", sta.code);
+            convert_station(sta, &path, w).map_err(|e| e.at_path(&path))
+        })?,
     })
 }
 
-fn convert_station(sta: FdsnStation) -> Result<Station> {
+fn convert_station(sta: FdsnStation, path: &str, w: &mut Warnings) -> Result<Station> {
     Ok(Station {
         code: sta.code,
-        description: None,
+        description: sta.description,
         latitude: sta.latitude.value,
         longitude: sta.longitude.value,
         elevation: sta.elevation.value,
+        latitude_uncertainty: convert_uncertainty(&sta.latitude),
+        longitude_uncertainty: convert_uncertainty(&sta.longitude),
+        elevation_uncertainty: convert_uncertainty(&sta.elevation),
         site: Site {
             name: sta.site.name,
             description: sta.site.description,
@@ -63,18 +154,67 @@ fn convert_station(sta: FdsnStation) -> Result<Station> {
             region: sta.site.region,
             country: sta.site.country,
         },
-        start_date: parse_datetime_opt(&sta.start_date)?,
-        end_date: parse_datetime_opt(&sta.end_date)?,
-        creation_date: parse_datetime_opt(&sta.creation_date)?,
-        channels: sta
-            .channels
+        water_level: sta.water_level.map(|v| v.value),
+        vault: sta.vault,
+        geology: sta.geology,
+        equipment: sta
+            .equipment
             .into_iter()
-            .map(convert_channel)
+            .map(|eq| convert_equipment(eq, path, w))
             .collect::<Result<Vec<_>>>()?,
+        external_references: sta
+            .external_references
+            .into_iter()
+            .map(|r| ExternalReference {
+                uri: r.uri,
+                description: r.description,
+            })
+            .collect(),
+        start_date: w.optional(path, parse_datetime_opt(&sta.start_date))?,
+        end_date: w.optional(path, parse_datetime_opt(&sta.end_date))?,
+        creation_date: w.optional(path, parse_datetime_opt(&sta.creation_date))?,
+        restricted_status: sta
+            .restricted_status
+            .as_deref()
+            .map(parse_restricted_status)
+            .transpose()?,
+        station_type: None,
+        affiliation: None,
+        archive: None,
+        archive_network_code: None,
+        shared: None,
+        public_id: None,
+        comments: sta
+            .comments
+            .into_iter()
+            .map(|c| convert_comment(c, path, w))
+            .collect::<Result<Vec<_>>>()?,
+        extensions: Extensions::default(),
+        channels: w.collect(sta.channels, |ch, w| {
+            let path = format!("{path}.{}.{}", ch.location_code, ch.code);
+            convert_channel(ch, &path, w).map_err(|e| e.at_path(&path))
+        })?,
     })
 }
 
-fn convert_channel(ch: FdsnChannel) -> Result<Channel> {
+fn convert_channel(ch: FdsnChannel, path: &str, w: &mut Warnings) -> Result<Channel> {
+    let mut equipment = ch
+        .equipment
+        .into_iter()
+        .map(|eq| convert_equipment(eq, path, w))
+        .collect::<Result<Vec<_>>>()?;
+    // An auxiliary stream is written with its device as the first Equipment
+    // and its source in the Description
+    let aux_source = ch
+        .description
+        .as_deref()
+        .and_then(parse_aux_source)
+        .filter(|_| {
+            equipment
+                .first()
+                .is_some_and(|eq| eq.equipment_type.as_deref() == Some("AuxDevice"))
+        });
+    let aux_device = aux_source.as_ref().map(|_| equipment.remove(0));
     Ok(Channel {
         code: ch.code,
         location_code: ch.location_code,
@@ -84,35 +224,161 @@ fn convert_channel(ch: FdsnChannel) -> Result<Channel> {
         depth: ch.depth.value,
         azimuth: ch.azimuth.value,
         dip: ch.dip.value,
-        sample_rate: ch.sample_rate.value,
-        start_date: parse_datetime_opt(&ch.start_date)?,
-        end_date: parse_datetime_opt(&ch.end_date)?,
-        sensor: ch.sensor.map(convert_equipment),
-        data_logger: ch.data_logger.map(convert_equipment),
-        response: ch.response.map(convert_response).transpose()?,
+        latitude_uncertainty: convert_uncertainty(&ch.latitude),
+        longitude_uncertainty: convert_uncertainty(&ch.longitude),
+        elevation_uncertainty: convert_uncertainty(&ch.elevation),
+        depth_uncertainty: convert_uncertainty(&ch.depth),
+        azimuth_uncertainty: convert_uncertainty(&ch.azimuth),
+        dip_uncertainty: convert_uncertainty(&ch.dip),
+        // SampleRate is optional in every 1.x schema; 0.0 means "unknown"
+        sample_rate: ch.sample_rate.map(|v| v.value).unwrap_or(0.0),
+        channel_types: ch
+            .channel_types
+            .iter()
+            .map(|t| parse_channel_type(t))
+            .collect::<Result<Vec<_>>>()?,
+        storage_format: ch.storage_format,
+        clock_drift: ch.clock_drift.map(|v| v.value),
+        calibration_units: ch.calibration_units.map(convert_units),
+        water_level: ch.water_level.map(|v| v.value),
+        start_date: w.optional(path, parse_datetime_opt(&ch.start_date))?,
+        end_date: w.optional(path, parse_datetime_opt(&ch.end_date))?,
+        restricted_status: ch
+            .restricted_status
+            .as_deref()
+            .map(parse_restricted_status)
+            .transpose()?,
+        shared: None,
+        sensor_location_id: None,
+        sensor_location_comments: vec![],
+        comments: ch
+            .comments
+            .into_iter()
+            .map(|c| convert_comment(c, path, w))
+            .collect::<Result<Vec<_>>>()?,
+        sensor: ch
+            .sensor
+            .map(|eq| convert_equipment(eq, path, w))
+            .transpose()?,
+        pre_amplifier: ch
+            .pre_amplifier
+            .map(|eq| convert_equipment(eq, path, w))
+            .transpose()?,
+        data_logger: ch
+            .data_logger
+            .map(|eq| convert_equipment(eq, path, w))
+            .transpose()?,
+        equipment,
+        aux_device,
+        aux_source,
+        extensions: Extensions::default(),
+        // A response missing a stage would be wrong, not just incomplete: in
+        // lenient mode a failing stage drops the whole response
+        response: match ch.response {
+            Some(resp) => w.nested(0, |w| convert_response(resp, path, w))?,
+            None => None,
+        },
+    })
+}
+
+/// Parse a channel description written by
+/// [`aux_source_description`](super::writer::aux_source_description).
+/// Text that would not be written back unchanged is not an auxiliary source.
+fn parse_aux_source(text: &str) -> Option<AuxSource> {
+    let mut rest = text.strip_prefix("Auxiliary source ")?;
+    let mut take = |label: &str| {
+        let (head, value) = rest.rsplit_once(label)?;
+        rest = head;
+        Some(value.to_string())
+    };
+    let remark = take(", remark: ");
+    let conversion = take(", conversion: ");
+    let unit = rest
+        .strip_suffix(')')
+        .and_then(|head| head.rsplit_once(" ("))
+        .map(|(head, unit)| {
+            rest = head;
+            unit.to_string()
+        });
+    let (name, description) = match rest.split_once(": ") {
+        Some((name, description)) => (name, Some(description.to_string())),
+        None => (rest, None),
+    };
+    let source = AuxSource {
+        name: name.to_string(),
+        description,
+        unit,
+        conversion,
+        remark,
+    };
+    (super::writer::aux_source_description(&source) == text).then_some(source)
+}
+
+fn convert_comment(c: FdsnComment, path: &str, w: &mut Warnings) -> Result<Comment> {
+    Ok(Comment {
+        value: c.value,
+        id: c.id.map(|id| id.to_string()),
+        subject: c.subject,
+        begin_effective_time: w.optional(path, parse_datetime_opt(&c.begin_effective_time))?,
+        end_effective_time: w.optional(path, parse_datetime_opt(&c.end_effective_time))?,
+        author: c
+            .authors
+            .into_iter()
+            .find_map(|a| a.names.into_iter().next()),
     })
 }
 
-fn convert_equipment(eq: FdsnEquipment) -> Equipment {
-    Equipment {
+fn convert_equipment(eq: FdsnEquipment, path: &str, w: &mut Warnings) -> Result<Equipment> {
+    Ok(Equipment {
         equipment_type: eq.equipment_type,
         description: eq.description,
         manufacturer: eq.manufacturer,
         vendor: eq.vendor,
         model: eq.model,
         serial_number: eq.serial_number,
-        installation_date: None, // datetime parsing for equipment dates
-        removal_date: None,
+        channel: None,
+        installation_date: w.optional(path, parse_datetime_opt(&eq.installation_date))?,
+        removal_date: w.optional(path, parse_datetime_opt(&eq.removal_date))?,
+        calibration_dates: eq
+            .calibration_dates
+            .iter()
+            .filter_map(|s| w.optional(path, parse_datetime(s).map(Some)).transpose())
+            .collect::<Result<Vec<_>>>()?,
+        calibrations: vec![],
+        decimations: vec![],
+        remark: None,
+        resource_id: eq.resource_id,
+    })
+}
+
+/// Extract the optional attributes of a float element, if any are set.
+fn convert_uncertainty(fv: &FdsnFloatValue) -> Option<Uncertainty> {
+    if fv.plus_error.is_none()
+        && fv.minus_error.is_none()
+        && fv.unit.is_none()
+        && fv.datum.is_none()
+    {
+        return None;
     }
+    Some(Uncertainty {
+        plus_error: fv.plus_error,
+        minus_error: fv.minus_error,
+        unit: fv.unit.clone(),
+        datum: fv.datum.clone(),
+    })
 }
 
-fn convert_response(resp: FdsnResponse) -> Result<Response> {
+fn convert_response(resp: FdsnResponse, path: &str, w: &mut Warnings) -> Result<Response> {
     Ok(Response {
         instrument_sensitivity: resp.instrument_sensitivity.map(convert_sensitivity),
         stages: resp
             .stages
             .into_iter()
-            .map(convert_stage)
+            .enumerate()
+            .map(|(i, stage)| {
+                let path = format!("{path} stage {}", stage.number);
+                w.part(i, |_| convert_stage(stage).map_err(|e| e.at_path(&path)))
+            })
             .collect::<Result<Vec<_>>>()?,
     })
 }
@@ -140,6 +406,7 @@ fn convert_stage(stage: FdsnResponseStage) -> Result<ResponseStage> {
             value: g.value,
             frequency: g.frequency,
         }),
+        nominal_gain: None,
         poles_zeros: stage.poles_zeros.map(convert_poles_zeros).transpose()?,
         coefficients: stage.coefficients.map(convert_coefficients).transpose()?,
         fir: stage.fir.map(convert_fir).transpose()?,
@@ -167,6 +434,8 @@ fn convert_poles_zeros(pz: FdsnPolesZeros) -> Result<PolesZeros> {
                 number: z.number,
                 real: z.real.value,
                 imaginary: z.imaginary.value,
+                real_uncertainty: convert_uncertainty(&z.real),
+                imaginary_uncertainty: convert_uncertainty(&z.imaginary),
             })
             .collect(),
         poles: pz
@@ -176,8 +445,11 @@ fn convert_poles_zeros(pz: FdsnPolesZeros) -> Result<PolesZeros> {
                 number: p.number,
                 real: p.real.value,
                 imaginary: p.imaginary.value,
+                real_uncertainty: convert_uncertainty(&p.real),
+                imaginary_uncertainty: convert_uncertainty(&p.imaginary),
             })
             .collect(),
+        resource_id: pz.resource_id,
     })
 }
 
@@ -201,11 +473,42 @@ fn convert_fir(fir: FdsnFIR) -> Result<FIR> {
             .into_iter()
             .map(|v| v.value)
             .collect(),
+        resource_id: fir.resource_id,
     })
 }
 
 // ─── Enum parsing ───────────────────────────────────────────────────
 
+fn parse_restricted_status(s: &str) -> Result<RestrictedStatus> {
+    match s {
+        "open" => Ok(RestrictedStatus::Open),
+        "closed" => Ok(RestrictedStatus::Closed),
+        "partial" => Ok(RestrictedStatus::Partial),
+        _ => Err(StationXmlError::InvalidData(format!(
+            "unknown restrictedStatus: '{s}'"
+        ))),
+    }
+}
+
+fn parse_channel_type(s: &str) -> Result<ChannelType> {
+    match s {
+        "TRIGGERED" => Ok(ChannelType::Triggered),
+        "CONTINUOUS" => Ok(ChannelType::Continuous),
+        "HEALTH" => Ok(ChannelType::Health),
+        "GEOPHYSICAL" => Ok(ChannelType::Geophysical),
+        "WEATHER" => Ok(ChannelType::Weather),
+        "FLAG" => Ok(ChannelType::Flag),
+        "SYNTHESIZED" => Ok(ChannelType::Synthesized),
+        "INPUT" => Ok(ChannelType::Input),
+        "EXPERIMENTAL" => Ok(ChannelType::Experimental),
+        "MAINTENANCE" => Ok(ChannelType::Maintenance),
+        "BEAM" => Ok(ChannelType::Beam),
+        _ => Err(StationXmlError::InvalidData(format!(
+            "unknown channel Type: '{s}'"
+        ))),
+    }
+}
+
 fn parse_pz_transfer_function(s: &str) -> Result<PzTransferFunction> {
     match s {
         "LAPLACE (RADIANS/SECOND)" => Ok(PzTransferFunction::LaplaceRadians),
@@ -243,6 +546,26 @@ fn parse_symmetry(s: &str) -> Result<Symmetry> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parses_aux_source_descriptions() {
+        let source = AuxSource {
+            name: "pressure".into(),
+            description: Some("Barometric pressure".into()),
+            unit: Some("hPa".into()),
+            conversion: Some("0.01 hPa/count".into()),
+            remark: Some("outside, north wall".into()),
+        };
+        let text = crate::fdsn::writer::aux_source_description(&source);
+        assert_eq!(parse_aux_source(&text), Some(source));
+
+        let bare = AuxSource {
+            name: "temperature".into(),
+            ..Default::default()
+        };
+        assert_eq!(parse_aux_source("Auxiliary source temperature"), Some(bare));
+        assert_eq!(parse_aux_source("Broadband seismometer"), None);
+    }
+
     #[test]
     fn read_sample_xml() {
         let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -407,6 +730,155 @@ mod tests {
         assert!((s2.stage_gain.as_ref().unwrap().value - 1678801.5).abs() < 0.1);
     }
 
+    #[test]
+    fn read_equipment_dates() {
+        let xml = r#"<FDSNStationXML schemaVersion="1.2">
+  <Source>Test</Source>
+  <Created>2026-01-01T00:00:00Z</Created>
+  <Network code="XX">
+    <Station code="TEST">
+      <Latitude>0.0</Latitude>
+      <Longitude>0.0</Longitude>
+      <Elevation>0.0</Elevation>
+      <Site><Name>Test Site</Name></Site>
+      <Channel code="SHZ" locationCode="00">
+        <Latitude>0.0</Latitude>
+        <Longitude>0.0</Longitude>
+        <Elevation>0.0</Elevation>
+        <Depth>0.0</Depth>
+        <Azimuth>0.0</Azimuth>
+        <Dip>-90.0</Dip>
+        <SampleRate>100.0</SampleRate>
+        <Sensor resourceId="Sensor#1">
+          <Model>GS-11D</Model>
+          <InstallationDate>2024-06-01T00:00:00Z</InstallationDate>
+          <RemovalDate>2025-06-01T00:00:00Z</RemovalDate>
+          <CalibrationDate>2024-05-20T00:00:00Z</CalibrationDate>
+        </Sensor>
+      </Channel>
+    </Station>
+  </Network>
+</FDSNStationXML>"#;
+        let inv = read_from_str(xml).unwrap();
+        let sensor = inv.networks[0].stations[0].channels[0]
+            .sensor
+            .as_ref()
+            .unwrap();
+        assert_eq!(sensor.resource_id.as_deref(), Some("Sensor#1"));
+        assert_eq!(
+            sensor.installation_date,
+            Some(parse_datetime("2024-06-01T00:00:00Z").unwrap())
+        );
+        assert_eq!(
+            sensor.removal_date,
+            Some(parse_datetime("2025-06-01T00:00:00Z").unwrap())
+        );
+        assert_eq!(
+            sensor.calibration_dates,
+            vec![parse_datetime("2024-05-20T00:00:00Z").unwrap()]
+        );
+    }
+
+    #[test]
+    fn read_float_uncertainty() {
+        let xml = r#"<FDSNStationXML schemaVersion="1.2">
+  <Source>Test</Source>
+  <Created>2026-01-01T00:00:00Z</Created>
+  <Network code="XX">
+    <Station code="TEST">
+      <Latitude datum="NAD83" plusError="0.001" minusError="0.002">45.0</Latitude>
+      <Longitude unit="DEGREES">-120.0</Longitude>
+      <Elevation>100.0</Elevation>
+      <Site><Name>Test Site</Name></Site>
+      <Channel code="SHZ" locationCode="00">
+        <Latitude>45.0</Latitude>
+        <Longitude>-120.0</Longitude>
+        <Elevation>100.0</Elevation>
+        <Depth plusError="0.5" minusError="0.5">2.0</Depth>
+        <Azimuth plusError="1.5">0.0</Azimuth>
+        <Dip>-90.0</Dip>
+        <SampleRate>100.0</SampleRate>
+      </Channel>
+    </Station>
+  </Network>
+</FDSNStationXML>"#;
+        let inv = read_from_str(xml).unwrap();
+        let sta = &inv.networks[0].stations[0];
+        let lat = sta.latitude_uncertainty.as_ref().unwrap();
+        assert_eq!(lat.datum.as_deref(), Some("NAD83"));
+        assert_eq!(lat.plus_error, Some(0.001));
+        assert_eq!(lat.minus_error, Some(0.002));
+        assert_eq!(
+            sta.longitude_uncertainty.as_ref().unwrap().unit.as_deref(),
+            Some("DEGREES")
+        );
+        assert!(sta.elevation_uncertainty.is_none());
+
+        let ch = &sta.channels[0];
+        assert_eq!(ch.depth_uncertainty.as_ref().unwrap().plus_error, Some(0.5));
+        assert_eq!(
+            ch.azimuth_uncertainty.as_ref().unwrap().plus_error,
+            Some(1.5)
+        );
+        assert!(ch.latitude_uncertainty.is_none());
+        assert!(ch.dip_uncertainty.is_none());
+    }
+
+    #[test]
+    fn read_channel_metadata() {
+        let xml = r#"<FDSNStationXML schemaVersion="1.2">
+  <Source>Test</Source>
+  <Created>2026-01-01T00:00:00Z</Created>
+  <Network code="XX">
+    <Station code="TEST">
+      <Latitude>0.0</Latitude>
+      <Longitude>0.0</Longitude>
+      <Elevation>0.0</Elevation>
+      <Site><Name>Test Site</Name></Site>
+      <Channel code="HHZ" locationCode="00">
+        <Latitude>0.0</Latitude>
+        <Longitude>0.0</Longitude>
+        <Elevation>-1200.0</Elevation>
+        <Depth>0.0</Depth>
+        <Azimuth>0.0</Azimuth>
+        <Dip>-90.0</Dip>
+        <WaterLevel>0.0</WaterLevel>
+        <Type>CONTINUOUS</Type>
+        <Type>GEOPHYSICAL</Type>
+        <SampleRate>100.0</SampleRate>
+        <ClockDrift unit="SECONDS/SAMPLE">0.0001</ClockDrift>
+        <CalibrationUnits><Name>A</Name><Description>Amperes</Description></CalibrationUnits>
+        <Sensor><Model>STS-2</Model></Sensor>
+        <PreAmplifier><Model>PA-1</Model></PreAmplifier>
+        <DataLogger><Model>Q330</Model></DataLogger>
+        <Equipment><Type>GPS</Type><Model>GPS-16</Model></Equipment>
+        <Equipment><Type>Power</Type></Equipment>
+      </Channel>
+    </Station>
+  </Network>
+</FDSNStationXML>"#;
+        let inv = read_from_str(xml).unwrap();
+        let ch = &inv.networks[0].stations[0].channels[0];
+        assert_eq!(
+            ch.channel_types,
+            vec![ChannelType::Continuous, ChannelType::Geophysical]
+        );
+        assert_eq!(ch.water_level, Some(0.0));
+        assert_eq!(ch.clock_drift, Some(0.0001));
+        assert_eq!(ch.calibration_units.as_ref().unwrap().name, "A");
+        assert_eq!(
+            ch.pre_amplifier.as_ref().unwrap().model.as_deref(),
+            Some("PA-1")
+        );
+        assert_eq!(ch.equipment.len(), 2);
+        assert_eq!(ch.equipment[0].model.as_deref(), Some("GPS-16"));
+    }
+
+    #[test]
+    fn read_unknown_channel_type_fails() {
+        assert!(parse_channel_type("BOGUS").is_err());
+    }
+
     #[test]
     fn read_from_bytes_works() {
         let xml = r#"<?xml version="1.0"?>
@@ -417,4 +889,47 @@ mod tests {
         let inv = read_from_bytes(xml.as_bytes()).unwrap();
         assert_eq!(inv.source, "Test");
     }
+
+    #[test]
+    fn read_schema_1_0() {
+        let xml = r#"<FDSNStationXML xmlns="http://www.fdsn.org/xml/station/1" schemaVersion="1.0">
+  <Source>IRIS-DMC</Source>
+  <Created>2014-03-01T00:00:00</Created>
+  <Network code="IU">
+    <Station code="ANMO">
+      <Latitude>34.9459</Latitude>
+      <Longitude>-106.4572</Longitude>
+      <Elevation>1850.0</Elevation>
+      <Site><Name>Albuquerque, New Mexico, USA</Name></Site>
+      <Vault>Underground</Vault>
+      <Geology>Granite</Geology>
+      <CreationDate>1989-08-29T00:00:00</CreationDate>
+      <Channel code="LOG" locationCode="">
+        <Latitude>34.9459</Latitude>
+        <Longitude>-106.4572</Longitude>
+        <Elevation>1850.0</Elevation>
+        <Depth>0.0</Depth>
+        <Azimuth>0.0</Azimuth>
+        <Dip>0.0</Dip>
+        <Type>HEALTH</Type>
+        <StorageFormat>Steim2</StorageFormat>
+      </Channel>
+    </Station>
+  </Network>
+</FDSNStationXML>"#;
+        let inv = read_from_str(xml).unwrap();
+        assert_eq!(inv.schema_version.as_deref(), Some("1.0"));
+        let ch = &inv.networks[0].stations[0].channels[0];
+        assert_eq!(ch.storage_format.as_deref(), Some("Steim2"));
+        assert_eq!(ch.sample_rate, 0.0);
+    }
+
+    #[test]
+    fn read_unsupported_major_version_fails() {
+        let xml = r#"<FDSNStationXML schemaVersion="2.0">
+  <Source>Test</Source>
+  <Created>2026-01-01T00:00:00Z</Created>
+</FDSNStationXML>"#;
+        assert!(read_from_str(xml).is_err());
+    }
 }
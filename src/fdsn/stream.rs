@@ -0,0 +1,320 @@
+//! Streaming FDSN StationXML writer.
+//!
+//! [`FdsnStreamWriter`] writes a document to any [`io::Write`] one station at
+//! a time. Only the station currently being written is converted and held in
+//! memory, so large response-level documents — or networks and stations
+//! produced on the fly — never need a complete [`Inventory`] or output
+//! `String`.
+
+use std::io::Write;
+
+use quick_xml::events::{BytesEnd, Event};
+use quick_xml::{Reader, Writer};
+
+use crate::error::{Result, StationXmlError};
+use crate::inventory::{ExtensionElement, Inventory, Network, Station};
+use crate::style;
+
+use super::FdsnWriteOptions;
+use super::extensions::{write_header_extensions, write_station_extensions};
+use super::writer::{convert_station, document_header, network_header};
+
+/// Writes FDSN StationXML incrementally to an [`io::Write`].
+///
+/// The output is identical to [`Fdsn::write_to_string_with`](super::Fdsn::write_to_string_with)
+/// for the same inventory. Wrap unbuffered sinks such as files in a
+/// [`BufWriter`](std::io::BufWriter).
+///
+/// ```
+/// use stationxml_rs::{FdsnStreamWriter, FdsnWriteOptions, Inventory};
+///
+/// let inv = Inventory::builder()
+///     .source("Test")
+///     .network("XX", |net| net.station("STA1", |sta| sta).station("STA2", |sta| sta))
+///     .build();
+/// let header = Inventory::builder().source("Test").build();
+///
+/// let mut w = FdsnStreamWriter::new(Vec::new(), &header, &FdsnWriteOptions::default()).unwrap();
+/// w.start_network(&inv.networks[0]).unwrap();
+/// for sta in &inv.networks[0].stations {
+///     w.write_station(sta).unwrap();
+/// }
+/// let xml = String::from_utf8(w.finish().unwrap()).unwrap();
+/// assert!(xml.contains(r#"<Station code="STA2""#));
+/// assert!(xml.ends_with("</Network></FDSNStationXML>"));
+/// ```
+pub struct FdsnStreamWriter<W: Write> {
+    out: Writer<W>,
+    options: FdsnWriteOptions,
+    /// Root extension elements anchored among the networks
+    root_elements: Vec<ExtensionElement>,
+    /// Networks started so far
+    networks: usize,
+    /// The open network, if any
+    network: Option<OpenNetwork>,
+    /// Reused serialization buffer
+    buf: String,
+}
+
+/// A `<Network>` whose end tag has not been written yet.
+struct OpenNetwork {
+    /// Serialized network without stations, until its start tag is written
+    pending: Option<String>,
+    /// Extension elements anchored among the stations
+    elements: Vec<ExtensionElement>,
+    /// Stations written so far
+    stations: usize,
+}
+
+impl<W: Write> FdsnStreamWriter<W> {
+    /// Write the XML declaration and root element header.
+    ///
+    /// `header` supplies the document-level fields (source, sender, module,
+    /// created, root extensions); its networks are ignored.
+    pub fn new(mut out: W, header: &Inventory, options: &FdsnWriteOptions) -> Result<Self> {
+        out.write_all(br#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        out.write_all(b"\n")?;
+        let mut writer = Self {
+            out: options.style.writer(out),
+            options: options.clone(),
+            root_elements: Vec::new(),
+            networks: 0,
+            network: None,
+            buf: String::new(),
+        };
+        let root = writer.serialize(&document_header(header, &writer.options))?;
+        let (root, root_elements) = write_header_extensions(root, &header.extensions)?;
+        writer.root_elements = root_elements;
+        writer.write_open(&root)?;
+        writer.buf = root;
+        Ok(writer)
+    }
+
+    /// Open a `<Network>`, closing the previous one. `net.stations` is ignored;
+    /// write them with [`write_station`](Self::write_station).
+    pub fn start_network(&mut self, net: &Network) -> Result<()> {
+        self.end_network()?;
+        let ready = take_anchored(&mut self.root_elements, self.networks);
+        self.write_elements(&ready)?;
+        self.networks += 1;
+        let xml = self.serialize(&network_header(net, &self.options))?;
+        let (header, elements) = write_header_extensions(xml, &net.extensions)?;
+        self.network = Some(OpenNetwork {
+            pending: Some(header),
+            elements,
+            stations: 0,
+        });
+        Ok(())
+    }
+
+    /// Write one station, with its channels and responses, into the open network.
+    pub fn write_station(&mut self, sta: &Station) -> Result<()> {
+        let Some(network) = &mut self.network else {
+            return Err(StationXmlError::InvalidData(format!(
+                "station {} written outside a network",
+                sta.code
+            )));
+        };
+        let header = network.pending.take();
+        let ready = take_anchored(&mut network.elements, network.stations);
+        network.stations += 1;
+        if let Some(header) = header {
+            self.write_open(&header)?;
+        }
+        self.write_elements(&ready)?;
+        let xml = self.serialize(&convert_station(sta, &self.options))?;
+        let xml = write_station_extensions(xml, sta)?;
+        style::write_events(&mut self.out, &xml, &self.options.style)?;
+        self.buf = xml;
+        Ok(())
+    }
+
+    /// Write a whole network: [`start_network`](Self::start_network), every
+    /// station, then [`end_network`](Self::end_network).
+    pub fn write_network(&mut self, net: &Network) -> Result<()> {
+        self.start_network(net)?;
+        for sta in &net.stations {
+            self.write_station(sta)?;
+        }
+        self.end_network()
+    }
+
+    /// Close the open network, if any.
+    pub fn end_network(&mut self) -> Result<()> {
+        let Some(network) = self.network.take() else {
+            return Ok(());
+        };
+        match network.pending {
+            // No stations: write the element exactly as serialized
+            Some(header) if network.elements.is_empty() => {
+                return style::write_events(&mut self.out, &header, &self.options.style);
+            }
+            Some(header) => self.write_open(&header)?,
+            None => {}
+        }
+        self.write_elements(&network.elements)?;
+        self.out.write_event(Event::End(BytesEnd::new("Network")))?;
+        Ok(())
+    }
+
+    /// Close the document and return the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        self.end_network()?;
+        let elements = std::mem::take(&mut self.root_elements);
+        self.write_elements(&elements)?;
+        self.out
+            .write_event(Event::End(BytesEnd::new("FDSNStationXML")))?;
+        let mut out = self.out.into_inner();
+        out.flush()?;
+        Ok(out)
+    }
+
+    /// Serialize `value` compactly, reusing the buffer.
+    fn serialize<T: serde::Serialize>(&mut self, value: &T) -> Result<String> {
+        let mut xml = std::mem::take(&mut self.buf);
+        xml.clear();
+        style::serialize(value, &self.options.style, &mut xml)?;
+        Ok(xml)
+    }
+
+    /// Write an element without its end tag, leaving it open for children.
+    fn write_open(&mut self, xml: &str) -> Result<()> {
+        let mut reader = Reader::from_str(xml);
+        let mut depth = 0usize;
+        loop {
+            let event = reader.read_event().map_err(style::xml_error)?;
+            match event {
+                Event::Empty(e) if depth == 0 => {
+                    return style::write_event(&mut self.out, Event::Start(e), &self.options.style);
+                }
+                Event::End(_) if depth == 1 => return Ok(()),
+                Event::Eof => {
+                    return Err(StationXmlError::InvalidData(
+                        "unterminated serialized element".into(),
+                    ));
+                }
+                event => {
+                    match event {
+                        Event::Start(_) => depth += 1,
+                        Event::End(_) => depth -= 1,
+                        _ => {}
+                    }
+                    style::write_event(&mut self.out, event, &self.options.style)?;
+                }
+            }
+        }
+    }
+
+    fn write_elements(&mut self, elements: &[ExtensionElement]) -> Result<()> {
+        for el in elements {
+            style::write_events(&mut self.out, &el.xml, &self.options.style)?;
+        }
+        Ok(())
+    }
+}
+
+/// Remove and return the `elements` anchored before child `index`.
+fn take_anchored(elements: &mut Vec<ExtensionElement>, index: usize) -> Vec<ExtensionElement> {
+    let (ready, rest) = std::mem::take(elements)
+        .into_iter()
+        .partition(|el| el.position.is_some_and(|p| p <= index));
+    *elements = rest;
+    ready
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fdsn::writer::write_to_string_with;
+    use crate::inventory::ExtensionAttribute;
+    use crate::style::{AttributeOrder, FloatFormat, XmlStyle};
+    use chrono::{TimeZone, Utc};
+
+    fn inventory() -> Inventory {
+        let mut inv = Inventory::builder()
+            .source("Test")
+            .created(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap())
+            .network("XX", |net| {
+                net.description("Test network")
+                    .station("STA1", |sta| {
+                        sta.channel("SHZ", "00", |ch| ch.sample_rate(100.0))
+                    })
+                    .station("STA2", |sta| {
+                        sta.channel("SHZ", "00", |ch| ch.sample_rate(100.0))
+                            .channel("SHN", "00", |ch| ch.sample_rate(100.0))
+                    })
+            })
+            .network("YY", |net| net.station("STA3", |sta| sta))
+            .network("ZZ", |net| net)
+            .build();
+        inv.extensions.attributes = vec![("xmlns:ext".into(), "urn:ext".into())];
+        inv.extensions.elements = vec!["<ext:root>r</ext:root>".into()];
+        inv.networks[1].extensions.attributes = vec![("ext:a".into(), "1".into())];
+        inv.networks[1].extensions.elements = vec!["<ext:net/>".into()];
+        inv.networks[0].stations[1].channels[1].extensions.elements =
+            vec!["<ext:chan>c</ext:chan>".into()];
+        // Anchored inside the header, between networks and between stations
+        inv.extensions.elements.extend([
+            anchored("", 1, "<ext:early/>"),
+            anchored("", 3, "<ext:between/>"),
+        ]);
+        inv.networks[0].extensions.elements = vec![
+            anchored("Description[0]", 0, "<ext:desc/>"),
+            anchored("", 2, "<ext:sta/>"),
+        ];
+        inv.networks[0].extensions.nested_attributes = vec![ExtensionAttribute {
+            element: "Description[0]".into(),
+            name: "ext:lang".into(),
+            value: "en".into(),
+        }];
+        inv
+    }
+
+    fn anchored(parent: &str, position: usize, xml: &str) -> ExtensionElement {
+        ExtensionElement {
+            parent: parent.into(),
+            position: Some(position),
+            xml: xml.into(),
+        }
+    }
+
+    #[test]
+    fn matches_string_writer() {
+        let inv = inventory();
+        let styles = [
+            XmlStyle::default(),
+            XmlStyle::pretty(),
+            XmlStyle::default()
+                .indent(4)
+                .floats(FloatFormat::Fixed(3))
+                .attributes(AttributeOrder::Alphabetical),
+        ];
+        for style in styles {
+            let options = FdsnWriteOptions::default().style(style);
+            let mut writer = FdsnStreamWriter::new(Vec::new(), &inv, &options).unwrap();
+            for net in &inv.networks {
+                writer.write_network(net).unwrap();
+            }
+            let streamed = String::from_utf8(writer.finish().unwrap()).unwrap();
+            assert_eq!(streamed, write_to_string_with(&inv, &options).unwrap());
+        }
+        let xml = write_to_string_with(&inv, &FdsnWriteOptions::default()).unwrap();
+        assert!(xml.contains("<ext:early/><Created>"));
+        assert!(xml.contains(r#"<ext:between/><Network code="YY""#));
+        assert!(
+            xml.contains(r#"<Description ext:lang="en">Test network<ext:desc/></Description>"#)
+        );
+        assert!(xml.contains(r#"<ext:sta/><Station code="STA2""#));
+    }
+
+    #[test]
+    fn station_outside_network_is_rejected() {
+        let inv = inventory();
+        let mut writer =
+            FdsnStreamWriter::new(Vec::new(), &inv, &FdsnWriteOptions::default()).unwrap();
+        let err = writer
+            .write_station(&inv.networks[0].stations[0])
+            .unwrap_err();
+        assert!(err.to_string().contains("outside a network"));
+    }
+}
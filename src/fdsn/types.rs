@@ -61,6 +61,8 @@ pub(crate) struct FdsnNetwork {
         skip_serializing_if = "Option::is_none"
     )]
     pub description: Option<String>,
+    #[serde(rename = "Comment", default)]
+    pub comments: Vec<FdsnComment>,
     #[serde(
         rename = "TotalNumberStations",
         default,
@@ -97,6 +99,14 @@ pub(crate) struct FdsnStation {
     )]
     pub restricted_status: Option<String>,
     // Child elements (order matches FDSN schema)
+    #[serde(
+        rename = "Description",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(rename = "Comment", default)]
+    pub comments: Vec<FdsnComment>,
     #[serde(rename = "Latitude")]
     pub latitude: FdsnFloatValue,
     #[serde(rename = "Longitude")]
@@ -105,6 +115,18 @@ pub(crate) struct FdsnStation {
     pub elevation: FdsnFloatValue,
     #[serde(rename = "Site")]
     pub site: FdsnSite,
+    #[serde(
+        rename = "WaterLevel",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub water_level: Option<FdsnFloatValue>,
+    #[serde(rename = "Vault", default, skip_serializing_if = "Option::is_none")]
+    pub vault: Option<String>,
+    #[serde(rename = "Geology", default, skip_serializing_if = "Option::is_none")]
+    pub geology: Option<String>,
+    #[serde(rename = "Equipment", default)]
+    pub equipment: Vec<FdsnEquipment>,
     #[serde(
         rename = "CreationDate",
         default,
@@ -123,10 +145,54 @@ pub(crate) struct FdsnStation {
         skip_serializing_if = "Option::is_none"
     )]
     pub selected_number_channels: Option<u32>,
+    #[serde(rename = "ExternalReference", default)]
+    pub external_references: Vec<FdsnExternalReference>,
     #[serde(rename = "Channel", default)]
     pub channels: Vec<FdsnChannel>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FdsnComment {
+    #[serde(rename = "@id", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    #[serde(rename = "@subject", default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(rename = "Value")]
+    pub value: String,
+    #[serde(
+        rename = "BeginEffectiveTime",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub begin_effective_time: Option<String>,
+    #[serde(
+        rename = "EndEffectiveTime",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub end_effective_time: Option<String>,
+    #[serde(rename = "Author", default)]
+    pub authors: Vec<FdsnPerson>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FdsnPerson {
+    #[serde(rename = "Name", default)]
+    pub names: Vec<String>,
+    #[serde(rename = "Agency", default)]
+    pub agencies: Vec<String>,
+    #[serde(rename = "Email", default)]
+    pub emails: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FdsnExternalReference {
+    #[serde(rename = "URI")]
+    pub uri: String,
+    #[serde(rename = "Description")]
+    pub description: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct FdsnSite {
     #[serde(rename = "Name")]
@@ -171,6 +237,14 @@ pub(crate) struct FdsnChannel {
     )]
     pub restricted_status: Option<String>,
     // Child elements (order matches FDSN schema)
+    #[serde(
+        rename = "Description",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(rename = "Comment", default)]
+    pub comments: Vec<FdsnComment>,
     #[serde(rename = "Latitude")]
     pub latitude: FdsnFloatValue,
     #[serde(rename = "Longitude")]
@@ -183,24 +257,66 @@ pub(crate) struct FdsnChannel {
     pub azimuth: FdsnFloatValue,
     #[serde(rename = "Dip")]
     pub dip: FdsnFloatValue,
-    #[serde(rename = "Type", default, skip_serializing_if = "Option::is_none")]
-    pub channel_type: Option<String>,
-    #[serde(rename = "SampleRate")]
-    pub sample_rate: FdsnFloatValue,
+    #[serde(
+        rename = "WaterLevel",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub water_level: Option<FdsnFloatValue>,
+    #[serde(rename = "Type", default)]
+    pub channel_types: Vec<String>,
+    #[serde(
+        rename = "SampleRate",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sample_rate: Option<FdsnFloatValue>,
+    #[serde(
+        rename = "StorageFormat",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub storage_format: Option<String>,
+    #[serde(
+        rename = "ClockDrift",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub clock_drift: Option<FdsnFloatValue>,
+    #[serde(
+        rename = "CalibrationUnits",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub calibration_units: Option<FdsnUnits>,
     #[serde(rename = "Sensor", default, skip_serializing_if = "Option::is_none")]
     pub sensor: Option<FdsnEquipment>,
+    #[serde(
+        rename = "PreAmplifier",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pre_amplifier: Option<FdsnEquipment>,
     #[serde(
         rename = "DataLogger",
         default,
         skip_serializing_if = "Option::is_none"
     )]
     pub data_logger: Option<FdsnEquipment>,
+    #[serde(rename = "Equipment", default)]
+    pub equipment: Vec<FdsnEquipment>,
     #[serde(rename = "Response", default, skip_serializing_if = "Option::is_none")]
     pub response: Option<FdsnResponse>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct FdsnEquipment {
+    #[serde(
+        rename = "@resourceId",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub resource_id: Option<String>,
     #[serde(rename = "Type", default, skip_serializing_if = "Option::is_none")]
     pub equipment_type: Option<String>,
     #[serde(
@@ -237,6 +353,8 @@ pub(crate) struct FdsnEquipment {
         skip_serializing_if = "Option::is_none"
     )]
     pub removal_date: Option<String>,
+    #[serde(rename = "CalibrationDate", default)]
+    pub calibration_dates: Vec<String>,
 }
 
 // ─── Float value helper ─────────────────────────────────────────────
@@ -360,6 +478,12 @@ pub(crate) struct FdsnStageGain {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct FdsnPolesZeros {
+    #[serde(
+        rename = "@resourceId",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub resource_id: Option<String>,
     #[serde(rename = "InputUnits")]
     pub input_units: FdsnUnits,
     #[serde(rename = "OutputUnits")]
@@ -406,6 +530,12 @@ pub(crate) struct FdsnCoefficients {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct FdsnFIR {
+    #[serde(
+        rename = "@resourceId",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub resource_id: Option<String>,
     #[serde(rename = "InputUnits")]
     pub input_units: FdsnUnits,
     #[serde(rename = "OutputUnits")]
@@ -510,6 +640,8 @@ mod tests {
   <Depth>0.0</Depth>
   <Azimuth>0.0</Azimuth>
   <Dip>-90.0</Dip>
+  <Type>CONTINUOUS</Type>
+  <Type>GEOPHYSICAL</Type>
   <SampleRate>100.0</SampleRate>
   <Sensor>
     <Type>Geophone</Type>
@@ -529,7 +661,8 @@ mod tests {
         assert_eq!(ch.code, "SHZ");
         assert_eq!(ch.location_code, "00");
         assert!((ch.dip.value - (-90.0)).abs() < 1e-6);
-        assert!((ch.sample_rate.value - 100.0).abs() < 1e-6);
+        assert!((ch.sample_rate.as_ref().unwrap().value - 100.0).abs() < 1e-6);
+        assert_eq!(ch.channel_types, vec!["CONTINUOUS", "GEOPHYSICAL"]);
 
         let sensor = ch.sensor.as_ref().unwrap();
         assert_eq!(sensor.model.as_deref(), Some("GS-11D"));
@@ -1,17 +1,24 @@
 //! FDSN StationXML writer: Inventory → fdsn types → XML.
 
-use chrono::{SecondsFormat, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 
-use crate::datetime::{format_datetime, format_datetime_opt};
 use crate::error::Result;
 use crate::inventory::*;
+use crate::style::{self, TimeFormat};
 
+use super::extensions::write_extensions;
 use super::types::*;
+use super::{FdsnVersion, FdsnWriteOptions};
 
 /// Serialize an [`Inventory`] to an FDSN StationXML string.
-pub(crate) fn write_to_string(inventory: &Inventory) -> Result<String> {
-    let fdsn = inventory_to_fdsn(inventory);
-    let body = quick_xml::se::to_string(&fdsn)?;
+pub(crate) fn write_to_string_with(
+    inventory: &Inventory,
+    options: &FdsnWriteOptions,
+) -> Result<String> {
+    let fdsn = inventory_to_fdsn(inventory, options);
+    let mut body = String::new();
+    style::serialize(&fdsn, &options.style, &mut body)?;
+    let body = style::restyle(write_extensions(body, inventory)?, &options.style)?;
     let mut xml = String::with_capacity(body.len() + 50);
     xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
     xml.push('\n');
@@ -21,44 +28,73 @@ pub(crate) fn write_to_string(inventory: &Inventory) -> Result<String> {
 
 // ─── Conversion functions ───────────────────────────────────────────
 
-fn inventory_to_fdsn(inv: &Inventory) -> FdsnStationXml {
+fn inventory_to_fdsn(inv: &Inventory, opts: &FdsnWriteOptions) -> FdsnStationXml {
+    FdsnStationXml {
+        networks: inv
+            .networks
+            .iter()
+            .map(|net| convert_network(net, opts))
+            .collect(),
+        ..document_header(inv, opts)
+    }
+}
+
+/// Root element without networks; `inv.networks` is ignored.
+pub(crate) fn document_header(inv: &Inventory, opts: &FdsnWriteOptions) -> FdsnStationXml {
     FdsnStationXml {
         xmlns: Some("http://www.fdsn.org/xml/station/1".into()),
-        schema_version: "1.2".into(),
+        schema_version: opts.version.as_str().into(),
         source: inv.source.clone(),
         sender: inv.sender.clone(),
-        module: None,
-        module_uri: None,
+        module: opts.module.clone().or_else(|| inv.module.clone()),
+        module_uri: opts.module_uri.clone().or_else(|| inv.module_uri.clone()),
         created: inv
             .created
-            .map(|dt| format_datetime(&dt))
+            .map(|dt| opts.style.times.format(&dt))
             .unwrap_or_else(|| Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)),
-        networks: inv.networks.iter().map(convert_network).collect(),
+        networks: Vec::new(),
     }
 }
 
-fn convert_network(net: &Network) -> FdsnNetwork {
+fn convert_network(net: &Network, opts: &FdsnWriteOptions) -> FdsnNetwork {
+    FdsnNetwork {
+        stations: net
+            .stations
+            .iter()
+            .map(|sta| convert_station(sta, opts))
+            .collect(),
+        ..network_header(net, opts)
+    }
+}
+
+/// Network element without stations; `net.stations` is ignored.
+pub(crate) fn network_header(net: &Network, opts: &FdsnWriteOptions) -> FdsnNetwork {
+    let times = opts.style.times;
     FdsnNetwork {
         code: net.code.clone(),
-        start_date: format_datetime_opt(&net.start_date),
-        end_date: format_datetime_opt(&net.end_date),
-        restricted_status: None,
+        start_date: times.format_opt(&net.start_date),
+        end_date: times.format_opt(&net.end_date.or(opts.open_end)),
+        restricted_status: net.restricted_status.as_ref().map(format_restricted_status),
         description: net.description.clone(),
+        comments: convert_comments(&net.comments, opts),
         total_number_stations: None,
         selected_number_stations: None,
-        stations: net.stations.iter().map(convert_station).collect(),
+        stations: Vec::new(),
     }
 }
 
-fn convert_station(sta: &Station) -> FdsnStation {
+pub(crate) fn convert_station(sta: &Station, opts: &FdsnWriteOptions) -> FdsnStation {
+    let (version, times) = (opts.version, opts.style.times);
     FdsnStation {
         code: sta.code.clone(),
-        start_date: format_datetime_opt(&sta.start_date),
-        end_date: format_datetime_opt(&sta.end_date),
-        restricted_status: None,
-        latitude: FdsnFloatValue::new(sta.latitude),
-        longitude: FdsnFloatValue::new(sta.longitude),
-        elevation: FdsnFloatValue::new(sta.elevation),
+        start_date: times.format_opt(&sta.start_date),
+        end_date: times.format_opt(&sta.end_date.or(opts.open_end)),
+        restricted_status: sta.restricted_status.as_ref().map(format_restricted_status),
+        description: sta.description.clone(),
+        comments: convert_comments(&sta.comments, opts),
+        latitude: float_value(sta.latitude, &sta.latitude_uncertainty),
+        longitude: float_value(sta.longitude, &sta.longitude_uncertainty),
+        elevation: float_value(sta.elevation, &sta.elevation_uncertainty),
         site: FdsnSite {
             name: sta.site.name.clone(),
             description: sta.site.description.clone(),
@@ -67,44 +103,198 @@ fn convert_station(sta: &Station) -> FdsnStation {
             region: sta.site.region.clone(),
             country: sta.site.country.clone(),
         },
-        creation_date: format_datetime_opt(&sta.creation_date),
+        // WaterLevel was added to Station in 1.1
+        water_level: match version {
+            FdsnVersion::V1_0 => None,
+            _ => sta.water_level.map(FdsnFloatValue::new),
+        },
+        vault: sta.vault.clone(),
+        geology: sta.geology.clone(),
+        equipment: convert_equipments(single_before_1_1(&sta.equipment, version), times),
+        // CreationDate is mandatory in 1.0; fall back to the start date,
+        // then the earliest channel start, then the Unix epoch
+        creation_date: match version {
+            FdsnVersion::V1_0 => Some(times.format(&fallback_creation_date(sta))),
+            _ => times.format_opt(&sta.creation_date),
+        },
         total_number_channels: None,
         selected_number_channels: None,
-        channels: sta.channels.iter().map(convert_channel).collect(),
+        external_references: sta
+            .external_references
+            .iter()
+            .map(|r| FdsnExternalReference {
+                uri: r.uri.clone(),
+                description: r.description.clone(),
+            })
+            .collect(),
+        channels: sta
+            .channels
+            .iter()
+            .map(|ch| convert_channel(ch, opts))
+            .collect(),
     }
 }
 
-fn convert_channel(ch: &Channel) -> FdsnChannel {
+fn convert_channel(ch: &Channel, opts: &FdsnWriteOptions) -> FdsnChannel {
+    let (version, times) = (opts.version, opts.style.times);
     FdsnChannel {
         code: ch.code.clone(),
         location_code: ch.location_code.clone(),
-        start_date: format_datetime_opt(&ch.start_date),
-        end_date: format_datetime_opt(&ch.end_date),
-        restricted_status: None,
-        latitude: FdsnFloatValue::new(ch.latitude),
-        longitude: FdsnFloatValue::new(ch.longitude),
-        elevation: FdsnFloatValue::new(ch.elevation),
-        depth: FdsnFloatValue::new(ch.depth),
-        azimuth: FdsnFloatValue::new(ch.azimuth),
-        dip: FdsnFloatValue::new(ch.dip),
-        channel_type: None,
-        sample_rate: FdsnFloatValue::new(ch.sample_rate),
-        sensor: ch.sensor.as_ref().map(convert_equipment),
-        data_logger: ch.data_logger.as_ref().map(convert_equipment),
+        start_date: times.format_opt(&ch.start_date),
+        end_date: times.format_opt(&ch.end_date.or(opts.open_end)),
+        restricted_status: ch.restricted_status.as_ref().map(format_restricted_status),
+        description: ch.aux_source.as_ref().map(aux_source_description),
+        // FDSN has no sensor location element to hold its comments
+        comments: convert_comments(
+            &[&ch.comments[..], &ch.sensor_location_comments].concat(),
+            opts,
+        ),
+        latitude: float_value(ch.latitude, &ch.latitude_uncertainty),
+        longitude: float_value(ch.longitude, &ch.longitude_uncertainty),
+        elevation: float_value(ch.elevation, &ch.elevation_uncertainty),
+        depth: float_value(ch.depth, &ch.depth_uncertainty),
+        azimuth: float_value(ch.azimuth, &ch.azimuth_uncertainty),
+        dip: float_value(ch.dip, &ch.dip_uncertainty),
+        // WaterLevel was added to Channel in 1.1
+        water_level: match version {
+            FdsnVersion::V1_0 => None,
+            _ => ch.water_level.map(FdsnFloatValue::new),
+        },
+        channel_types: ch.channel_types.iter().map(format_channel_type).collect(),
+        // A zero rate is how readers record a missing SampleRate
+        sample_rate: (ch.sample_rate != 0.0).then(|| FdsnFloatValue::new(ch.sample_rate)),
+        // StorageFormat was removed in StationXML 1.1
+        storage_format: match version {
+            FdsnVersion::V1_0 => ch.storage_format.clone(),
+            _ => None,
+        },
+        clock_drift: ch.clock_drift.map(|v| FdsnFloatValue {
+            unit: Some("SECONDS/SAMPLE".into()),
+            ..FdsnFloatValue::new(v)
+        }),
+        calibration_units: ch.calibration_units.as_ref().map(convert_units),
+        sensor: ch.sensor.as_ref().map(|eq| convert_equipment(eq, times)),
+        pre_amplifier: ch
+            .pre_amplifier
+            .as_ref()
+            .map(|eq| convert_equipment(eq, times)),
+        data_logger: ch
+            .data_logger
+            .as_ref()
+            .map(|eq| convert_equipment(eq, times)),
+        equipment: convert_equipments(single_before_1_1(&channel_equipment(ch), version), times),
         response: ch.response.as_ref().map(convert_response),
     }
 }
 
-fn convert_equipment(eq: &Equipment) -> FdsnEquipment {
+/// Other equipment of a channel, led by its auxiliary device: FDSN has no
+/// element of its own for one.
+fn channel_equipment(ch: &Channel) -> Vec<Equipment> {
+    ch.aux_device.iter().chain(&ch.equipment).cloned().collect()
+}
+
+/// Describe the auxiliary device output a channel records, e.g.
+/// `Auxiliary source pressure: Barometric pressure (hPa), conversion: 0.01 hPa/count`.
+/// The FDSN reader parses this text back into an [`AuxSource`].
+pub(crate) fn aux_source_description(source: &AuxSource) -> String {
+    let mut text = format!("Auxiliary source {}", source.name);
+    if let Some(description) = &source.description {
+        text.push_str(&format!(": {description}"));
+    }
+    if let Some(unit) = &source.unit {
+        text.push_str(&format!(" ({unit})"));
+    }
+    if let Some(conversion) = &source.conversion {
+        text.push_str(&format!(", conversion: {conversion}"));
+    }
+    if let Some(remark) = &source.remark {
+        text.push_str(&format!(", remark: {remark}"));
+    }
+    text
+}
+
+/// Equipment is written once at most before 1.1, where it became repeatable.
+fn single_before_1_1(equipment: &[Equipment], version: FdsnVersion) -> &[Equipment] {
+    match version {
+        FdsnVersion::V1_0 => &equipment[..equipment.len().min(1)],
+        _ => equipment,
+    }
+}
+
+/// Creation date for versions that require one.
+fn fallback_creation_date(sta: &Station) -> DateTime<Utc> {
+    sta.creation_date
+        .or(sta.start_date)
+        .or_else(|| sta.channels.iter().filter_map(|ch| ch.start_date).min())
+        .unwrap_or(DateTime::UNIX_EPOCH)
+}
+
+/// Build a float element, carrying over any uncertainty attributes.
+fn float_value(value: f64, uncertainty: &Option<Uncertainty>) -> FdsnFloatValue {
+    match uncertainty {
+        Some(u) => FdsnFloatValue {
+            value,
+            unit: u.unit.clone(),
+            datum: u.datum.clone(),
+            plus_error: u.plus_error,
+            minus_error: u.minus_error,
+        },
+        None => FdsnFloatValue::new(value),
+    }
+}
+
+fn convert_comments(comments: &[Comment], opts: &FdsnWriteOptions) -> Vec<FdsnComment> {
+    comments.iter().map(|c| convert_comment(c, opts)).collect()
+}
+
+fn convert_comment(c: &Comment, opts: &FdsnWriteOptions) -> FdsnComment {
+    let times = opts.style.times;
+    FdsnComment {
+        // FDSN comment ids are integers; free-form ids (e.g. from SC3ML) are dropped
+        id: c.id.as_deref().and_then(|id| id.parse().ok()),
+        // The subject attribute was added in 1.1
+        subject: match opts.version {
+            FdsnVersion::V1_0 => None,
+            _ => c.subject.clone(),
+        },
+        value: c.value.clone(),
+        begin_effective_time: times.format_opt(&c.begin_effective_time),
+        end_effective_time: times.format_opt(&c.end_effective_time),
+        authors: c
+            .author
+            .iter()
+            .map(|name| FdsnPerson {
+                names: vec![name.clone()],
+                agencies: vec![],
+                emails: vec![],
+            })
+            .collect(),
+    }
+}
+
+fn convert_equipments(equipment: &[Equipment], times: TimeFormat) -> Vec<FdsnEquipment> {
+    equipment
+        .iter()
+        .map(|eq| convert_equipment(eq, times))
+        .collect()
+}
+
+fn convert_equipment(eq: &Equipment, times: TimeFormat) -> FdsnEquipment {
     FdsnEquipment {
+        resource_id: eq.resource_id.clone(),
         equipment_type: eq.equipment_type.clone(),
         description: eq.description.clone(),
         manufacturer: eq.manufacturer.clone(),
         vendor: eq.vendor.clone(),
         model: eq.model.clone(),
         serial_number: eq.serial_number.clone(),
-        installation_date: format_datetime_opt(&eq.installation_date),
-        removal_date: format_datetime_opt(&eq.removal_date),
+        installation_date: times.format_opt(&eq.installation_date),
+        removal_date: times.format_opt(&eq.removal_date),
+        calibration_dates: eq
+            .calibration_dates
+            .iter()
+            .map(|dt| times.format(dt))
+            .collect(),
     }
 }
 
@@ -156,6 +346,7 @@ fn convert_stage(stage: &ResponseStage) -> FdsnResponseStage {
 
 fn convert_poles_zeros(pz: &PolesZeros) -> FdsnPolesZeros {
     FdsnPolesZeros {
+        resource_id: pz.resource_id.clone(),
         input_units: convert_units(&pz.input_units),
         output_units: convert_units(&pz.output_units),
         pz_transfer_function_type: format_pz_transfer_function(&pz.pz_transfer_function_type),
@@ -166,8 +357,8 @@ fn convert_poles_zeros(pz: &PolesZeros) -> FdsnPolesZeros {
             .iter()
             .map(|z| FdsnPoleZero {
                 number: z.number,
-                real: FdsnFloatValue::new(z.real),
-                imaginary: FdsnFloatValue::new(z.imaginary),
+                real: float_value(z.real, &z.real_uncertainty),
+                imaginary: float_value(z.imaginary, &z.imaginary_uncertainty),
             })
             .collect(),
         poles: pz
@@ -175,8 +366,8 @@ fn convert_poles_zeros(pz: &PolesZeros) -> FdsnPolesZeros {
             .iter()
             .map(|p| FdsnPoleZero {
                 number: p.number,
-                real: FdsnFloatValue::new(p.real),
-                imaginary: FdsnFloatValue::new(p.imaginary),
+                real: float_value(p.real, &p.real_uncertainty),
+                imaginary: float_value(p.imaginary, &p.imaginary_uncertainty),
             })
             .collect(),
     }
@@ -202,6 +393,7 @@ fn convert_coefficients(cf: &Coefficients) -> FdsnCoefficients {
 
 fn convert_fir(fir: &FIR) -> FdsnFIR {
     FdsnFIR {
+        resource_id: fir.resource_id.clone(),
         input_units: convert_units(&fir.input_units),
         output_units: convert_units(&fir.output_units),
         symmetry: format_symmetry(&fir.symmetry),
@@ -215,6 +407,30 @@ fn convert_fir(fir: &FIR) -> FdsnFIR {
 
 // ─── Enum formatting ────────────────────────────────────────────────
 
+fn format_restricted_status(status: &RestrictedStatus) -> String {
+    match status {
+        RestrictedStatus::Open => "open".into(),
+        RestrictedStatus::Closed => "closed".into(),
+        RestrictedStatus::Partial => "partial".into(),
+    }
+}
+
+fn format_channel_type(t: &ChannelType) -> String {
+    match t {
+        ChannelType::Triggered => "TRIGGERED".into(),
+        ChannelType::Continuous => "CONTINUOUS".into(),
+        ChannelType::Health => "HEALTH".into(),
+        ChannelType::Geophysical => "GEOPHYSICAL".into(),
+        ChannelType::Weather => "WEATHER".into(),
+        ChannelType::Flag => "FLAG".into(),
+        ChannelType::Synthesized => "SYNTHESIZED".into(),
+        ChannelType::Input => "INPUT".into(),
+        ChannelType::Experimental => "EXPERIMENTAL".into(),
+        ChannelType::Maintenance => "MAINTENANCE".into(),
+        ChannelType::Beam => "BEAM".into(),
+    }
+}
+
 fn format_pz_transfer_function(pz: &PzTransferFunction) -> String {
     match pz {
         PzTransferFunction::LaplaceRadians => "LAPLACE (RADIANS/SECOND)".into(),
@@ -1,19 +1,98 @@
 //! Format trait and auto-detection.
 //!
 //! The [`StationXmlFormat`] trait is implemented by each format backend
-//! (FDSN, SC3ML). [`detect_format`] inspects the root XML element to
-//! determine which format a document uses.
+//! (FDSN, SC3ML, JSON). [`detect_format`] inspects the root XML element
+//! (or the opening brace of a JSON document) to determine which format a
+//! document uses.
 
-use crate::error::Result;
+use chrono::{DateTime, Utc};
+
+use crate::error::{Result, StationXmlError};
 use crate::inventory::Inventory;
 
-/// Supported XML formats.
+/// Supported formats.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Format {
-    /// FDSN StationXML 1.2
+    /// FDSN StationXML 1.0–1.2
     Fdsn,
-    /// SeisComP SC3ML 0.13
+    /// SeisComP SC3ML 0.6–0.13
     Sc3ml,
+    /// JSON encoding of the inventory model, see [`crate::json`]
+    Json,
+}
+
+/// Options controlling how documents are read.
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use stationxml_rs::{Fdsn, ReadOptions, StationXmlFormat};
+///
+/// let xml = r#"<FDSNStationXML xmlns="http://www.fdsn.org/xml/station/1" schemaVersion="1.2">
+///   <Source>Test</Source><Created>2024-01-01T00:00:00Z</Created>
+///   <Network code="XX" startDate="2020-01-01" endDate="2599-12-31T23:59:59"/>
+/// </FDSNStationXML>"#;
+/// let opts = ReadOptions::default().open_end_after(Utc.with_ymd_and_hms(2500, 1, 1, 0, 0, 0).unwrap());
+/// let inv = Fdsn::read_from_str_with(xml, &opts).unwrap().inventory;
+/// assert_eq!(inv.networks[0].end_date, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReadOptions {
+    /// Skip networks, stations, channels and responses that fail to parse,
+    /// and read unparsable optional dates as `None`, reporting them as
+    /// warnings, instead of failing the whole document. Each broken element
+    /// found while deserializing costs another reading of the document; after
+    /// 100 of them the next error is returned
+    pub lenient: bool,
+    /// Network, station and channel end dates at or after this instant are
+    /// far-future sentinels for "still open" (`2599-12-31T23:59:59`,
+    /// `3000-01-01`…) and are read as `None`
+    pub open_end_after: Option<DateTime<Utc>>,
+}
+
+impl ReadOptions {
+    /// Enable or disable lenient parsing.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Read end dates at or after `threshold` as open-ended.
+    pub fn open_end_after(mut self, threshold: DateTime<Utc>) -> Self {
+        self.open_end_after = Some(threshold);
+        self
+    }
+
+    /// Apply the normalizations to a freshly read inventory.
+    pub(crate) fn normalize(&self, mut outcome: ReadOutcome) -> ReadOutcome {
+        if let Some(threshold) = self.open_end_after {
+            let open = |end: &mut Option<DateTime<Utc>>| {
+                if end.is_some_and(|end| end >= threshold) {
+                    *end = None;
+                }
+            };
+            for net in &mut outcome.inventory.networks {
+                open(&mut net.end_date);
+                for sta in &mut net.stations {
+                    open(&mut sta.end_date);
+                    for ch in &mut sta.channels {
+                        open(&mut ch.end_date);
+                    }
+                }
+            }
+        }
+        outcome
+    }
+}
+
+/// An [`Inventory`] read with [`ReadOptions`], with the problems that were
+/// skipped over.
+#[derive(Debug)]
+pub struct ReadOutcome {
+    /// The inventory, without the items that failed to parse
+    pub inventory: Inventory,
+    /// One error per skipped item or unset date, with its path and location
+    /// where known; always empty unless [`ReadOptions::lenient`] is set
+    pub warnings: Vec<StationXmlError>,
 }
 
 /// Trait implemented by each format backend.
@@ -27,8 +106,31 @@ pub trait StationXmlFormat {
     /// Deserialize XML bytes into an [`Inventory`].
     fn read_from_bytes(bytes: &[u8]) -> Result<Inventory>;
 
+    /// Deserialize an XML string with explicit options.
+    ///
+    /// The default implementation reads strictly, reports no warnings and
+    /// then applies the date normalizations.
+    fn read_from_str_with(xml: &str, options: &ReadOptions) -> Result<ReadOutcome> {
+        Ok(options.normalize(ReadOutcome {
+            inventory: Self::read_from_str(xml)?,
+            warnings: Vec::new(),
+        }))
+    }
+
     /// Serialize an [`Inventory`] to an XML string.
     fn write_to_string(inventory: &Inventory) -> Result<String>;
+
+    /// Serialize an [`Inventory`] to an [`io::Write`](std::io::Write).
+    ///
+    /// The default implementation writes the output of
+    /// [`write_to_string`](Self::write_to_string); backends that can stream
+    /// override it. Implementations flush `writer` before returning, so a
+    /// failed write is reported rather than lost with a buffer.
+    fn write_to_writer<W: std::io::Write>(inventory: &Inventory, mut writer: W) -> Result<()> {
+        writer.write_all(Self::write_to_string(inventory)?.as_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
 }
 
 /// Detect the XML format by inspecting the root element name.
@@ -38,8 +140,17 @@ pub trait StationXmlFormat {
 /// - `<FDSNStationXML ...>` → [`Format::Fdsn`]
 /// - `<seiscomp ...>` → [`Format::Sc3ml`]
 ///
+/// A document starting with `{` is taken to be [`Format::Json`].
+///
 /// Returns `None` if the root element is not recognized.
 pub fn detect_format(xml: &str) -> Option<Format> {
+    if xml
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .starts_with('{')
+    {
+        return Some(Format::Json);
+    }
     let mut reader = quick_xml::Reader::from_str(xml);
     loop {
         match reader.read_event() {
@@ -99,6 +210,13 @@ mod tests {
         assert_eq!(detect_format(xml), None);
     }
 
+    #[test]
+    fn detect_json() {
+        let json = "\n  {\"schema\": \"stationxml-rs/inventory\"}";
+        assert_eq!(detect_format(json), Some(Format::Json));
+        assert_eq!(detect_format("[1, 2]"), None);
+    }
+
     #[test]
     fn detect_empty() {
         assert_eq!(detect_format(""), None);
@@ -109,6 +227,27 @@ mod tests {
         assert_eq!(detect_format("not xml at all"), None);
     }
 
+    /// Accepts every write but fails to flush, like a full disk behind a buffer.
+    struct FailingFlush;
+
+    impl std::io::Write for FailingFlush {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Err(std::io::Error::other("disk full"))
+        }
+    }
+
+    #[test]
+    fn write_to_writer_reports_flush_errors() {
+        let inv = Inventory::builder().source("Test").build();
+        assert!(crate::Fdsn::write_to_writer(&inv, FailingFlush).is_err());
+        assert!(crate::Sc3ml::write_to_writer(&inv, FailingFlush).is_err());
+        assert!(crate::Json::write_to_writer(&inv, FailingFlush).is_err());
+    }
+
     #[test]
     fn format_enum_copy() {
         let f = Format::Fdsn;
@@ -0,0 +1,263 @@
+//! GeoJSON export of station and channel locations, for maps.
+//!
+//! [`GeoJson`] writes an RFC 7946 `FeatureCollection` with one `Point`
+//! feature per station epoch, or per channel epoch with
+//! [`GeoJsonOptions::channels`]. Coordinates are `[longitude, latitude,
+//! elevation]`. Export only; combine with [`Inventory::select`] to map a
+//! subset.
+//!
+//! Feature ids are the station or channel code followed by the epoch start,
+//! e.g. `XX.PBUMI.00.SHZ/2024-06-01T00:00:00Z`. Ids must be unique, so an
+//! epoch whose id is already taken (no start date, or the same start) has
+//! none.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+
+use crate::datetime::format_datetime_opt;
+use crate::error::Result;
+use crate::inventory::*;
+
+/// GeoJSON writer marker.
+///
+/// ```
+/// use stationxml_rs::{GeoJson, GeoJsonOptions, Inventory};
+///
+/// let inv = Inventory::builder()
+///     .network("XX", |net| {
+///         net.station("PBUMI", |sta| {
+///             sta.latitude(-7.77).longitude(110.38).elevation(150.0)
+///         })
+///     })
+///     .build();
+/// let map = GeoJson::to_value(&inv, &GeoJsonOptions::default());
+/// assert_eq!(map["features"][0]["geometry"]["coordinates"][0], 110.38);
+/// assert_eq!(map["features"][0]["properties"]["station"], "PBUMI");
+/// ```
+pub struct GeoJson;
+
+impl GeoJson {
+    /// Build the `FeatureCollection` as a JSON value.
+    pub fn to_value(inventory: &Inventory, options: &GeoJsonOptions) -> Value {
+        let mut features = Vec::new();
+        for net in &inventory.networks {
+            for sta in &net.stations {
+                if options.channels {
+                    features.extend(sta.channels.iter().map(|ch| channel_feature(net, sta, ch)));
+                } else {
+                    features.push(station_feature(net, sta));
+                }
+            }
+        }
+        let mut ids = HashSet::new();
+        for feature in &mut features {
+            let id = feature["id"].as_str().map(str::to_string);
+            if let Some(id) = id
+                && !ids.insert(id)
+                && let Some(feature) = feature.as_object_mut()
+            {
+                feature.remove("id");
+            }
+        }
+        json!({ "type": "FeatureCollection", "features": features })
+    }
+
+    /// Serialize the `FeatureCollection` to a string.
+    pub fn write_to_string_with(inventory: &Inventory, options: &GeoJsonOptions) -> Result<String> {
+        let value = Self::to_value(inventory, options);
+        let json = if options.pretty {
+            serde_json::to_string_pretty(&value)?
+        } else {
+            serde_json::to_string(&value)?
+        };
+        Ok(json)
+    }
+}
+
+/// Options controlling the GeoJSON export.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeoJsonOptions {
+    /// One feature per channel epoch instead of per station epoch
+    pub channels: bool,
+    /// Indent the output with two spaces (default compact)
+    pub pretty: bool,
+}
+
+impl GeoJsonOptions {
+    /// Write one feature per channel epoch.
+    pub fn channels(mut self, channels: bool) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Enable or disable indentation.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+}
+
+/// Id of the epoch of `code` starting at `start`.
+fn feature_id(code: String, start: &Option<DateTime<Utc>>) -> String {
+    match format_datetime_opt(start) {
+        Some(start) => format!("{code}/{start}"),
+        None => code,
+    }
+}
+
+fn point(longitude: f64, latitude: f64, elevation: f64) -> Value {
+    json!({ "type": "Point", "coordinates": [longitude, latitude, elevation] })
+}
+
+fn station_feature(net: &Network, sta: &Station) -> Value {
+    let mut channels: Vec<String> = Vec::new();
+    let mut sensors: Vec<&str> = Vec::new();
+    for ch in &sta.channels {
+        let id = format!("{}.{}", ch.location_code, ch.code);
+        if !channels.contains(&id) {
+            channels.push(id);
+        }
+        let model = ch.sensor.as_ref().and_then(|eq| eq.model.as_deref());
+        if let Some(model) = model.filter(|m| !sensors.contains(m)) {
+            sensors.push(model);
+        }
+    }
+    json!({
+        "type": "Feature",
+        "id": feature_id(format!("{}.{}", net.code, sta.code), &sta.start_date),
+        "geometry": point(sta.longitude, sta.latitude, sta.elevation),
+        "properties": {
+            "network": net.code,
+            "station": sta.code,
+            "site": sta.site.name,
+            "start_date": format_datetime_opt(&sta.start_date),
+            "end_date": format_datetime_opt(&sta.end_date),
+            "channels": channels,
+            "sensors": sensors,
+        },
+    })
+}
+
+fn channel_feature(net: &Network, sta: &Station, ch: &Channel) -> Value {
+    let model = |eq: &Option<Equipment>| eq.as_ref().and_then(|eq| eq.model.clone());
+    json!({
+        "type": "Feature",
+        "id": feature_id(
+            format!("{}.{}.{}.{}", net.code, sta.code, ch.location_code, ch.code),
+            &ch.start_date,
+        ),
+        "geometry": point(ch.longitude, ch.latitude, ch.elevation),
+        "properties": {
+            "network": net.code,
+            "station": sta.code,
+            "location": ch.location_code,
+            "channel": ch.code,
+            "site": sta.site.name,
+            "start_date": format_datetime_opt(&ch.start_date),
+            "end_date": format_datetime_opt(&ch.end_date),
+            "sample_rate": ch.sample_rate,
+            "depth": ch.depth,
+            "azimuth": ch.azimuth,
+            "dip": ch.dip,
+            "sensor": model(&ch.sensor),
+            "data_logger": model(&ch.data_logger),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn inventory() -> Inventory {
+        let start = chrono::Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let sensor = Equipment {
+            model: Some("GS-11D".into()),
+            ..Default::default()
+        };
+        Inventory::builder()
+            .network("XX", |net| {
+                net.station("PBUMI", |sta| {
+                    sta.latitude(-7.77)
+                        .longitude(110.38)
+                        .elevation(150.0)
+                        .site_name("Yogyakarta")
+                        .start_date(start)
+                        .channel("SHZ", "00", |ch| {
+                            ch.sample_rate(100.0).sensor(sensor.clone())
+                        })
+                        .channel("SHN", "00", |ch| {
+                            ch.sample_rate(100.0).sensor(sensor.clone())
+                        })
+                        .channel("SHZ", "00", |ch| ch.sample_rate(50.0))
+                })
+            })
+            .build()
+    }
+
+    #[test]
+    fn one_feature_per_station() {
+        let map = GeoJson::to_value(&inventory(), &GeoJsonOptions::default());
+        assert_eq!(map["type"], "FeatureCollection");
+        let features = map["features"].as_array().unwrap();
+        assert_eq!(features.len(), 1);
+        let station = &features[0];
+        assert_eq!(station["id"], "XX.PBUMI/2024-06-01T00:00:00Z");
+        assert_eq!(
+            station["geometry"]["coordinates"],
+            json!([110.38, -7.77, 150.0])
+        );
+        let properties = &station["properties"];
+        assert_eq!(properties["site"], "Yogyakarta");
+        assert_eq!(properties["start_date"], "2024-06-01T00:00:00Z");
+        assert_eq!(properties["end_date"], Value::Null);
+        assert_eq!(properties["channels"], json!(["00.SHZ", "00.SHN"]));
+        assert_eq!(properties["sensors"], json!(["GS-11D"]));
+    }
+
+    #[test]
+    fn one_feature_per_channel_epoch() {
+        let options = GeoJsonOptions::default().channels(true);
+        let map = GeoJson::to_value(&inventory(), &options);
+        let features = map["features"].as_array().unwrap();
+        assert_eq!(features.len(), 3);
+        assert_eq!(features[0]["id"], "XX.PBUMI.00.SHZ");
+        assert_eq!(features[0]["properties"]["sensor"], "GS-11D");
+        assert_eq!(features[2]["properties"]["sample_rate"], 50.0);
+        assert_eq!(features[2]["properties"]["sensor"], Value::Null);
+        // Without start dates the second SHZ epoch cannot have a unique id
+        assert!(features[2].get("id").is_none());
+    }
+
+    #[test]
+    fn epochs_of_the_same_channel_have_distinct_ids() {
+        let time = |year| chrono::Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap();
+        let inv = Inventory::builder()
+            .network("XX", |net| {
+                net.station("PBUMI", |sta| {
+                    sta.channel("SHZ", "00", |ch| {
+                        ch.start_date(time(2020)).end_date(time(2024))
+                    })
+                    .channel("SHZ", "00", |ch| ch.start_date(time(2024)))
+                })
+            })
+            .build();
+        let map = GeoJson::to_value(&inv, &GeoJsonOptions::default().channels(true));
+        let ids: Vec<&Value> = map["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| &f["id"])
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                "XX.PBUMI.00.SHZ/2020-01-01T00:00:00Z",
+                "XX.PBUMI.00.SHZ/2024-01-01T00:00:00Z"
+            ]
+        );
+    }
+}
@@ -28,7 +28,18 @@ pub struct Inventory {
     pub sender: Option<String>,
     /// When this metadata document was created
     pub created: Option<DateTime<Utc>>,
+    /// Name of the software module that generated this document
+    pub module: Option<String>,
+    /// URI of the software module that generated this document
+    pub module_uri: Option<String>,
+    /// Schema version of the source document (e.g. "1.2" for FDSN, "0.13" for SC3ML).
+    /// Informational only — writers emit their own target version.
+    pub schema_version: Option<String>,
+    /// Foreign-namespace content attached to the document root
+    #[serde(default)]
+    pub extensions: Extensions,
     /// Networks contained in this inventory
+    #[serde(default)]
     pub networks: Vec<Network>,
 }
 
@@ -47,7 +58,26 @@ pub struct Network {
     pub start_date: Option<DateTime<Utc>>,
     /// When this network epoch ended (None = still active)
     pub end_date: Option<DateTime<Utc>>,
+    /// Access restriction of this network's data
+    pub restricted_status: Option<RestrictedStatus>,
+    /// SeisComP network type (e.g. "BB", "SM")
+    pub network_type: Option<String>,
+    /// SeisComP network class ("p" = permanent, "t" = temporary)
+    pub network_class: Option<String>,
+    /// SeisComP archive (data center) holding this network's data
+    pub archive: Option<String>,
+    /// SeisComP flag: whether this network is shared with other data centers
+    pub shared: Option<bool>,
+    /// SC3ML `publicID` this network was read with, reused when writing SC3ML
+    pub public_id: Option<String>,
+    /// Free-form comments
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+    /// Foreign-namespace content attached to this network
+    #[serde(default)]
+    pub extensions: Extensions,
     /// Stations in this network
+    #[serde(default)]
     pub stations: Vec<Station>,
 }
 
@@ -64,18 +94,94 @@ pub struct Station {
     pub longitude: f64,
     /// Elevation in meters above sea level
     pub elevation: f64,
+    /// Uncertainty and datum of `latitude`
+    pub latitude_uncertainty: Option<Uncertainty>,
+    /// Uncertainty and datum of `longitude`
+    pub longitude_uncertainty: Option<Uncertainty>,
+    /// Uncertainty of `elevation`
+    pub elevation_uncertainty: Option<Uncertainty>,
     /// Site information (name, region, country, etc.)
     pub site: Site,
+    /// Elevation of the water surface in meters, for underwater sites
+    pub water_level: Option<f64>,
+    /// Type of vault (e.g. "Tunnel", "Borehole")
+    pub vault: Option<String>,
+    /// Type of rock and/or geologic formation
+    pub geology: Option<String>,
+    /// Station-level equipment (GPS, power supply, telemetry, etc.)
+    #[serde(default)]
+    pub equipment: Vec<Equipment>,
+    /// Links to external resources describing this station
+    #[serde(default)]
+    pub external_references: Vec<ExternalReference>,
     /// When this station epoch started
     pub start_date: Option<DateTime<Utc>>,
     /// When this station epoch ended (None = still active)
     pub end_date: Option<DateTime<Utc>>,
     /// When this station was originally created
     pub creation_date: Option<DateTime<Utc>>,
+    /// Access restriction of this station's data
+    pub restricted_status: Option<RestrictedStatus>,
+    /// SeisComP station type
+    pub station_type: Option<String>,
+    /// SeisComP affiliation (operating institution)
+    pub affiliation: Option<String>,
+    /// SeisComP archive (data center) holding this station's data
+    pub archive: Option<String>,
+    /// SeisComP network code under which the data is archived, if different
+    pub archive_network_code: Option<String>,
+    /// SeisComP flag: whether this station is shared with other data centers
+    pub shared: Option<bool>,
+    /// SC3ML `publicID` this station was read with, reused when writing SC3ML
+    pub public_id: Option<String>,
+    /// Free-form comments
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+    /// Foreign-namespace content attached to this station
+    #[serde(default)]
+    pub extensions: Extensions,
     /// Channels (measurement components) at this station
+    #[serde(default)]
     pub channels: Vec<Channel>,
 }
 
+/// Access restriction on a node's data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RestrictedStatus {
+    /// Openly available
+    Open,
+    /// Restricted
+    Closed,
+    /// Partially restricted (some child nodes are closed)
+    Partial,
+}
+
+/// A free-form comment attached to a network, station or channel.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Comment {
+    /// Comment text
+    pub value: String,
+    /// Identifier (numeric in FDSN, free-form in SC3ML)
+    pub id: Option<String>,
+    /// Subject or category of the comment (FDSN 1.1+)
+    pub subject: Option<String>,
+    /// Start of the period the comment applies to
+    pub begin_effective_time: Option<DateTime<Utc>>,
+    /// End of the period the comment applies to
+    pub end_effective_time: Option<DateTime<Utc>>,
+    /// Name of the comment's author
+    pub author: Option<String>,
+}
+
+/// A link to an external resource (e.g. a station book or photo gallery).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ExternalReference {
+    /// URI of the resource
+    pub uri: String,
+    /// What the resource contains
+    pub description: String,
+}
+
 /// Site information for a station — describes the physical location.
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct Site {
@@ -121,18 +227,111 @@ pub struct Channel {
     pub azimuth: f64,
     /// Dip in degrees from horizontal (-90=up, 0=horizontal, 90=down)
     pub dip: f64,
+    /// Uncertainty and datum of `latitude`
+    pub latitude_uncertainty: Option<Uncertainty>,
+    /// Uncertainty and datum of `longitude`
+    pub longitude_uncertainty: Option<Uncertainty>,
+    /// Uncertainty of `elevation`
+    pub elevation_uncertainty: Option<Uncertainty>,
+    /// Uncertainty of `depth`
+    pub depth_uncertainty: Option<Uncertainty>,
+    /// Uncertainty of `azimuth`
+    pub azimuth_uncertainty: Option<Uncertainty>,
+    /// Uncertainty of `dip`
+    pub dip_uncertainty: Option<Uncertainty>,
     /// Sample rate in Hz
     pub sample_rate: f64,
+    /// Channel types (e.g. CONTINUOUS, GEOPHYSICAL)
+    #[serde(default)]
+    pub channel_types: Vec<ChannelType>,
+    /// Data storage format (e.g. "Steim2"); dropped from FDSN StationXML 1.2
+    pub storage_format: Option<String>,
+    /// Clock drift tolerance in seconds per sample
+    pub clock_drift: Option<f64>,
+    /// Units of the calibration signal input (e.g. V, A)
+    pub calibration_units: Option<Units>,
+    /// Elevation of the water surface in meters, for underwater sensors
+    pub water_level: Option<f64>,
     /// When this channel epoch started
     pub start_date: Option<DateTime<Utc>>,
     /// When this channel epoch ended (None = still active)
     pub end_date: Option<DateTime<Utc>>,
+    /// Access restriction of this channel's data
+    pub restricted_status: Option<RestrictedStatus>,
+    /// SeisComP flag: whether this stream is shared with other data centers
+    pub shared: Option<bool>,
+    /// SC3ML `publicID` of the sensor location this channel belongs to
+    pub sensor_location_id: Option<String>,
+    /// Free-form comments
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+    /// Comments on the SC3ML sensor location this channel belongs to,
+    /// shared by all of its channels
+    #[serde(default)]
+    pub sensor_location_comments: Vec<Comment>,
     /// Sensor (geophone, broadband, accelerometer, etc.)
     pub sensor: Option<Equipment>,
+    /// Pre-amplifier between sensor and data logger
+    pub pre_amplifier: Option<Equipment>,
     /// Data logger / digitizer
     pub data_logger: Option<Equipment>,
+    /// Other equipment serving this channel (GPS clock, power supply, etc.)
+    #[serde(default)]
+    pub equipment: Vec<Equipment>,
+    /// Auxiliary device recording this channel instead of a sensor/data logger
+    /// (SC3ML `auxDevice`, e.g. state-of-health or environmental channels)
+    pub aux_device: Option<Equipment>,
+    /// Output of `aux_device` this channel records
+    pub aux_source: Option<AuxSource>,
     /// Instrument response (sensitivity, poles & zeros, etc.)
     pub response: Option<Response>,
+    /// Foreign-namespace content attached to this channel
+    #[serde(default)]
+    pub extensions: Extensions,
+}
+
+/// Channel type flag, as used in FDSN `<Type>` (and SEED blockette 52).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChannelType {
+    /// Triggered data
+    Triggered,
+    /// Continuous data
+    Continuous,
+    /// State-of-health data
+    Health,
+    /// Geophysical data
+    Geophysical,
+    /// Weather / environmental data
+    Weather,
+    /// Flag data (e.g. calibration markers)
+    Flag,
+    /// Synthesized data
+    Synthesized,
+    /// Calibration input
+    Input,
+    /// Experimental data
+    Experimental,
+    /// Maintenance tests
+    Maintenance,
+    /// Beam synthesis
+    Beam,
+}
+
+/// Uncertainty and reference attributes of a measured value.
+///
+/// Mirrors the attributes FDSN StationXML allows on float elements:
+/// `plusError`/`minusError` bounds, an explicit `unit`, and for
+/// coordinates the geodetic `datum` (WGS84 when absent).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Uncertainty {
+    /// Upper error bound, in the same units as the value
+    pub plus_error: Option<f64>,
+    /// Lower error bound, in the same units as the value
+    pub minus_error: Option<f64>,
+    /// Unit of the value as written in the source (e.g. "DEGREES", "METERS")
+    pub unit: Option<String>,
+    /// Geodetic datum for latitude/longitude (e.g. "WGS84", "NAD83")
+    pub datum: Option<String>,
 }
 
 /// Equipment description — sensor, datalogger, or other instrument.
@@ -150,10 +349,180 @@ pub struct Equipment {
     pub model: Option<String>,
     /// Serial number of this specific unit
     pub serial_number: Option<String>,
-    /// When this equipment was installed
+    /// Component of a multi-channel unit that this channel records (SC3ML
+    /// stream `sensorChannel` / `dataloggerChannel`)
+    pub channel: Option<u32>,
+    /// When this equipment was installed. FDSN only: SC3ML has no per-unit
+    /// dates, the stream epoch (the channel's start/end) is the installation
     pub installation_date: Option<DateTime<Utc>>,
-    /// When this equipment was removed
+    /// When this equipment was removed (FDSN only, see `installation_date`)
     pub removal_date: Option<DateTime<Utc>>,
+    /// Dates on which this unit was calibrated. In SC3ML these are the starts
+    /// of the unit's `calibration` records (requires a serial number)
+    #[serde(default)]
+    pub calibration_dates: Vec<DateTime<Utc>>,
+    /// Gain calibrations of this unit (SC3ML `calibration`)
+    #[serde(default)]
+    pub calibrations: Vec<Calibration>,
+    /// Other decimations of an SC3ML datalogger definition, at sample rates
+    /// this channel does not record (SC3ML only)
+    #[serde(default)]
+    pub decimations: Vec<DataloggerDecimation>,
+    /// Free-form remark on the definition (SC3ML only)
+    pub remark: Option<String>,
+    /// Identifier of this unit in an external system (FDSN `resourceId`)
+    pub resource_id: Option<String>,
+}
+
+/// One output rate of a digitizer (SC3ML `datalogger/decimation`).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct DataloggerDecimation {
+    /// Output sample rate in Hz
+    pub sample_rate: f64,
+    /// Analogue filter (poles & zeros) and digital filter (FIR) stages, numbered from 1
+    #[serde(default)]
+    pub stages: Vec<ResponseStage>,
+}
+
+/// One output of an auxiliary device (SC3ML `auxDevice/source`).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AuxSource {
+    /// Source name, unique within the device
+    pub name: String,
+    /// Human-readable description
+    pub description: Option<String>,
+    /// Physical unit of the values (e.g. "hPa", "degC")
+    pub unit: Option<String>,
+    /// Conversion from counts to `unit`, as free text
+    pub conversion: Option<String>,
+    /// Free-form remark
+    pub remark: Option<String>,
+}
+
+/// Gain calibration of one unit, valid for a time window.
+///
+/// Mirrors SeisComP's sensor/datalogger calibration records. When reading
+/// SC3ML, a calibration matching a stream's serial number, channel and start
+/// time replaces the nominal gain of the corresponding response stage.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Calibration {
+    /// Serial number of the calibrated unit
+    pub serial_number: String,
+    /// Component of a multi-channel unit (0-based), if the calibration is per component
+    pub channel: Option<u32>,
+    /// Start of validity
+    pub start: Option<DateTime<Utc>>,
+    /// End of validity (None = still valid)
+    pub end: Option<DateTime<Utc>>,
+    /// Calibrated gain
+    pub gain: Option<f64>,
+    /// Frequency at which `gain` applies (Hz)
+    pub gain_frequency: Option<f64>,
+    /// Free-form remark
+    pub remark: Option<String>,
+}
+
+impl Calibration {
+    /// Whether this calibration is valid at `time`.
+    pub fn is_valid_at(&self, time: DateTime<Utc>) -> bool {
+        self.start.is_none_or(|start| start <= time) && self.end.is_none_or(|end| time < end)
+    }
+
+    /// True if the record only carries a date, as written for an
+    /// [`Equipment::calibration_dates`] entry.
+    pub(crate) fn is_date_only(&self) -> bool {
+        self.channel.is_none()
+            && self.end.is_none()
+            && self.gain.is_none()
+            && self.gain_frequency.is_none()
+            && self.remark.is_none()
+    }
+}
+
+// ─── Extensions ─────────────────────────────────────────────────────
+
+/// Content from other XML namespaces (vendor extensions) kept verbatim.
+///
+/// FDSN StationXML allows foreign attributes and elements on most nodes.
+/// The FDSN reader captures them here and the FDSN writer re-emits them,
+/// so they survive read → write cycles. Content nested below a node that
+/// has no `Extensions` of its own (a `Response`, `Stage`, `Site`,
+/// `Equipment`, ...) is kept on the nearest enclosing node, addressed by a
+/// relative element path. Namespace prefixes used inside `elements` must be
+/// declared either in the fragment itself or through an `xmlns:*` entry in
+/// `attributes` of this node or an ancestor (the reader keeps the root's
+/// declarations on [`Inventory::extensions`]).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Extensions {
+    /// Foreign attributes of this node as (qualified name, unescaped value), e.g.
+    /// `("xmlns:iris", "http://www.fdsn.org/xml/station/1/iris")`
+    #[serde(default)]
+    pub attributes: Vec<(String, String)>,
+    /// Foreign elements of this node and its nested FDSN elements, in document order
+    #[serde(default)]
+    pub elements: Vec<ExtensionElement>,
+    /// Foreign attributes of nested FDSN elements
+    #[serde(default)]
+    pub nested_attributes: Vec<ExtensionAttribute>,
+}
+
+impl Extensions {
+    /// True if there is no extension content.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.elements.is_empty() && self.nested_attributes.is_empty()
+    }
+}
+
+/// A foreign element kept as a raw XML fragment, with its place in the document.
+///
+/// Element paths are relative to the node owning the [`Extensions`] and
+/// list FDSN elements as `Name[index]`, the index counting same-named
+/// siblings from 0, e.g. `Response[0]/Stage[1]/PolesZeros[0]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtensionElement {
+    /// Path of the parent element; empty for children of the owning node
+    #[serde(default)]
+    pub parent: String,
+    /// Number of FDSN sibling elements before the fragment; `None` places it
+    /// after all of them
+    #[serde(default)]
+    pub position: Option<usize>,
+    /// The fragment, e.g. `<iris:Flag>noisy</iris:Flag>`
+    pub xml: String,
+}
+
+impl ExtensionElement {
+    /// A fragment written after all FDSN children of the owning node.
+    pub fn new(xml: impl Into<String>) -> Self {
+        Self {
+            parent: String::new(),
+            position: None,
+            xml: xml.into(),
+        }
+    }
+}
+
+impl From<&str> for ExtensionElement {
+    fn from(xml: &str) -> Self {
+        Self::new(xml)
+    }
+}
+
+impl From<String> for ExtensionElement {
+    fn from(xml: String) -> Self {
+        Self::new(xml)
+    }
+}
+
+/// A foreign attribute of an FDSN element nested inside the owning node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtensionAttribute {
+    /// Path of the element carrying the attribute, see [`ExtensionElement`]
+    pub element: String,
+    /// Qualified attribute name, e.g. `iris:checked`
+    pub name: String,
+    /// Unescaped attribute value
+    pub value: String,
 }
 
 // ─── Response ───────────────────────────────────────────────────────
@@ -169,6 +538,7 @@ pub struct Response {
     pub instrument_sensitivity: Option<InstrumentSensitivity>,
     /// Detailed per-stage response information.
     /// Stage 1 is typically the sensor, stage 2+ are digitizer/filters.
+    #[serde(default)]
     pub stages: Vec<ResponseStage>,
 }
 
@@ -209,6 +579,10 @@ pub struct ResponseStage {
     pub number: u32,
     /// Gain at a reference frequency for this stage
     pub stage_gain: Option<StageGain>,
+    /// Nominal gain of the equipment model when `stage_gain` holds a
+    /// calibrated value of this unit (SC3ML only: the gain of the shared
+    /// `responsePAZ` or `datalogger` definition)
+    pub nominal_gain: Option<StageGain>,
     /// Poles & zeros transfer function (typically stage 1 — sensor)
     pub poles_zeros: Option<PolesZeros>,
     /// Coefficient transfer function
@@ -250,9 +624,13 @@ pub struct PolesZeros {
     /// Frequency at which the normalization factor is computed (Hz)
     pub normalization_frequency: f64,
     /// Zeros of the transfer function (complex numbers)
+    #[serde(default)]
     pub zeros: Vec<PoleZero>,
     /// Poles of the transfer function (complex numbers)
+    #[serde(default)]
     pub poles: Vec<PoleZero>,
+    /// Resource identifier (FDSN `resourceId`, SC3ML `responsePAZ` publicID)
+    pub resource_id: Option<String>,
 }
 
 /// A single complex pole or zero.
@@ -264,6 +642,10 @@ pub struct PoleZero {
     pub real: f64,
     /// Imaginary part of the complex value
     pub imaginary: f64,
+    /// Uncertainty of the real part
+    pub real_uncertainty: Option<Uncertainty>,
+    /// Uncertainty of the imaginary part
+    pub imaginary_uncertainty: Option<Uncertainty>,
 }
 
 /// Transfer function type for poles & zeros.
@@ -287,8 +669,10 @@ pub struct Coefficients {
     /// Transfer function type
     pub cf_transfer_function_type: CfTransferFunction,
     /// Numerator coefficients
+    #[serde(default)]
     pub numerators: Vec<f64>,
     /// Denominator coefficients
+    #[serde(default)]
     pub denominators: Vec<f64>,
 }
 
@@ -313,7 +697,10 @@ pub struct FIR {
     /// Filter symmetry
     pub symmetry: Symmetry,
     /// Numerator coefficients
+    #[serde(default)]
     pub numerator_coefficients: Vec<f64>,
+    /// Resource identifier (FDSN `resourceId`, SC3ML `responseFIR` publicID)
+    pub resource_id: Option<String>,
 }
 
 /// FIR filter symmetry type.
@@ -342,6 +729,80 @@ pub struct Decimation {
     pub correction: f64,
 }
 
+// ─── Shallow copies ─────────────────────────────────────────────────
+
+impl Inventory {
+    /// Copy of the document header without networks.
+    pub(crate) fn without_networks(&self) -> Inventory {
+        Inventory {
+            source: self.source.clone(),
+            sender: self.sender.clone(),
+            created: self.created,
+            module: self.module.clone(),
+            module_uri: self.module_uri.clone(),
+            schema_version: self.schema_version.clone(),
+            extensions: self.extensions.clone(),
+            networks: Vec::new(),
+        }
+    }
+}
+
+impl Network {
+    /// Copy of the network metadata without stations.
+    pub(crate) fn without_stations(&self) -> Network {
+        Network {
+            code: self.code.clone(),
+            description: self.description.clone(),
+            start_date: self.start_date,
+            end_date: self.end_date,
+            restricted_status: self.restricted_status,
+            network_type: self.network_type.clone(),
+            network_class: self.network_class.clone(),
+            archive: self.archive.clone(),
+            shared: self.shared,
+            public_id: self.public_id.clone(),
+            comments: self.comments.clone(),
+            extensions: self.extensions.clone(),
+            stations: Vec::new(),
+        }
+    }
+}
+
+impl Station {
+    /// Copy of the station metadata without channels.
+    pub(crate) fn without_channels(&self) -> Station {
+        Station {
+            code: self.code.clone(),
+            description: self.description.clone(),
+            latitude: self.latitude,
+            longitude: self.longitude,
+            elevation: self.elevation,
+            latitude_uncertainty: self.latitude_uncertainty.clone(),
+            longitude_uncertainty: self.longitude_uncertainty.clone(),
+            elevation_uncertainty: self.elevation_uncertainty.clone(),
+            site: self.site.clone(),
+            water_level: self.water_level,
+            vault: self.vault.clone(),
+            geology: self.geology.clone(),
+            equipment: self.equipment.clone(),
+            external_references: self.external_references.clone(),
+            start_date: self.start_date,
+            end_date: self.end_date,
+            creation_date: self.creation_date,
+            restricted_status: self.restricted_status,
+            station_type: self.station_type.clone(),
+            affiliation: self.affiliation.clone(),
+            archive: self.archive.clone(),
+            archive_network_code: self.archive_network_code.clone(),
+            shared: self.shared,
+            public_id: self.public_id.clone(),
+            comments: self.comments.clone(),
+            extensions: self.extensions.clone(),
+            channels: Vec::new(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -352,6 +813,10 @@ mod tests {
             source: "Test".into(),
             sender: None,
             created: None,
+            module: None,
+            module_uri: None,
+            schema_version: None,
+            extensions: Extensions::default(),
             networks: vec![],
         };
         assert_eq!(inv.source, "Test");
@@ -364,24 +829,53 @@ mod tests {
             source: "Pena Bumi".into(),
             sender: Some("stationxml-rs".into()),
             created: None,
+            module: None,
+            module_uri: None,
+            schema_version: None,
+            extensions: Extensions::default(),
             networks: vec![Network {
                 code: "XX".into(),
                 description: Some("Local Test Network".into()),
                 start_date: None,
                 end_date: None,
+                restricted_status: None,
+                network_type: None,
+                network_class: None,
+                archive: None,
+                shared: None,
+                public_id: None,
+                comments: vec![],
+                extensions: Extensions::default(),
                 stations: vec![Station {
                     code: "PBUMI".into(),
                     description: None,
                     latitude: -7.7714,
                     longitude: 110.3776,
                     elevation: 150.0,
+                    latitude_uncertainty: None,
+                    longitude_uncertainty: None,
+                    elevation_uncertainty: None,
                     site: Site {
                         name: "Yogyakarta".into(),
                         ..Default::default()
                     },
+                    water_level: None,
+                    vault: None,
+                    geology: None,
+                    equipment: vec![],
+                    external_references: vec![],
                     start_date: None,
                     end_date: None,
                     creation_date: None,
+                    restricted_status: None,
+                    station_type: None,
+                    affiliation: None,
+                    archive: None,
+                    archive_network_code: None,
+                    shared: None,
+                    public_id: None,
+                    comments: vec![],
+                    extensions: Extensions::default(),
                     channels: vec![Channel {
                         code: "SHZ".into(),
                         location_code: "00".into(),
@@ -391,16 +885,37 @@ mod tests {
                         depth: 0.0,
                         azimuth: 0.0,
                         dip: -90.0,
+                        latitude_uncertainty: None,
+                        longitude_uncertainty: None,
+                        elevation_uncertainty: None,
+                        depth_uncertainty: None,
+                        azimuth_uncertainty: None,
+                        dip_uncertainty: None,
                         sample_rate: 100.0,
+                        channel_types: vec![],
+                        storage_format: None,
+                        clock_drift: None,
+                        calibration_units: None,
+                        water_level: None,
                         start_date: None,
                         end_date: None,
+                        restricted_status: None,
+                        shared: None,
+                        sensor_location_id: None,
+                        comments: vec![],
+                        sensor_location_comments: vec![],
                         sensor: Some(Equipment {
                             equipment_type: Some("Geophone".into()),
                             model: Some("GS-11D".into()),
                             manufacturer: Some("Geospace".into()),
                             ..Default::default()
                         }),
+                        pre_amplifier: None,
                         data_logger: None,
+                        equipment: vec![],
+                        aux_device: None,
+                        aux_source: None,
+                        extensions: Extensions::default(),
                         response: Some(Response {
                             instrument_sensitivity: Some(InstrumentSensitivity {
                                 value: 53721548.8,
@@ -450,6 +965,8 @@ mod tests {
         let eq = Equipment::default();
         assert!(eq.model.is_none());
         assert!(eq.manufacturer.is_none());
+        assert!(eq.calibration_dates.is_empty());
+        assert!(eq.resource_id.is_none());
     }
 
     #[test]
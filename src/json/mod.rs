@@ -0,0 +1,261 @@
+//! JSON format backend.
+//!
+//! Implements [`StationXmlFormat`] for a JSON encoding of the [`Inventory`]
+//! model, for services and web frontends that exchange inventories without
+//! XML. A document wraps the inventory in a versioned envelope:
+//!
+//! ```json
+//! {
+//!   "schema": "stationxml-rs/inventory",
+//!   "schema_version": "1.0",
+//!   "inventory": { "source": "...", "networks": [ ... ] }
+//! }
+//! ```
+//!
+//! Field names are those of the Rust model (`start_date`, `sample_rate`…),
+//! datetimes are RFC 3339 strings and enumerations their variant names
+//! (`"Open"`, `"LaplaceRadians"`). Optional fields and empty lists may be
+//! omitted on input; the writer always emits every field. JSON has no NaN or
+//! infinity, so inventories holding one fail to write.
+//!
+//! The minor version increases when optional fields are added, the major
+//! version when fields are removed, renamed or change meaning. The reader
+//! accepts any document with the same major version as
+//! [`JSON_SCHEMA_VERSION`]. [`Json::schema`] describes the current version
+//! as a JSON Schema.
+
+mod schema;
+
+use serde::ser::Error as _;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::error::{Result, StationXmlError};
+use crate::format::StationXmlFormat;
+use crate::inventory::Inventory;
+use crate::style::{FloatPolicy, Styled};
+
+/// Value of the envelope's `schema` member.
+pub const JSON_SCHEMA_ID: &str = "stationxml-rs/inventory";
+
+/// Version of the JSON encoding written by [`Json`].
+pub const JSON_SCHEMA_VERSION: &str = "1.0";
+
+/// JSON format marker.
+///
+/// Use this with [`StationXmlFormat`] methods to read/write the JSON encoding.
+///
+/// ```
+/// use stationxml_rs::{Inventory, Json, StationXmlFormat};
+///
+/// let inv = Inventory::builder()
+///     .source("Test")
+///     .network("XX", |net| net.station("STA", |sta| sta.latitude(-7.5)))
+///     .build();
+/// let json = Json::write_to_string(&inv).unwrap();
+/// assert!(json.starts_with(r#"{"schema":"stationxml-rs/inventory","schema_version":"1.0""#));
+/// assert_eq!(Json::read_from_str(&json).unwrap(), inv);
+/// ```
+pub struct Json;
+
+impl Json {
+    /// Serialize an [`Inventory`] to a JSON string with explicit options.
+    pub fn write_to_string_with(
+        inventory: &Inventory,
+        options: &JsonWriteOptions,
+    ) -> Result<String> {
+        let document = DocumentRef::new(inventory);
+        let document = Styled::new(&document, Finite::default());
+        let json = if options.pretty {
+            serde_json::to_string_pretty(&document)?
+        } else {
+            serde_json::to_string(&document)?
+        };
+        Ok(json)
+    }
+
+    /// JSON Schema (draft 2020-12) of the documents written by this version.
+    ///
+    /// ```
+    /// use stationxml_rs::{JSON_SCHEMA_VERSION, Json};
+    ///
+    /// let schema = Json::schema();
+    /// assert_eq!(schema["properties"]["schema_version"]["const"], JSON_SCHEMA_VERSION);
+    /// ```
+    pub fn schema() -> serde_json::Value {
+        schema::document()
+    }
+}
+
+/// Options controlling how JSON is written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonWriteOptions {
+    /// Indent the output with two spaces (default compact)
+    pub pretty: bool,
+}
+
+impl JsonWriteOptions {
+    /// Enable or disable indentation.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+}
+
+#[derive(Serialize)]
+struct DocumentRef<'a> {
+    schema: &'a str,
+    schema_version: &'a str,
+    inventory: &'a Inventory,
+}
+
+impl<'a> DocumentRef<'a> {
+    fn new(inventory: &'a Inventory) -> Self {
+        Self {
+            schema: JSON_SCHEMA_ID,
+            schema_version: JSON_SCHEMA_VERSION,
+            inventory,
+        }
+    }
+}
+
+/// Rejects NaN and infinities, which serde_json would write as `null` and
+/// then fail to read back.
+#[derive(Clone, Copy, Default)]
+struct Finite {
+    /// Struct field holding the value
+    field: &'static str,
+}
+
+impl FloatPolicy for Finite {
+    fn serialize_f64<S: Serializer>(
+        self,
+        value: f64,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        if !value.is_finite() {
+            return Err(S::Error::custom(format!(
+                "cannot write {value} in field '{}': JSON numbers must be finite",
+                self.field
+            )));
+        }
+        serializer.serialize_f64(value)
+    }
+
+    fn field(self, key: &'static str) -> Self {
+        Self { field: key }
+    }
+}
+
+#[derive(Deserialize)]
+struct Document {
+    inventory: Inventory,
+}
+
+/// Envelope members, checked before the inventory is deserialized so that
+/// documents of another schema fail with a clear message.
+#[derive(Deserialize)]
+struct Header {
+    schema: Option<String>,
+    schema_version: Option<String>,
+}
+
+/// Reject documents that are not inventories or use another major version.
+fn check_header(schema: Option<&str>, version: Option<&str>) -> Result<()> {
+    if schema != Some(JSON_SCHEMA_ID) {
+        return Err(StationXmlError::InvalidData(format!(
+            "not a {JSON_SCHEMA_ID} document (schema: {})",
+            schema.unwrap_or("missing")
+        )));
+    }
+    let major = |v: &str| v.split('.').next().map(str::to_string);
+    let version = version.unwrap_or_default();
+    if major(version) != major(JSON_SCHEMA_VERSION) {
+        return Err(StationXmlError::InvalidData(format!(
+            "unsupported JSON schema version '{version}', expected {}.x",
+            major(JSON_SCHEMA_VERSION).unwrap_or_default()
+        )));
+    }
+    Ok(())
+}
+
+impl StationXmlFormat for Json {
+    fn read_from_str(json: &str) -> Result<Inventory> {
+        let header: Header = serde_json::from_str(json)?;
+        check_header(header.schema.as_deref(), header.schema_version.as_deref())?;
+        let document: Document = serde_json::from_str(json)?;
+        Ok(document.inventory)
+    }
+
+    fn read_from_bytes(bytes: &[u8]) -> Result<Inventory> {
+        let json =
+            std::str::from_utf8(bytes).map_err(|e| StationXmlError::InvalidData(e.to_string()))?;
+        Self::read_from_str(json)
+    }
+
+    fn write_to_string(inventory: &Inventory) -> Result<String> {
+        Self::write_to_string_with(inventory, &JsonWriteOptions::default())
+    }
+
+    fn write_to_writer<W: std::io::Write>(inventory: &Inventory, mut writer: W) -> Result<()> {
+        let document = DocumentRef::new(inventory);
+        serde_json::to_writer(&mut writer, &Styled::new(&document, Finite::default()))?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_other_documents() {
+        let err = Json::read_from_str(r#"{"networks": []}"#).unwrap_err();
+        assert!(
+            err.to_string().contains("not a stationxml-rs/inventory"),
+            "{err}"
+        );
+
+        let doc =
+            r#"{"schema": "stationxml-rs/inventory", "schema_version": "2.0", "inventory": {}}"#;
+        let err = Json::read_from_str(doc).unwrap_err();
+        assert!(err.to_string().contains("'2.0', expected 1.x"), "{err}");
+    }
+
+    #[test]
+    fn rejects_non_finite_floats() {
+        let mut inv = Inventory::builder()
+            .source("Test")
+            .network("XX", |net| net.station("STA", |sta| sta.latitude(-7.5)))
+            .build();
+        inv.networks[0].stations[0].elevation = f64::NAN;
+        let err = Json::write_to_string(&inv).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("cannot write NaN in field 'elevation'"),
+            "{err}"
+        );
+
+        inv.networks[0].stations[0].elevation = 0.0;
+        inv.networks[0].stations[0].water_level = Some(f64::INFINITY);
+        let mut out = Vec::new();
+        let err = Json::write_to_writer(&inv, &mut out).unwrap_err();
+        assert!(
+            err.to_string().contains("inf in field 'water_level'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn reads_newer_minor_version_with_defaults() {
+        let doc = r#"{
+            "schema": "stationxml-rs/inventory",
+            "schema_version": "1.7",
+            "inventory": {"source": "Test", "networks": [{"code": "XX"}]}
+        }"#;
+        let inv = Json::read_from_str(doc).unwrap();
+        assert_eq!(inv.networks[0].code, "XX");
+        assert!(inv.networks[0].stations.is_empty());
+        assert!(inv.extensions.is_empty());
+    }
+}
@@ -0,0 +1,523 @@
+//! JSON Schema of the JSON encoding.
+//!
+//! Kept by hand next to the model; the tests validate serialized sample
+//! inventories against it so that new model fields cannot go undeclared.
+
+use serde_json::{Map, Value, json};
+
+use super::{JSON_SCHEMA_ID, JSON_SCHEMA_VERSION};
+
+/// Schema of a whole document, with one `$defs` entry per model type.
+pub(super) fn document() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "stationxml-rs inventory",
+        "type": "object",
+        "properties": {
+            "schema": { "const": JSON_SCHEMA_ID },
+            "schema_version": { "const": JSON_SCHEMA_VERSION },
+            "inventory": reference("Inventory"),
+        },
+        "required": ["schema", "schema_version", "inventory"],
+        "$defs": definitions(),
+    })
+}
+
+fn definitions() -> Value {
+    json!({
+        "Inventory": object(
+            &[
+                ("source", string()),
+                ("sender", optional(string())),
+                ("created", optional(datetime())),
+                ("module", optional(string())),
+                ("module_uri", optional(string())),
+                ("schema_version", optional(string())),
+                ("extensions", reference("Extensions")),
+                ("networks", array(reference("Network"))),
+            ],
+            &["source"],
+        ),
+        "Network": object(
+            &[
+                ("code", string()),
+                ("description", optional(string())),
+                ("start_date", optional(datetime())),
+                ("end_date", optional(datetime())),
+                ("restricted_status", optional(reference("RestrictedStatus"))),
+                ("network_type", optional(string())),
+                ("network_class", optional(string())),
+                ("archive", optional(string())),
+                ("shared", optional(boolean())),
+                ("public_id", optional(string())),
+                ("comments", array(reference("Comment"))),
+                ("extensions", reference("Extensions")),
+                ("stations", array(reference("Station"))),
+            ],
+            &["code"],
+        ),
+        "Station": object(
+            &[
+                ("code", string()),
+                ("description", optional(string())),
+                ("latitude", number()),
+                ("longitude", number()),
+                ("elevation", number()),
+                ("latitude_uncertainty", optional(reference("Uncertainty"))),
+                ("longitude_uncertainty", optional(reference("Uncertainty"))),
+                ("elevation_uncertainty", optional(reference("Uncertainty"))),
+                ("site", reference("Site")),
+                ("water_level", optional(number())),
+                ("vault", optional(string())),
+                ("geology", optional(string())),
+                ("equipment", array(reference("Equipment"))),
+                ("external_references", array(reference("ExternalReference"))),
+                ("start_date", optional(datetime())),
+                ("end_date", optional(datetime())),
+                ("creation_date", optional(datetime())),
+                ("restricted_status", optional(reference("RestrictedStatus"))),
+                ("station_type", optional(string())),
+                ("affiliation", optional(string())),
+                ("archive", optional(string())),
+                ("archive_network_code", optional(string())),
+                ("shared", optional(boolean())),
+                ("public_id", optional(string())),
+                ("comments", array(reference("Comment"))),
+                ("extensions", reference("Extensions")),
+                ("channels", array(reference("Channel"))),
+            ],
+            &["code", "latitude", "longitude", "elevation", "site"],
+        ),
+        "RestrictedStatus": enumeration(&["Open", "Closed", "Partial"]),
+        "Comment": object(
+            &[
+                ("value", string()),
+                ("id", optional(string())),
+                ("subject", optional(string())),
+                ("begin_effective_time", optional(datetime())),
+                ("end_effective_time", optional(datetime())),
+                ("author", optional(string())),
+            ],
+            &["value"],
+        ),
+        "ExternalReference": object(
+            &[("uri", string()), ("description", string())],
+            &["uri", "description"],
+        ),
+        "Site": object(
+            &[
+                ("name", string()),
+                ("description", optional(string())),
+                ("town", optional(string())),
+                ("county", optional(string())),
+                ("region", optional(string())),
+                ("country", optional(string())),
+            ],
+            &["name"],
+        ),
+        "Channel": object(
+            &[
+                ("code", string()),
+                ("location_code", string()),
+                ("latitude", number()),
+                ("longitude", number()),
+                ("elevation", number()),
+                ("depth", number()),
+                ("azimuth", number()),
+                ("dip", number()),
+                ("latitude_uncertainty", optional(reference("Uncertainty"))),
+                ("longitude_uncertainty", optional(reference("Uncertainty"))),
+                ("elevation_uncertainty", optional(reference("Uncertainty"))),
+                ("depth_uncertainty", optional(reference("Uncertainty"))),
+                ("azimuth_uncertainty", optional(reference("Uncertainty"))),
+                ("dip_uncertainty", optional(reference("Uncertainty"))),
+                ("sample_rate", number()),
+                ("channel_types", array(reference("ChannelType"))),
+                ("storage_format", optional(string())),
+                ("clock_drift", optional(number())),
+                ("calibration_units", optional(reference("Units"))),
+                ("water_level", optional(number())),
+                ("start_date", optional(datetime())),
+                ("end_date", optional(datetime())),
+                ("restricted_status", optional(reference("RestrictedStatus"))),
+                ("shared", optional(boolean())),
+                ("sensor_location_id", optional(string())),
+                ("comments", array(reference("Comment"))),
+                ("sensor_location_comments", array(reference("Comment"))),
+                ("sensor", optional(reference("Equipment"))),
+                ("pre_amplifier", optional(reference("Equipment"))),
+                ("data_logger", optional(reference("Equipment"))),
+                ("equipment", array(reference("Equipment"))),
+                ("aux_device", optional(reference("Equipment"))),
+                ("aux_source", optional(reference("AuxSource"))),
+                ("response", optional(reference("Response"))),
+                ("extensions", reference("Extensions")),
+            ],
+            &[
+                "code",
+                "location_code",
+                "latitude",
+                "longitude",
+                "elevation",
+                "depth",
+                "azimuth",
+                "dip",
+                "sample_rate",
+            ],
+        ),
+        "ChannelType": enumeration(&[
+            "Triggered",
+            "Continuous",
+            "Health",
+            "Geophysical",
+            "Weather",
+            "Flag",
+            "Synthesized",
+            "Input",
+            "Experimental",
+            "Maintenance",
+            "Beam",
+        ]),
+        "Uncertainty": object(
+            &[
+                ("plus_error", optional(number())),
+                ("minus_error", optional(number())),
+                ("unit", optional(string())),
+                ("datum", optional(string())),
+            ],
+            &[],
+        ),
+        "Equipment": object(
+            &[
+                ("equipment_type", optional(string())),
+                ("description", optional(string())),
+                ("manufacturer", optional(string())),
+                ("vendor", optional(string())),
+                ("model", optional(string())),
+                ("serial_number", optional(string())),
+                ("channel", optional(unsigned())),
+                ("installation_date", optional(datetime())),
+                ("removal_date", optional(datetime())),
+                ("calibration_dates", array(datetime())),
+                ("calibrations", array(reference("Calibration"))),
+                ("decimations", array(reference("DataloggerDecimation"))),
+                ("remark", optional(string())),
+                ("resource_id", optional(string())),
+            ],
+            &[],
+        ),
+        "DataloggerDecimation": object(
+            &[
+                ("sample_rate", number()),
+                ("stages", array(reference("ResponseStage"))),
+            ],
+            &["sample_rate"],
+        ),
+        "AuxSource": object(
+            &[
+                ("name", string()),
+                ("description", optional(string())),
+                ("unit", optional(string())),
+                ("conversion", optional(string())),
+                ("remark", optional(string())),
+            ],
+            &["name"],
+        ),
+        "Calibration": object(
+            &[
+                ("serial_number", string()),
+                ("channel", optional(unsigned())),
+                ("start", optional(datetime())),
+                ("end", optional(datetime())),
+                ("gain", optional(number())),
+                ("gain_frequency", optional(number())),
+                ("remark", optional(string())),
+            ],
+            &["serial_number"],
+        ),
+        "Extensions": object(
+            &[
+                ("attributes", array(pair(string()))),
+                ("elements", array(reference("ExtensionElement"))),
+                ("nested_attributes", array(reference("ExtensionAttribute"))),
+            ],
+            &[],
+        ),
+        "ExtensionElement": object(
+            &[
+                ("parent", string()),
+                ("position", optional(unsigned())),
+                ("xml", string()),
+            ],
+            &["xml"],
+        ),
+        "ExtensionAttribute": object(
+            &[
+                ("element", string()),
+                ("name", string()),
+                ("value", string()),
+            ],
+            &["element", "name", "value"],
+        ),
+        "Response": object(
+            &[
+                ("instrument_sensitivity", optional(reference("InstrumentSensitivity"))),
+                ("stages", array(reference("ResponseStage"))),
+            ],
+            &[],
+        ),
+        "InstrumentSensitivity": object(
+            &[
+                ("value", number()),
+                ("frequency", number()),
+                ("input_units", reference("Units")),
+                ("output_units", reference("Units")),
+            ],
+            &["value", "frequency", "input_units", "output_units"],
+        ),
+        "Units": object(
+            &[("name", string()), ("description", optional(string()))],
+            &["name"],
+        ),
+        "ResponseStage": object(
+            &[
+                ("number", unsigned()),
+                ("stage_gain", optional(reference("StageGain"))),
+                ("nominal_gain", optional(reference("StageGain"))),
+                ("poles_zeros", optional(reference("PolesZeros"))),
+                ("coefficients", optional(reference("Coefficients"))),
+                ("fir", optional(reference("FIR"))),
+                ("decimation", optional(reference("Decimation"))),
+            ],
+            &["number"],
+        ),
+        "StageGain": object(
+            &[("value", number()), ("frequency", number())],
+            &["value", "frequency"],
+        ),
+        "PolesZeros": object(
+            &[
+                ("input_units", reference("Units")),
+                ("output_units", reference("Units")),
+                ("pz_transfer_function_type", reference("PzTransferFunction")),
+                ("normalization_factor", number()),
+                ("normalization_frequency", number()),
+                ("zeros", array(reference("PoleZero"))),
+                ("poles", array(reference("PoleZero"))),
+                ("resource_id", optional(string())),
+            ],
+            &[
+                "input_units",
+                "output_units",
+                "pz_transfer_function_type",
+                "normalization_factor",
+                "normalization_frequency",
+            ],
+        ),
+        "PoleZero": object(
+            &[
+                ("number", unsigned()),
+                ("real", number()),
+                ("imaginary", number()),
+                ("real_uncertainty", optional(reference("Uncertainty"))),
+                ("imaginary_uncertainty", optional(reference("Uncertainty"))),
+            ],
+            &["number", "real", "imaginary"],
+        ),
+        "PzTransferFunction": enumeration(&[
+            "LaplaceRadians",
+            "LaplaceHertz",
+            "DigitalZTransform",
+        ]),
+        "Coefficients": object(
+            &[
+                ("input_units", reference("Units")),
+                ("output_units", reference("Units")),
+                ("cf_transfer_function_type", reference("CfTransferFunction")),
+                ("numerators", array(number())),
+                ("denominators", array(number())),
+            ],
+            &["input_units", "output_units", "cf_transfer_function_type"],
+        ),
+        "CfTransferFunction": enumeration(&["AnalogRadians", "AnalogHertz", "Digital"]),
+        "FIR": object(
+            &[
+                ("input_units", reference("Units")),
+                ("output_units", reference("Units")),
+                ("symmetry", reference("Symmetry")),
+                ("numerator_coefficients", array(number())),
+                ("resource_id", optional(string())),
+            ],
+            &["input_units", "output_units", "symmetry"],
+        ),
+        "Symmetry": enumeration(&["None", "Even", "Odd"]),
+        "Decimation": object(
+            &[
+                ("input_sample_rate", number()),
+                ("factor", unsigned()),
+                ("offset", unsigned()),
+                ("delay", number()),
+                ("correction", number()),
+            ],
+            &["input_sample_rate", "factor", "offset", "delay", "correction"],
+        ),
+    })
+}
+
+fn object(properties: &[(&str, Value)], required: &[&str]) -> Value {
+    let properties: Map<String, Value> = properties
+        .iter()
+        .map(|(name, schema)| (name.to_string(), schema.clone()))
+        .collect();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn optional(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+/// A 2-tuple, serialized as a two-element array.
+fn pair(items: Value) -> Value {
+    json!({
+        "type": "array",
+        "prefixItems": [items.clone(), items],
+        "minItems": 2,
+        "maxItems": 2,
+    })
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{name}") })
+}
+
+fn enumeration(variants: &[&str]) -> Value {
+    json!({ "enum": variants })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn datetime() -> Value {
+    json!({ "type": "string", "format": "date-time" })
+}
+
+fn number() -> Value {
+    json!({ "type": "number" })
+}
+
+fn unsigned() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::Json;
+    use crate::{Fdsn, Sc3ml, StationXmlFormat};
+
+    /// Check `value` against the subset of JSON Schema used above.
+    fn validate(value: &Value, schema: &Value, defs: &Value, path: &str) -> Vec<String> {
+        if let Some(name) = schema["$ref"].as_str() {
+            let name = name.trim_start_matches("#/$defs/");
+            return validate(value, &defs[name], defs, path);
+        }
+        if let Some(options) = schema["anyOf"].as_array() {
+            let matches = options
+                .iter()
+                .any(|option| validate(value, option, defs, path).is_empty());
+            return if matches {
+                vec![]
+            } else {
+                vec![format!("{path}: matches no alternative")]
+            };
+        }
+        if let Some(variants) = schema["enum"].as_array() {
+            return if variants.contains(value) {
+                vec![]
+            } else {
+                vec![format!("{path}: {value} is not one of {variants:?}")]
+            };
+        }
+        if !schema["const"].is_null() && *value != schema["const"] {
+            return vec![format!("{path}: expected {}", schema["const"])];
+        }
+        let mut errors = Vec::new();
+        let type_ok = match schema["type"].as_str() {
+            Some("object") => value.is_object(),
+            Some("array") => value.is_array(),
+            Some("string") => value.is_string(),
+            Some("number") => value.is_number(),
+            Some("integer") => value.is_u64(),
+            Some("boolean") => value.is_boolean(),
+            Some("null") => value.is_null(),
+            _ => true,
+        };
+        if !type_ok {
+            return vec![format!("{path}: expected {}, got {value}", schema["type"])];
+        }
+        if let Some(object) = value.as_object() {
+            for key in schema["required"].as_array().into_iter().flatten() {
+                if !object.contains_key(key.as_str().unwrap()) {
+                    errors.push(format!("{path}: missing {key}"));
+                }
+            }
+            for (key, member) in object {
+                match schema["properties"].get(key) {
+                    Some(property) => {
+                        errors.extend(validate(member, property, defs, &format!("{path}.{key}")))
+                    }
+                    None => errors.push(format!("{path}: undeclared property {key}")),
+                }
+            }
+        }
+        if let Some(items) = value.as_array() {
+            for (i, item) in items.iter().enumerate() {
+                let schema = schema["prefixItems"].get(i).unwrap_or(&schema["items"]);
+                errors.extend(validate(item, schema, defs, &format!("{path}[{i}]")));
+            }
+        }
+        errors
+    }
+
+    fn check(inventory: &crate::Inventory) {
+        let schema = Json::schema();
+        let value: Value =
+            serde_json::from_str(&Json::write_to_string(inventory).unwrap()).unwrap();
+        let errors = validate(&value, &schema, &schema["$defs"], "$");
+        assert!(errors.is_empty(), "{errors:#?}");
+    }
+
+    #[test]
+    fn samples_match_schema() {
+        let fdsn = include_str!("../../tests/fixtures/fdsn_sample.xml");
+        let sc3ml = include_str!("../../tests/fixtures/sc3ml_sample.xml");
+        check(&Fdsn::read_from_str(fdsn).unwrap());
+        check(&Sc3ml::read_from_str(sc3ml).unwrap());
+    }
+
+    #[test]
+    fn validator_catches_mismatches() {
+        let schema = Json::schema();
+        let doc = json!({
+            "schema": JSON_SCHEMA_ID,
+            "schema_version": JSON_SCHEMA_VERSION,
+            "inventory": { "source": 1, "networks": [{ "code": "XX", "colour": "red" }] },
+        });
+        let errors = validate(&doc, &schema, &schema["$defs"], "$");
+        assert_eq!(errors.len(), 2, "{errors:#?}");
+    }
+}
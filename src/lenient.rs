@@ -0,0 +1,337 @@
+//! Lenient reading: networks, stations, channels and responses that fail to
+//! parse are skipped and reported as warnings instead of failing the whole
+//! document.
+//!
+//! Failures are handled at two levels. Deserialization errors (missing
+//! elements, malformed values) are located in the text; the enclosing
+//! network, station, channel or response element is blanked out and the
+//! document is read again. Conversion errors (unknown enumerations) are
+//! collected by a [`Warnings`] sink while converting, and the failed item is
+//! left out. An unparsable optional value such as a date only loses that
+//! value: the item is kept.
+//!
+//! Each re-read deserializes the whole document again. Deserialization stops
+//! at its first error, so a document with `n` broken elements costs `n + 1`
+//! readings; conversion failures found in one reading are blanked together.
+//! After [`MAX_REREADS`] re-reads the document is given up on and the next
+//! error is returned, as in strict mode.
+
+use crate::error::{Result, StationXmlError};
+use crate::format::ReadOutcome;
+use crate::inventory::Inventory;
+use crate::locate::{self, Segmenter};
+
+/// Re-reads of one document before lenient reading gives up, bounding the
+/// cost of a badly broken document to this many full readings.
+pub(crate) const MAX_REREADS: usize = 100;
+
+/// Items skipped while converting one document.
+///
+/// In strict mode the first error is returned as usual.
+pub(crate) struct Warnings {
+    /// `None` in strict mode
+    skipped: Option<Vec<Skipped>>,
+    /// Index of the item being converted at each nesting level
+    position: Vec<usize>,
+    /// Position of the innermost item that failed, in strict mode
+    failed: Option<Vec<usize>>,
+    /// Optional values left out of the items that were kept
+    dropped: Vec<Skipped>,
+}
+
+/// An item, or an optional value of one, left out during conversion.
+pub(crate) struct Skipped {
+    /// Indices of the item and its ancestors, see [`locate::position_span`]
+    pub(crate) position: Vec<usize>,
+    pub(crate) error: StationXmlError,
+}
+
+impl Warnings {
+    pub(crate) fn strict() -> Self {
+        Self {
+            skipped: None,
+            position: Vec::new(),
+            failed: None,
+            dropped: Vec::new(),
+        }
+    }
+
+    pub(crate) fn lenient() -> Self {
+        Self {
+            skipped: Some(Vec::new()),
+            position: Vec::new(),
+            failed: None,
+            dropped: Vec::new(),
+        }
+    }
+
+    /// Convert each item, leaving out the ones that fail in lenient mode.
+    pub(crate) fn collect<T, U>(
+        &mut self,
+        items: impl IntoIterator<Item = T>,
+        mut convert: impl FnMut(T, &mut Self) -> Result<U>,
+    ) -> Result<Vec<U>> {
+        let mut converted = Vec::new();
+        for (i, item) in items.into_iter().enumerate() {
+            converted.extend(self.nested(i, |w| convert(item, w))?);
+        }
+        Ok(converted)
+    }
+
+    /// Convert the item at `index` among its siblings, `Ok(None)` if it
+    /// fails in lenient mode.
+    pub(crate) fn nested<T>(
+        &mut self,
+        index: usize,
+        convert: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<Option<T>> {
+        self.position.push(index);
+        let result = convert(self);
+        let value = self.item(result);
+        self.position.pop();
+        value
+    }
+
+    /// Convert part `index` of the current item. Unlike [`nested`](Self::nested),
+    /// a failure fails the item even in lenient mode; strict mode still
+    /// locates the error at the part.
+    pub(crate) fn part<T>(
+        &mut self,
+        index: usize,
+        convert: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        self.position.push(index);
+        let result = convert(self);
+        if result.is_err() && self.skipped.is_none() {
+            self.failed.get_or_insert_with(|| self.position.clone());
+        }
+        self.position.pop();
+        result
+    }
+
+    /// `Ok(None)` for a failed item in lenient mode, recording the error.
+    pub(crate) fn item<T>(&mut self, result: Result<T>) -> Result<Option<T>> {
+        match (result, &mut self.skipped) {
+            (Ok(value), _) => Ok(Some(value)),
+            (Err(error), Some(skipped)) => {
+                // The whole item is reported, not the values it lost
+                let position = &self.position;
+                self.dropped.retain(|d| !d.position.starts_with(position));
+                skipped.push(Skipped {
+                    position: self.position.clone(),
+                    error,
+                });
+                Ok(None)
+            }
+            (Err(error), None) => {
+                // Enclosing items see the error again on its way out
+                self.failed.get_or_insert_with(|| self.position.clone());
+                Err(error)
+            }
+        }
+    }
+
+    /// An optional value of the current item, `Ok(None)` if it fails in
+    /// lenient mode. The item itself is kept; the error is reported at its
+    /// `path`.
+    pub(crate) fn optional<T>(
+        &mut self,
+        path: &str,
+        result: Result<Option<T>>,
+    ) -> Result<Option<T>> {
+        match result {
+            Err(error) if self.skipped.is_some() => {
+                self.dropped.push(Skipped {
+                    position: self.position.clone(),
+                    error: error.at_path(path),
+                });
+                Ok(None)
+            }
+            result => result,
+        }
+    }
+
+    /// Locate an error returned in strict mode at the item that failed.
+    pub(crate) fn locate(
+        &self,
+        err: StationXmlError,
+        xml: &str,
+        segmenter: Segmenter,
+    ) -> StationXmlError {
+        match &self.failed {
+            Some(position) => locate::locate_at(err, xml, position, segmenter),
+            None => locate::locate(err, xml, segmenter),
+        }
+    }
+
+    /// The skipped items and the values dropped from kept items.
+    fn into_parts(self) -> (Vec<Skipped>, Vec<Skipped>) {
+        (self.skipped.unwrap_or_default(), self.dropped)
+    }
+}
+
+/// How items skipped during conversion are handled.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rereading {
+    /// Blank the skipped elements and read again, for readers that also map
+    /// elements by position (FDSN extensions)
+    Blank,
+    /// Keep the converted inventory as it is
+    Keep,
+}
+
+/// Read `xml` leniently. `read` performs one strict deserialization and a
+/// conversion that records failed items in the given sink.
+pub(crate) fn read(
+    xml: &str,
+    segmenter: Segmenter,
+    rereading: Rereading,
+    read: impl Fn(&str, &mut Warnings) -> Result<Inventory>,
+) -> Result<ReadOutcome> {
+    let mut doc = xml.to_string();
+    let mut warnings = Vec::new();
+    let mut rereads = 0;
+    loop {
+        let last = rereads == MAX_REREADS;
+        rereads += 1;
+        let mut sink = Warnings::lenient();
+        let err = match read(&doc, &mut sink) {
+            Ok(inventory) => {
+                let (skipped, dropped) = sink.into_parts();
+                if skipped.is_empty() || rereading == Rereading::Keep {
+                    warnings.extend(
+                        skipped
+                            .into_iter()
+                            .chain(dropped)
+                            .map(|s| locate::locate_at(s.error, &doc, &s.position, segmenter)),
+                    );
+                    return Ok(ReadOutcome {
+                        inventory,
+                        warnings,
+                    });
+                }
+                let mut spans = Vec::new();
+                for Skipped { position, error } in skipped {
+                    let span = locate::position_span(&doc, &position, segmenter);
+                    let Some(span) = span.filter(|_| !last) else {
+                        return Err(locate::locate(error, &doc, segmenter));
+                    };
+                    let location = locate::line_column(&doc, span.start);
+                    warnings.push(error.at_location(location));
+                    spans.push(span);
+                }
+                // Dropped values are reported again by the next reading
+                for span in spans {
+                    locate::blank(&mut doc, span);
+                }
+                continue;
+            }
+            Err(err) => err,
+        };
+        // Malformed XML cannot be repaired by dropping an element
+        let recoverable = !matches!(
+            err.root_cause(),
+            StationXmlError::XmlParse(quick_xml::DeError::InvalidXml(_))
+        );
+        let span = err
+            .location()
+            .filter(|_| recoverable && !last)
+            .and_then(|location| locate::enclosing_span(&doc, location.offset, segmenter));
+        let Some(span) = span else {
+            return Err(err);
+        };
+        locate::blank(&mut doc, span);
+        warnings.push(err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_returns_first_error() {
+        let mut sink = Warnings::strict();
+        let result = sink.collect([1, -1, 2], |n: i32, _| {
+            if n < 0 {
+                Err(StationXmlError::InvalidData("negative".into()))
+            } else {
+                Ok(n)
+            }
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn strict_remembers_innermost_failure() {
+        let mut sink = Warnings::strict();
+        let result = sink.collect([vec![1], vec![2, -1]], |items, sink| {
+            sink.collect(items, |n: i32, _| {
+                if n < 0 {
+                    Err(StationXmlError::InvalidData("negative".into()))
+                } else {
+                    Ok(n)
+                }
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(sink.failed, Some(vec![1, 1]));
+    }
+
+    #[test]
+    fn lenient_records_positions() {
+        let mut sink = Warnings::lenient();
+        let result = sink
+            .collect([vec![1, 2], vec![3, -1]], |items, sink| {
+                sink.collect(items, |n: i32, _| {
+                    if n < 0 {
+                        Err(StationXmlError::InvalidData("negative".into()))
+                    } else {
+                        Ok(n)
+                    }
+                })
+            })
+            .unwrap();
+        assert_eq!(result, vec![vec![1, 2], vec![3]]);
+        let (skipped, _) = sink.into_parts();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].position, vec![1, 1]);
+    }
+
+    #[test]
+    fn lenient_keeps_items_with_dropped_values() {
+        let mut sink = Warnings::lenient();
+        let result = sink
+            .collect([1, -1, -2], |n: i32, sink| {
+                let value = sink.optional(
+                    "n",
+                    if n < 0 {
+                        Err(StationXmlError::InvalidData("negative".into()))
+                    } else {
+                        Ok(Some(n))
+                    },
+                )?;
+                if n == -2 {
+                    return Err(StationXmlError::InvalidData("too negative".into()));
+                }
+                Ok(value)
+            })
+            .unwrap();
+        assert_eq!(result, vec![Some(1), None]);
+        let (skipped, dropped) = sink.into_parts();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].position, vec![2]);
+        // The value dropped from the skipped item is not reported twice
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].position, vec![1]);
+        assert_eq!(dropped[0].error.path(), Some("n"));
+    }
+
+    #[test]
+    fn strict_fails_on_optional_values() {
+        let mut sink = Warnings::strict();
+        let value: Result<Option<i32>> =
+            sink.optional("n", Err(StationXmlError::InvalidData("bad".into())));
+        assert!(value.is_err());
+    }
+}
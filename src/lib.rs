@@ -7,15 +7,17 @@
 //!
 //! | Format | Read | Write |
 //! |--------|------|-------|
-//! | FDSN StationXML 1.2 | Yes | Yes |
+//! | FDSN StationXML 1.0--1.2 | Yes | Yes |
 //! | SeisComP SC3ML 0.6--0.13 | Yes | Yes |
+//! | JSON ([`json`] module) | Yes | Yes |
+//! | GeoJSON ([`geojson`] module) | No | Yes |
 //!
 //! # Quick Start
 //!
 //! ```no_run
 //! use stationxml_rs::{read_from_file, write_to_string, Sc3ml};
 //!
-//! // Read any format (auto-detects FDSN or SC3ML)
+//! // Read any format (auto-detects FDSN, SC3ML or JSON)
 //! let inv = read_from_file("station.xml").unwrap();
 //! println!("{} networks", inv.networks.len());
 //!
@@ -38,22 +40,41 @@
 pub mod builder;
 pub mod conversion;
 pub(crate) mod datetime;
+pub mod diff;
 pub mod error;
 pub mod fdsn;
 pub mod format;
+pub mod geojson;
 pub mod inventory;
+pub mod json;
+pub(crate) mod lenient;
+pub(crate) mod locate;
+pub mod merge;
 pub mod sc3ml;
+pub mod select;
 pub mod sensor;
+pub mod style;
+pub mod validate;
 
 pub use builder::InventoryBuilder;
 pub use conversion::AdcConversion;
-pub use error::{Result, StationXmlError};
-pub use fdsn::Fdsn;
-pub use format::{Format, StationXmlFormat, detect_format};
+pub use datetime::parse_datetime;
+pub use diff::{ChangeKind, EpochChange, FieldChange, InventoryDiff};
+pub use error::{Location, Result, StationXmlError};
+pub use fdsn::{Fdsn, FdsnStreamWriter, FdsnVersion, FdsnWriteOptions};
+pub use format::{Format, ReadOptions, ReadOutcome, StationXmlFormat, detect_format};
+pub use geojson::{GeoJson, GeoJsonOptions};
 pub use inventory::*;
-pub use sc3ml::Sc3ml;
+pub use json::{JSON_SCHEMA_ID, JSON_SCHEMA_VERSION, Json, JsonWriteOptions};
+pub use sc3ml::{
+    PublicIdKind, PublicIdRequest, PublicIdStrategy, Sc3ml, Sc3mlVersion, Sc3mlWriteOptions,
+};
+pub use select::Selection;
 pub use sensor::{SensorEntry, find_sensor, load_sensor_library};
+pub use style::{AttributeOrder, FloatFormat, TimeFormat, XmlStyle};
+pub use validate::{Severity, ValidationIssue};
 
+use std::io::Write;
 use std::path::Path;
 
 /// Read from file with auto-format detection.
@@ -67,6 +88,39 @@ pub fn read_from_str(xml: &str) -> Result<Inventory> {
     match detect_format(xml) {
         Some(Format::Fdsn) => Fdsn::read_from_str(xml),
         Some(Format::Sc3ml) => Sc3ml::read_from_str(xml),
+        Some(Format::Json) => Json::read_from_str(xml),
+        None => Err(StationXmlError::UnknownFormat),
+    }
+}
+
+/// Read from file with auto-format detection and explicit options.
+pub fn read_from_file_with(path: impl AsRef<Path>, options: &ReadOptions) -> Result<ReadOutcome> {
+    let content = std::fs::read_to_string(path)?;
+    read_from_str_with(&content, options)
+}
+
+/// Read from string with auto-format detection and explicit options.
+///
+/// ```
+/// use stationxml_rs::{ReadOptions, read_from_str_with};
+///
+/// let xml = r#"<FDSNStationXML schemaVersion="1.2">
+///   <Source>Test</Source><Created>2024-01-01T00:00:00Z</Created>
+///   <Network code="XX" startDate="not a date"/>
+///   <Network code="YY"/>
+/// </FDSNStationXML>"#;
+///
+/// // The network is kept without its start date
+/// let outcome = read_from_str_with(xml, &ReadOptions::default().lenient(true)).unwrap();
+/// assert_eq!(outcome.inventory.networks.len(), 2);
+/// assert_eq!(outcome.inventory.networks[0].start_date, None);
+/// assert_eq!(outcome.warnings[0].path(), Some("XX"));
+/// ```
+pub fn read_from_str_with(xml: &str, options: &ReadOptions) -> Result<ReadOutcome> {
+    match detect_format(xml) {
+        Some(Format::Fdsn) => Fdsn::read_from_str_with(xml, options),
+        Some(Format::Sc3ml) => Sc3ml::read_from_str_with(xml, options),
+        Some(Format::Json) => Json::read_from_str_with(xml, options),
         None => Err(StationXmlError::UnknownFormat),
     }
 }
@@ -82,8 +136,10 @@ pub fn write_to_file<F: StationXmlFormat>(
     path: impl AsRef<Path>,
     inventory: &Inventory,
 ) -> Result<()> {
-    let xml = F::write_to_string(inventory)?;
-    std::fs::write(path, xml)?;
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+    F::write_to_writer(inventory, &mut writer)?;
+    // Dropping the buffer would discard a failed final write
+    writer.flush()?;
     Ok(())
 }
 
@@ -0,0 +1,355 @@
+//! Mapping between byte offsets, line/column positions and logical
+//! `NET.STA.LOC.CHA` paths in an XML document.
+//!
+//! Used by both readers to point errors at the element that caused them.
+//! Each format supplies a [`Segmenter`] naming the path segment an element
+//! contributes, if any; the logical path of an element is the concatenation
+//! of the segments of its ancestors and itself.
+
+use std::ops::Range;
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use serde::de::DeserializeOwned;
+
+use crate::error::{Location, Result, StationXmlError};
+
+/// Path segment contributed by an element, including its leading separator
+/// (e.g. `.PBUMI` for a station, ` stage 3` for a response stage).
+///
+/// A segment led by a space names a part of the enclosing element rather
+/// than an item of its own: lenient reading drops the enclosing element
+/// with it.
+pub(crate) type Segmenter = fn(&BytesStart<'_>) -> Option<String>;
+
+/// Deserialize `xml`, locating any error in the document.
+pub(crate) fn deserialize<T: DeserializeOwned>(xml: &str, segmenter: Segmenter) -> Result<T> {
+    let mut de = quick_xml::de::Deserializer::from_str(xml);
+    T::deserialize(&mut de).map_err(|err| {
+        let reader = de.get_ref().get_ref();
+        let offset = match &err {
+            quick_xml::DeError::InvalidXml(_) => reader.error_position() as usize,
+            // Reported after the deserializer has looked ahead one event past
+            // the incomplete element's end tag
+            quick_xml::DeError::Custom(msg) if msg.starts_with("missing field") => {
+                before_lookahead(xml, reader.buffer_position() as usize)
+            }
+            _ => reader.buffer_position() as usize,
+        };
+        let err = StationXmlError::from(err).at_location(line_column(xml, offset));
+        match path_at(xml, offset, segmenter) {
+            Some(path) => err.at_path(&path),
+            None => err,
+        }
+    })
+}
+
+/// Add the document position of the element named by the error's path, if
+/// the error has a path but no position yet.
+///
+/// Paths with no element of their own (an SC3ML stream has no stage
+/// children) resolve to their closest ancestor that has one.
+pub(crate) fn locate(err: StationXmlError, xml: &str, segmenter: Segmenter) -> StationXmlError {
+    if err.location().is_some() {
+        return err;
+    }
+    let mut path = err.path().unwrap_or_default();
+    while !path.is_empty() {
+        if let Some(offset) = offset_of(xml, path, segmenter) {
+            return err.at_location(line_column(xml, offset));
+        }
+        path = &path[..path.rfind(['.', ' ']).unwrap_or(0)];
+    }
+    err
+}
+
+/// Add the document position of the path element at `position` (see
+/// [`position_span`]), the item a reader was converting when the error
+/// occurred. Unlike a path, a position tells repeated epochs apart; errors
+/// without one are located by [`locate`].
+pub(crate) fn locate_at(
+    err: StationXmlError,
+    xml: &str,
+    position: &[usize],
+    segmenter: Segmenter,
+) -> StationXmlError {
+    if err.location().is_some() {
+        return err;
+    }
+    match position_span(xml, position, segmenter) {
+        Some(span) => err.at_location(line_column(xml, span.start)),
+        None => locate(err, xml, segmenter),
+    }
+}
+
+/// End of the last end tag before the tag that finishes at `offset`.
+fn before_lookahead(xml: &str, offset: usize) -> usize {
+    let before = &xml[..offset.min(xml.len())];
+    let lookahead = before.rfind('<').unwrap_or(0);
+    before[..lookahead]
+        .rfind("</")
+        .and_then(|start| before[start..].find('>').map(|end| start + end + 1))
+        .unwrap_or(offset)
+}
+
+/// Line and column of a byte offset.
+pub(crate) fn line_column(xml: &str, offset: usize) -> Location {
+    let mut end = offset.min(xml.len());
+    while !xml.is_char_boundary(end) {
+        end -= 1;
+    }
+    let before = &xml[..end];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Location {
+        offset,
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+/// Logical path of the innermost element open at `offset`.
+///
+/// An element whose end tag finishes exactly at `offset` still counts as
+/// open: the deserializer reports missing fields after consuming the end tag.
+pub(crate) fn path_at(xml: &str, offset: usize, segmenter: Segmenter) -> Option<String> {
+    let path: String = open_elements(xml, offset, segmenter)
+        .into_iter()
+        .filter_map(|(segment, _)| segment)
+        .collect();
+    (!path.is_empty()).then_some(path)
+}
+
+/// Byte range of the innermost path element (network, station, …) open at
+/// `offset`, by the same rule as [`path_at`], skipping parts of elements
+/// (see [`Segmenter`]).
+pub(crate) fn enclosing_span(
+    xml: &str,
+    offset: usize,
+    segmenter: Segmenter,
+) -> Option<Range<usize>> {
+    let (_, start) = open_elements(xml, offset, segmenter)
+        .into_iter()
+        .rfind(|(segment, _)| segment.as_deref().is_some_and(|s| !s.starts_with(' ')))?;
+    Some(start..element_end(xml, start)?)
+}
+
+/// Segment and start offset of each element open at `offset`, outermost first.
+fn open_elements(xml: &str, offset: usize, segmenter: Segmenter) -> Vec<(Option<String>, usize)> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack = Vec::new();
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader.read_event();
+        let end = reader.buffer_position() as usize;
+        match event {
+            Ok(Event::Start(e)) if end <= offset => {
+                stack.push((segmenter(&e), start + xml[start..].find('<').unwrap_or(0)));
+            }
+            Ok(Event::End(_)) if end < offset => {
+                stack.pop();
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ if end > offset => break,
+            _ => {}
+        }
+    }
+    stack
+}
+
+/// Byte range of the path element at `position`: the indices of it and its
+/// path ancestors among the path elements of the same name in their parent,
+/// as produced by a reader converting each kind of element in document order.
+pub(crate) fn position_span(
+    xml: &str,
+    position: &[usize],
+    segmenter: Segmenter,
+) -> Option<Range<usize>> {
+    let mut reader = Reader::from_str(xml);
+    // Per open element: whether it is a path element
+    let mut open: Vec<bool> = Vec::new();
+    // Position of the innermost open path element, and the number of path
+    // elements of each name seen so far below each open path element (and
+    // the root)
+    let mut current: Vec<usize> = Vec::new();
+    let mut children: Vec<Vec<(Vec<u8>, usize)>> = vec![Vec::new()];
+    loop {
+        let start = reader.buffer_position() as usize;
+        let (e, empty) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, false),
+            Ok(Event::Empty(e)) => (e, true),
+            Ok(Event::End(_)) => {
+                if open.pop() == Some(true) {
+                    current.pop();
+                    children.pop();
+                }
+                continue;
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => continue,
+        };
+        let is_path = segmenter(&e).is_some();
+        if is_path {
+            let counts = children.last_mut()?;
+            let name = e.local_name().as_ref().to_vec();
+            let count = match counts.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, count)) => count,
+                None => {
+                    counts.push((name, 0));
+                    &mut counts.last_mut()?.1
+                }
+            };
+            current.push(*count);
+            *count += 1;
+            if current == position {
+                let start = start + xml[start..].find('<').unwrap_or(0);
+                return Some(start..element_end(xml, start)?);
+            }
+            if empty {
+                current.pop();
+            } else {
+                children.push(Vec::new());
+            }
+        }
+        if !empty {
+            open.push(is_path);
+        }
+    }
+}
+
+/// End offset of the element starting at `start`.
+fn element_end(xml: &str, start: usize) -> Option<usize> {
+    let mut reader = Reader::from_str(&xml[start..]);
+    let mut depth = 0usize;
+    loop {
+        match reader.read_event().ok()? {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            Event::Empty(_) if depth == 0 => {}
+            Event::Eof => return None,
+            _ => {}
+        }
+        if depth == 0 {
+            return Some(start + reader.buffer_position() as usize);
+        }
+    }
+}
+
+/// Replace `span` with whitespace of the same byte length, keeping line
+/// breaks, so that offsets and line numbers elsewhere stay valid.
+pub(crate) fn blank(xml: &mut String, span: Range<usize>) {
+    let blanked: String = xml[span.clone()]
+        .chars()
+        .map(|c| match c {
+            '\n' => "\n".to_string(),
+            c => " ".repeat(c.len_utf8()),
+        })
+        .collect();
+    xml.replace_range(span, &blanked);
+}
+
+/// Byte offset of the first element with logical path `path`.
+pub(crate) fn offset_of(xml: &str, path: &str, segmenter: Segmenter) -> Option<usize> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<String> = Vec::new();
+    loop {
+        let start = reader.buffer_position() as usize;
+        let (e, empty) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, false),
+            Ok(Event::Empty(e)) => (e, true),
+            Ok(Event::End(_)) => {
+                stack.pop();
+                continue;
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => continue,
+        };
+        let mut current = stack.last().cloned().unwrap_or_default();
+        if let Some(segment) = segmenter(&e) {
+            current.push_str(&segment);
+            if current == path {
+                // Skip whitespace between the previous event and the tag
+                return Some(start + xml[start..].find('<').unwrap_or(0));
+            }
+        }
+        if !empty {
+            stack.push(current);
+        }
+    }
+}
+
+/// Value of attribute `name`, unescaped.
+pub(crate) fn attribute(e: &BytesStart<'_>, name: &[u8]) -> Option<String> {
+    e.try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|a| a.unescape_value().ok())
+        .map(|v| v.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XML: &str = "<root>\n  <net code=\"XX\">\n    <sta code=\"ABC\"><x>1</x></sta>\n    <sta code=\"DÉF\"/>\n  </net>\n</root>";
+
+    fn segment(e: &BytesStart<'_>) -> Option<String> {
+        match e.local_name().as_ref() {
+            b"net" => attribute(e, b"code"),
+            b"sta" => attribute(e, b"code").map(|code| format!(".{code}")),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn line_and_column() {
+        let offset = XML.find("<x>").unwrap();
+        let location = line_column(XML, offset);
+        assert_eq!((location.line, location.column), (3, 21));
+        assert_eq!(line_column(XML, 0).line, 1);
+        // Columns count characters, not bytes
+        let after = XML.find("/>").unwrap();
+        assert_eq!(line_column(XML, after).column, 20);
+    }
+
+    #[test]
+    fn paths_and_offsets() {
+        assert_eq!(
+            path_at(XML, XML.find("1</x>").unwrap(), segment).as_deref(),
+            Some("XX.ABC")
+        );
+        let end_of_sta = XML.find("</sta>").unwrap() + "</sta>".len();
+        assert_eq!(path_at(XML, end_of_sta, segment).as_deref(), Some("XX.ABC"));
+        assert_eq!(path_at(XML, end_of_sta + 1, segment).as_deref(), Some("XX"));
+        assert_eq!(path_at(XML, 3, segment), None);
+
+        assert_eq!(
+            offset_of(XML, "XX.DÉF", segment),
+            XML.find("<sta code=\"DÉF\"")
+        );
+        assert_eq!(offset_of(XML, "XX.NOPE", segment), None);
+
+        let err = locate(
+            StationXmlError::InvalidData("bad".into()).at_path("XX.ABC stage 2"),
+            XML,
+            segment,
+        );
+        assert_eq!(err.location().map(|l| l.line), Some(3));
+    }
+
+    #[test]
+    fn spans_and_blanking() {
+        let second = position_span(XML, &[0, 1], segment).unwrap();
+        assert_eq!(&XML[second.clone()], "<sta code=\"DÉF\"/>");
+        let first = enclosing_span(XML, XML.find("1</x>").unwrap(), segment).unwrap();
+        assert_eq!(&XML[first.clone()], "<sta code=\"ABC\"><x>1</x></sta>");
+        assert_eq!(position_span(XML, &[0, 2], segment), None);
+
+        let mut xml = XML.to_string();
+        blank(&mut xml, second);
+        blank(&mut xml, first);
+        assert_eq!(xml.len(), XML.len());
+        assert_eq!(xml.lines().count(), XML.lines().count());
+        assert_eq!(offset_of(&xml, "XX.ABC", segment), None);
+        assert_eq!(offset_of(&xml, "XX", segment), XML.find("<net"));
+    }
+}
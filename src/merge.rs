@@ -0,0 +1,132 @@
+//! Merging of inventories from several files into one.
+
+use crate::inventory::*;
+
+impl Inventory {
+    /// Merge `other` into this inventory.
+    ///
+    /// Networks, stations and channels are matched like in
+    /// [`Inventory::diff`]: by code (plus location code for channels) and
+    /// start date. Unmatched epochs are appended. Matched epochs take
+    /// `other`'s metadata, so later files win. The document header of `self`
+    /// is kept.
+    ///
+    /// Returns the paths (`NET`, `NET.STA`, `NET.STA.LOC.CHA`) of epochs
+    /// whose content was overwritten with different values.
+    ///
+    /// ```
+    /// use stationxml_rs::Inventory;
+    ///
+    /// let mut a = Inventory::builder()
+    ///     .network("XX", |net| net.station("STA1", |sta| sta))
+    ///     .build();
+    /// let b = Inventory::builder()
+    ///     .network("XX", |net| net.station("STA2", |sta| sta))
+    ///     .build();
+    ///
+    /// let replaced = a.merge(b);
+    /// assert!(replaced.is_empty());
+    /// assert_eq!(a.networks.len(), 1);
+    /// assert_eq!(a.networks[0].stations.len(), 2);
+    /// ```
+    pub fn merge(&mut self, other: Inventory) -> Vec<String> {
+        let mut replaced = Vec::new();
+        for mut net in other.networks {
+            let existing = self
+                .networks
+                .iter_mut()
+                .find(|n| n.code == net.code && n.start_date == net.start_date);
+            let Some(existing) = existing else {
+                self.networks.push(net);
+                continue;
+            };
+            let stations = std::mem::take(&mut net.stations);
+            if existing.without_stations() != net {
+                replaced.push(net.code.clone());
+                net.stations = std::mem::take(&mut existing.stations);
+                *existing = net;
+            }
+            for sta in stations {
+                merge_station(existing, sta, &mut replaced);
+            }
+        }
+        replaced
+    }
+}
+
+fn merge_station(net: &mut Network, mut sta: Station, replaced: &mut Vec<String>) {
+    let existing = net
+        .stations
+        .iter_mut()
+        .find(|s| s.code == sta.code && s.start_date == sta.start_date);
+    let Some(existing) = existing else {
+        net.stations.push(sta);
+        return;
+    };
+    let path = format!("{}.{}", net.code, sta.code);
+    let channels = std::mem::take(&mut sta.channels);
+    if existing.without_channels() != sta {
+        replaced.push(path.clone());
+        sta.channels = std::mem::take(&mut existing.channels);
+        *existing = sta;
+    }
+    for ch in channels {
+        let same = existing.channels.iter_mut().find(|c| {
+            c.code == ch.code
+                && c.location_code == ch.location_code
+                && c.start_date == ch.start_date
+        });
+        match same {
+            Some(same) if *same != ch => {
+                replaced.push(format!("{path}.{}.{}", ch.location_code, ch.code));
+                *same = ch;
+            }
+            Some(_) => {}
+            None => existing.channels.push(ch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory(sample_rate: f64) -> Inventory {
+        Inventory::builder()
+            .source("A")
+            .network("XX", |net| {
+                net.station("PBUMI", |sta| {
+                    sta.channel("SHZ", "00", |ch| ch.sample_rate(sample_rate))
+                })
+            })
+            .build()
+    }
+
+    #[test]
+    fn merge_identical_is_noop() {
+        let mut inv = inventory(100.0);
+        assert!(inv.merge(inventory(100.0)).is_empty());
+        assert_eq!(inv, inventory(100.0));
+    }
+
+    #[test]
+    fn later_epochs_win() {
+        let mut inv = inventory(100.0);
+        let mut other = inventory(200.0);
+        other.source = "B".into();
+        other.networks[0].description = Some("Updated".into());
+        other.networks[0].stations[0].channels[0].location_code = "10".into();
+        other.networks[0].stations[0]
+            .channels
+            .push(inventory(200.0).networks[0].stations[0].channels[0].clone());
+
+        let replaced = inv.merge(other);
+        assert_eq!(replaced, vec!["XX", "XX.PBUMI.00.SHZ"]);
+        assert_eq!(inv.source, "A");
+        assert_eq!(inv.networks[0].description.as_deref(), Some("Updated"));
+        let channels = &inv.networks[0].stations[0].channels;
+        assert_eq!(channels.len(), 2);
+        assert_eq!(channels[0].sample_rate, 200.0);
+        assert_eq!(channels[1].location_code, "10");
+    }
+}
@@ -1,17 +1,23 @@
-//! SeisComP SC3ML 0.13 format backend.
+//! SeisComP SC3ML format backend.
 //!
 //! Implements [`StationXmlFormat`] for reading and writing
-//! SeisComP SC3ML documents (versions 0.6–0.13).
+//! SeisComP SC3ML documents (versions 0.6–0.13, writing 0.13 by default).
 
+mod public_id;
 pub(crate) mod reader;
 pub(crate) mod types;
 pub(crate) mod writer;
 
+use chrono::{DateTime, Utc};
+
 use crate::error::Result;
-use crate::format::StationXmlFormat;
+use crate::format::{ReadOptions, ReadOutcome, StationXmlFormat};
 use crate::inventory::Inventory;
+use crate::style::XmlStyle;
+
+pub use public_id::{PublicIdKind, PublicIdRequest, PublicIdStrategy};
 
-/// SeisComP SC3ML 0.13 format marker.
+/// SeisComP SC3ML format marker.
 ///
 /// Use this with [`StationXmlFormat`] methods to read/write SC3ML.
 ///
@@ -23,6 +29,144 @@ use crate::inventory::Inventory;
 /// ```
 pub struct Sc3ml;
 
+impl Sc3ml {
+    /// Serialize an [`Inventory`] to an SC3ML string with explicit options.
+    ///
+    /// ```
+    /// use stationxml_rs::{Inventory, Sc3ml, Sc3mlVersion, Sc3mlWriteOptions};
+    ///
+    /// let inv = Inventory::builder().source("Test").build();
+    /// let opts = Sc3mlWriteOptions::default().version(Sc3mlVersion::V0_9);
+    /// let xml = Sc3ml::write_to_string_with(&inv, &opts).unwrap();
+    /// assert!(xml.contains(r#"version="0.9""#));
+    /// ```
+    pub fn write_to_string_with(
+        inventory: &Inventory,
+        options: &Sc3mlWriteOptions,
+    ) -> Result<String> {
+        writer::write_to_string_with(inventory, options)
+    }
+}
+
+/// SC3ML schema version.
+///
+/// Each version has its own namespace
+/// (`http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/<version>`). When
+/// writing an older version, elements that version lacks are omitted:
+///
+/// | Element | Since |
+/// |---------|-------|
+/// | `responseFIR/gainFrequency` | 0.7 |
+/// | `stream/gainUnit` | 0.9 |
+/// | `comment` (network, station, stream) | 0.10 |
+/// | `calibration` (sensor, datalogger) | 0.10 |
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Sc3mlVersion {
+    /// SC3ML 0.6
+    V0_6,
+    /// SC3ML 0.7
+    V0_7,
+    /// SC3ML 0.8
+    V0_8,
+    /// SC3ML 0.9
+    V0_9,
+    /// SC3ML 0.10
+    V0_10,
+    /// SC3ML 0.11
+    V0_11,
+    /// SC3ML 0.12
+    V0_12,
+    /// SC3ML 0.13
+    #[default]
+    V0_13,
+}
+
+impl Sc3mlVersion {
+    /// The `version` attribute value, e.g. `"0.13"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V0_6 => "0.6",
+            Self::V0_7 => "0.7",
+            Self::V0_8 => "0.8",
+            Self::V0_9 => "0.9",
+            Self::V0_10 => "0.10",
+            Self::V0_11 => "0.11",
+            Self::V0_12 => "0.12",
+            Self::V0_13 => "0.13",
+        }
+    }
+
+    /// Parse a `version` attribute value. Returns `None` for unknown versions.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim() {
+            "0.6" => Some(Self::V0_6),
+            "0.7" => Some(Self::V0_7),
+            "0.8" => Some(Self::V0_8),
+            "0.9" => Some(Self::V0_9),
+            "0.10" => Some(Self::V0_10),
+            "0.11" => Some(Self::V0_11),
+            "0.12" => Some(Self::V0_12),
+            "0.13" => Some(Self::V0_13),
+            _ => None,
+        }
+    }
+
+    /// XML namespace of this schema version.
+    pub fn namespace(&self) -> String {
+        format!(
+            "http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/{}",
+            self.as_str()
+        )
+    }
+}
+
+/// Options controlling how SC3ML is written.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sc3mlWriteOptions {
+    /// Target schema version (default 0.13)
+    pub version: Sc3mlVersion,
+    /// How `publicID`s are generated (default [`PublicIdStrategy::Sequential`])
+    pub public_ids: PublicIdStrategy,
+    /// Indentation, float and attribute formatting (default compact)
+    pub style: XmlStyle,
+    /// End time written for open network, station and stream epochs, for
+    /// consumers that expect a far-future sentinel; omitted when unset
+    pub open_end: Option<DateTime<Utc>>,
+}
+
+impl Sc3mlWriteOptions {
+    /// Set the target schema version.
+    pub fn version(mut self, version: Sc3mlVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Set the `publicID` generation strategy.
+    ///
+    /// ```
+    /// use stationxml_rs::{PublicIdStrategy, Sc3mlWriteOptions};
+    ///
+    /// let opts = Sc3mlWriteOptions::default().public_ids(PublicIdStrategy::Nslc);
+    /// assert_eq!(opts.public_ids, PublicIdStrategy::Nslc);
+    /// ```
+    pub fn public_ids(mut self, strategy: PublicIdStrategy) -> Self {
+        self.public_ids = strategy;
+        self
+    }
+
+    /// Set the output formatting, e.g. [`XmlStyle::pretty`].
+    pub fn style(mut self, style: XmlStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Write `sentinel` as the end time of open epochs.
+    pub fn open_end(mut self, sentinel: DateTime<Utc>) -> Self {
+        self.open_end = Some(sentinel);
+        self
+    }
+}
+
 impl StationXmlFormat for Sc3ml {
     fn read_from_str(xml: &str) -> Result<Inventory> {
         reader::read_from_str(xml)
@@ -32,6 +176,10 @@ impl StationXmlFormat for Sc3ml {
         reader::read_from_bytes(bytes)
     }
 
+    fn read_from_str_with(xml: &str, options: &ReadOptions) -> Result<ReadOutcome> {
+        reader::read_from_str_with(xml, options)
+    }
+
     fn write_to_string(inventory: &Inventory) -> Result<String> {
         writer::write_to_string(inventory)
     }
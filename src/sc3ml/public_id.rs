@@ -0,0 +1,338 @@
+//! `publicID` generation strategies for the SC3ML writer.
+//!
+//! SC3ML links sensors, dataloggers and responses to streams through
+//! `publicID` references. SeisComP tools such as `scinv merge` compare
+//! objects by these IDs, so regenerating them on every write shows up as
+//! spurious changes. [`PublicIdStrategy`] selects how IDs are derived.
+
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+/// Kind of SC3ML object a `publicID` is generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PublicIdKind {
+    /// `<network>`
+    Network,
+    /// `<station>`
+    Station,
+    /// `<sensorLocation>`
+    SensorLocation,
+    /// `<auxDevice>`
+    AuxDevice,
+    /// `<sensor>`
+    Sensor,
+    /// `<datalogger>`
+    Datalogger,
+    /// `<responsePAZ>`
+    ResponsePaz,
+    /// `<responseFIR>`
+    ResponseFir,
+}
+
+impl PublicIdKind {
+    /// Conventional `publicID` prefix, e.g. `"ResponsePAZ"`.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Self::Network => "Network",
+            Self::Station => "Station",
+            Self::SensorLocation => "SensorLocation",
+            Self::AuxDevice => "AuxDevice",
+            Self::Sensor => "Sensor",
+            Self::Datalogger => "Datalogger",
+            Self::ResponsePaz => "ResponsePAZ",
+            Self::ResponseFir => "ResponseFIR",
+        }
+    }
+}
+
+/// Everything known about an object when its `publicID` is generated.
+///
+/// Networks, stations and sensor locations are described by their codes
+/// and epoch start. Equipment and responses may be shared between streams,
+/// so they are described by their model and content only: which channel
+/// happens to use them first must not change their ID.
+#[derive(Debug, Clone)]
+pub struct PublicIdRequest<'a> {
+    /// Object kind
+    pub kind: PublicIdKind,
+    /// Network code (networks, stations and sensor locations)
+    pub network: Option<&'a str>,
+    /// Station code (stations and sensor locations)
+    pub station: Option<&'a str>,
+    /// Location code (sensor locations)
+    pub location: Option<&'a str>,
+    /// Channel code (currently never set; reserved for per-stream objects)
+    pub channel: Option<&'a str>,
+    /// Start of the epoch the object belongs to
+    pub start: Option<DateTime<Utc>>,
+    /// Equipment model name, if known
+    pub model: Option<&'a str>,
+    /// Canonical description of the object's content; equal content gives equal keys
+    pub content: &'a str,
+}
+
+impl PublicIdRequest<'_> {
+    /// `NET.STA.LOC.CHA`-style path of the object, as far as it is known;
+    /// empty for equipment and responses.
+    pub fn nslc(&self) -> String {
+        [self.network, self.station, self.location, self.channel]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// True for the equipment and response definitions streams refer to.
+    pub fn is_definition(&self) -> bool {
+        !matches!(
+            self.kind,
+            PublicIdKind::Network | PublicIdKind::Station | PublicIdKind::SensorLocation
+        )
+    }
+}
+
+/// How the SC3ML writer generates `publicID`s.
+///
+/// Objects that carry a publicID preserved from a previous read
+/// ([`Network::public_id`](crate::Network::public_id),
+/// [`Equipment::resource_id`](crate::Equipment::resource_id), …) keep it;
+/// the strategy only names the rest. Whatever the strategy, the writer
+/// guarantees uniqueness within a document by appending `/2`, `/3`, … to an
+/// ID that was already handed out.
+#[derive(Clone, Default)]
+pub enum PublicIdStrategy {
+    /// Counter-based IDs (`ResponsePAZ/3`, `Sensor/GS-11D_2`, …). Any change
+    /// to the inventory may renumber later objects.
+    #[default]
+    Sequential,
+    /// Networks, stations and sensor locations are named by their codes and
+    /// epoch start, e.g. `SensorLocation/XX/PBUMI/00/20240601T000000Z`.
+    /// Equipment and responses are shared between streams and have no codes
+    /// of their own; they get their model and a content hash, e.g.
+    /// `Sensor/GS-11D/5d9c0e6f3a1b2c47`.
+    Nslc,
+    /// Equipment and responses are identified by a hash of their content
+    /// (e.g. `ResponsePAZ/5d9c0e6f3a1b2c47`), so identical definitions keep
+    /// the same ID across documents. Networks, stations and sensor locations
+    /// use the [`Nslc`](Self::Nslc) form.
+    ContentHash,
+    /// Caller-supplied generator.
+    Custom(Arc<dyn Fn(&PublicIdRequest) -> String + Send + Sync>),
+}
+
+impl PublicIdStrategy {
+    /// Wrap a closure as a [`Custom`](Self::Custom) strategy.
+    pub fn custom(f: impl Fn(&PublicIdRequest) -> String + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(f))
+    }
+
+    /// Generate an ID. `counter` is the running sequence used by
+    /// [`Sequential`](Self::Sequential).
+    pub(crate) fn generate(&self, req: &PublicIdRequest, counter: &mut u32) -> String {
+        match self {
+            Self::Sequential => sequential(req, counter),
+            Self::Nslc => nslc(req),
+            Self::ContentHash if req.is_definition() => {
+                format!("{}/{:016x}", req.kind.prefix(), fnv1a(req.content))
+            }
+            Self::ContentHash => nslc(req),
+            Self::Custom(f) => f(req),
+        }
+    }
+}
+
+impl fmt::Debug for PublicIdStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sequential => f.write_str("Sequential"),
+            Self::Nslc => f.write_str("Nslc"),
+            Self::ContentHash => f.write_str("ContentHash"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl PartialEq for PublicIdStrategy {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Sequential, Self::Sequential)
+            | (Self::Nslc, Self::Nslc)
+            | (Self::ContentHash, Self::ContentHash) => true,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// The writer's historical counter-based scheme.
+fn sequential(req: &PublicIdRequest, counter: &mut u32) -> String {
+    let mut next = || {
+        let id = *counter;
+        *counter += 1;
+        id
+    };
+    let model = || req.model.unwrap_or("unknown").replace([' ', '/'], "_");
+    match req.kind {
+        PublicIdKind::Network => {
+            next();
+            format!("Network/{}", req.network.unwrap_or(""))
+        }
+        PublicIdKind::Station => format!(
+            "Station/{}/{}",
+            req.network.unwrap_or(""),
+            req.station.unwrap_or("")
+        ),
+        PublicIdKind::SensorLocation => format!(
+            "SensorLocation/{}/{}/{}/{}",
+            req.network.unwrap_or(""),
+            req.station.unwrap_or(""),
+            req.location.unwrap_or(""),
+            next()
+        ),
+        PublicIdKind::AuxDevice => format!("AuxDevice/{}_{}", model(), next()),
+        PublicIdKind::Sensor => format!("Sensor/{}_{}", model(), next()),
+        PublicIdKind::Datalogger => format!("Datalogger/{}_{}", model(), next()),
+        PublicIdKind::ResponsePaz => format!("ResponsePAZ/{}", next()),
+        PublicIdKind::ResponseFir => format!("ResponseFIR/{}", next()),
+    }
+}
+
+fn nslc(req: &PublicIdRequest) -> String {
+    let mut id = req.kind.prefix().to_string();
+    if req.is_definition() {
+        if let Some(model) = req.model {
+            id.push('/');
+            id.push_str(&model.replace([' ', '/'], "_"));
+        }
+        id.push_str(&format!("/{:016x}", fnv1a(req.content)));
+        return id;
+    }
+    // Hierarchy nodes keep the slash-separated form SeisComP tools use
+    for part in [req.network, req.station, req.location]
+        .into_iter()
+        .flatten()
+    {
+        id.push('/');
+        id.push_str(part);
+    }
+    if let Some(start) = req.start {
+        id.push('/');
+        id.push_str(&start.format("%Y%m%dT%H%M%SZ").to_string());
+    }
+    id
+}
+
+/// 64-bit FNV-1a — small, stable across platforms and Rust versions.
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn request(kind: PublicIdKind) -> PublicIdRequest<'static> {
+        PublicIdRequest {
+            kind,
+            network: None,
+            station: None,
+            location: None,
+            channel: None,
+            start: None,
+            model: Some("GS-11D"),
+            content: "GS-11D|Geospace",
+        }
+    }
+
+    fn location_request() -> PublicIdRequest<'static> {
+        PublicIdRequest {
+            kind: PublicIdKind::SensorLocation,
+            network: Some("XX"),
+            station: Some("PBUMI"),
+            location: Some("00"),
+            channel: None,
+            start: Some(Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap()),
+            model: None,
+            content: "00",
+        }
+    }
+
+    #[test]
+    fn sequential_matches_legacy_scheme() {
+        let mut counter = 1;
+        let strategy = PublicIdStrategy::Sequential;
+        let sensor = strategy.generate(&request(PublicIdKind::Sensor), &mut counter);
+        let paz = strategy.generate(&request(PublicIdKind::ResponsePaz), &mut counter);
+        assert_eq!(sensor, "Sensor/GS-11D_1");
+        assert_eq!(paz, "ResponsePAZ/2");
+    }
+
+    #[test]
+    fn nslc_ids() {
+        let mut counter = 1;
+        let strategy = PublicIdStrategy::Nslc;
+        assert_eq!(
+            strategy.generate(&location_request(), &mut counter),
+            "SensorLocation/XX/PBUMI/00/20240601T000000Z"
+        );
+
+        let mut sta = location_request();
+        sta.kind = PublicIdKind::Station;
+        sta.location = None;
+        sta.start = None;
+        assert_eq!(strategy.generate(&sta, &mut counter), "Station/XX/PBUMI");
+
+        // Shared definitions are named by model and content alone
+        let sensor = strategy.generate(&request(PublicIdKind::Sensor), &mut counter);
+        assert_eq!(
+            sensor,
+            format!("Sensor/GS-11D/{:016x}", fnv1a("GS-11D|Geospace"))
+        );
+        let mut fir = request(PublicIdKind::ResponseFir);
+        fir.model = None;
+        fir.content = "A|[1.0]";
+        assert_eq!(
+            strategy.generate(&fir, &mut counter),
+            format!("ResponseFIR/{:016x}", fnv1a("A|[1.0]"))
+        );
+        assert_eq!(counter, 1);
+    }
+
+    #[test]
+    fn content_hash_depends_only_on_content() {
+        let mut counter = 1;
+        let strategy = PublicIdStrategy::ContentHash;
+        let a = strategy.generate(&request(PublicIdKind::Sensor), &mut counter);
+        let mut other = request(PublicIdKind::Sensor);
+        other.model = Some("renamed");
+        let b = strategy.generate(&other, &mut counter);
+        assert_eq!(a, b);
+        assert!(a.starts_with("Sensor/"));
+
+        other.content = "STS-2|Streckeisen";
+        assert_ne!(strategy.generate(&other, &mut counter), a);
+    }
+
+    #[test]
+    fn fnv1a_reference_values() {
+        assert_eq!(fnv1a(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn custom_strategy() {
+        let strategy = PublicIdStrategy::custom(|req| format!("smi:test/{}", req.nslc()));
+        let mut counter = 1;
+        assert_eq!(
+            strategy.generate(&location_request(), &mut counter),
+            "smi:test/XX.PBUMI.00"
+        );
+        assert_eq!(strategy, strategy.clone());
+        assert_ne!(strategy, PublicIdStrategy::Nslc);
+    }
+}
@@ -7,16 +7,43 @@
 
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
+use quick_xml::events::BytesStart;
+
 use crate::datetime::parse_datetime_opt;
 use crate::error::{Result, StationXmlError};
+use crate::format::{ReadOptions, ReadOutcome};
 use crate::inventory::*;
+use crate::lenient::{self, Rereading, Warnings};
+use crate::locate::{self, attribute};
 
 use super::types::*;
 
 /// Parse SC3ML XML string into an [`Inventory`].
+///
+/// Errors carry the line/column and `NET.STA.LOC.CHA` path of the offending
+/// element where they can be determined.
 pub(crate) fn read_from_str(xml: &str) -> Result<Inventory> {
-    let root: Sc3mlRoot = quick_xml::de::from_str(xml)?;
-    sc3ml_to_inventory(root)
+    let mut warnings = Warnings::strict();
+    read_document(xml, &mut warnings).map_err(|e| warnings.locate(e, xml, path_segment))
+}
+
+/// Parse SC3ML with explicit options, skipping failed items in lenient mode.
+pub(crate) fn read_from_str_with(xml: &str, options: &ReadOptions) -> Result<ReadOutcome> {
+    let outcome = if options.lenient {
+        lenient::read(xml, path_segment, Rereading::Keep, read_document)?
+    } else {
+        ReadOutcome {
+            inventory: read_from_str(xml)?,
+            warnings: Vec::new(),
+        }
+    };
+    Ok(options.normalize(outcome))
+}
+
+fn read_document(xml: &str, warnings: &mut Warnings) -> Result<Inventory> {
+    let root: Sc3mlRoot = locate::deserialize(xml, path_segment)?;
+    sc3ml_to_inventory(root, warnings)
 }
 
 /// Parse SC3ML XML bytes into an [`Inventory`].
@@ -26,6 +53,21 @@ pub(crate) fn read_from_bytes(bytes: &[u8]) -> Result<Inventory> {
     read_from_str(xml)
 }
 
+/// Path segment an SC3ML element contributes to error paths. Top-level
+/// definitions are identified by their publicID.
+fn path_segment(e: &BytesStart<'_>) -> Option<String> {
+    match e.local_name().as_ref() {
+        b"network" => attribute(e, b"code"),
+        b"station" | b"sensorLocation" | b"stream" | b"auxStream" => {
+            attribute(e, b"code").map(|code| format!(".{code}"))
+        }
+        b"sensor" | b"datalogger" | b"auxDevice" | b"responsePAZ" | b"responseFIR" => {
+            attribute(e, b"publicID")
+        }
+        _ => None,
+    }
+}
+
 // ─── Response definition enum ────────────────────────────────────────
 
 /// A resolved response definition (PAZ or FIR).
@@ -34,9 +76,25 @@ enum ResponseDef<'a> {
     Fir(&'a Sc3mlResponseFir),
 }
 
+/// Top-level definitions referenced by streams, keyed by publicID.
+struct Lookup<'a> {
+    sensors: HashMap<&'a str, &'a Sc3mlSensor>,
+    dataloggers: HashMap<&'a str, &'a Sc3mlDatalogger>,
+    aux_devices: HashMap<&'a str, &'a Sc3mlAuxDevice>,
+    responses: HashMap<&'a str, ResponseDef<'a>>,
+    /// Serial numbers of the units installed on streams, per sensor or datalogger publicID
+    installed: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl Lookup<'_> {
+    fn installed_serials(&self, public_id: &str) -> &[&str] {
+        self.installed.get(public_id).map_or(&[], Vec::as_slice)
+    }
+}
+
 // ─── Main conversion ─────────────────────────────────────────────────
 
-fn sc3ml_to_inventory(root: Sc3mlRoot) -> Result<Inventory> {
+fn sc3ml_to_inventory(root: Sc3mlRoot, w: &mut Warnings) -> Result<Inventory> {
     let inv = &root.inventory;
 
     // Build lookup maps for top-level definitions
@@ -52,6 +110,12 @@ fn sc3ml_to_inventory(root: Sc3mlRoot) -> Result<Inventory> {
         .map(|d| (d.public_id.as_str(), d))
         .collect();
 
+    let aux_devices: HashMap<&str, &Sc3mlAuxDevice> = inv
+        .aux_devices
+        .iter()
+        .map(|d| (d.public_id.as_str(), d))
+        .collect();
+
     let mut responses: HashMap<&str, ResponseDef> = HashMap::new();
     for paz in &inv.response_paz {
         responses.insert(paz.public_id.as_str(), ResponseDef::Paz(paz));
@@ -60,59 +124,90 @@ fn sc3ml_to_inventory(root: Sc3mlRoot) -> Result<Inventory> {
         responses.insert(fir.public_id.as_str(), ResponseDef::Fir(fir));
     }
 
-    let networks = inv
+    let mut installed: HashMap<&str, Vec<&str>> = HashMap::new();
+    let streams = inv
         .networks
         .iter()
-        .map(|net| convert_network(net, &sensors, &dataloggers, &responses))
-        .collect::<Result<Vec<_>>>()?;
+        .flat_map(|net| &net.stations)
+        .flat_map(|sta| &sta.sensor_locations)
+        .flat_map(|loc| loc.seismic_streams());
+    for stream in streams {
+        for (id, serial) in [
+            (&stream.sensor, &stream.sensor_serial_number),
+            (&stream.datalogger, &stream.datalogger_serial_number),
+        ] {
+            if let (Some(id), Some(serial)) = (id, serial) {
+                installed.entry(id).or_default().push(serial);
+            }
+        }
+    }
+
+    let lookup = Lookup {
+        sensors,
+        dataloggers,
+        aux_devices,
+        responses,
+        installed,
+    };
+
+    let networks = w.collect(&inv.networks, |net, w| {
+        convert_network(net, &lookup, w).map_err(|e| e.at_path(&net.code))
+    })?;
 
     Ok(Inventory {
         source: "SeisComP".into(),
         sender: None,
         created: None,
+        module: None,
+        module_uri: None,
+        schema_version: root.version.clone(),
+        extensions: Extensions::default(),
         networks,
     })
 }
 
 // ─── Hierarchy conversion ────────────────────────────────────────────
 
-fn convert_network(
-    net: &Sc3mlNetwork,
-    sensors: &HashMap<&str, &Sc3mlSensor>,
-    dataloggers: &HashMap<&str, &Sc3mlDatalogger>,
-    responses: &HashMap<&str, ResponseDef>,
-) -> Result<Network> {
-    let stations = net
-        .stations
-        .iter()
-        .map(|sta| convert_station(sta, sensors, dataloggers, responses))
-        .collect::<Result<Vec<_>>>()?;
+fn convert_network(net: &Sc3mlNetwork, lookup: &Lookup, w: &mut Warnings) -> Result<Network> {
+    let stations = w.collect(&net.stations, |sta, w| {
+        let path = format!("{}.{}", net.code, sta.code);
+        convert_station(sta, &path, lookup, w).map_err(|e| e.at_path(&path))
+    })?;
 
     Ok(Network {
         code: net.code.clone(),
         description: net.description.clone(),
-        start_date: parse_datetime_opt(&net.start)?,
-        end_date: parse_datetime_opt(&net.end)?,
+        start_date: w.optional(&net.code, parse_datetime_opt(&net.start))?,
+        end_date: w.optional(&net.code, parse_datetime_opt(&net.end))?,
+        restricted_status: net.restricted.map(restricted_status),
+        network_type: net.network_type.clone(),
+        network_class: net.net_class.clone(),
+        archive: net.archive.clone(),
+        shared: net.shared,
+        public_id: public_id(&net.public_id),
+        comments: convert_comments(&net.comments, &net.code, w)?,
+        extensions: Extensions::default(),
         stations,
     })
 }
 
 fn convert_station(
     sta: &Sc3mlStation,
-    sensors: &HashMap<&str, &Sc3mlSensor>,
-    dataloggers: &HashMap<&str, &Sc3mlDatalogger>,
-    responses: &HashMap<&str, ResponseDef>,
+    path: &str,
+    lookup: &Lookup,
+    w: &mut Warnings,
 ) -> Result<Station> {
     // Flatten sensorLocation → channels
-    let mut channels = Vec::new();
-    for loc in &sta.sensor_locations {
-        for stream in &loc.streams {
-            let ch = convert_stream(stream, loc, sta, sensors, dataloggers, responses)?;
-            channels.push(ch);
-        }
-    }
+    let channels = w
+        .collect(&sta.sensor_locations, |loc, w| {
+            convert_sensor_location(loc, path, sta, lookup, w)
+        })?
+        .into_iter()
+        .flatten()
+        .collect();
 
-    // Use station description as site name if available, else use place or station code
+    // SeisComP uses the station description as the FDSN site name; fall back
+    // to place or station code. Place itself maps to the site town.
     let site_name = sta
         .description
         .clone()
@@ -125,54 +220,88 @@ fn convert_station(
         latitude: sta.latitude,
         longitude: sta.longitude,
         elevation: sta.elevation,
+        latitude_uncertainty: None,
+        longitude_uncertainty: None,
+        elevation_uncertainty: None,
         site: Site {
             name: site_name,
+            town: sta.place.clone(),
             country: sta.country.clone(),
             ..Default::default()
         },
-        start_date: parse_datetime_opt(&sta.start)?,
-        end_date: parse_datetime_opt(&sta.end)?,
+        water_level: None,
+        vault: None,
+        geology: None,
+        equipment: vec![],
+        external_references: vec![],
+        start_date: w.optional(path, parse_datetime_opt(&sta.start))?,
+        end_date: w.optional(path, parse_datetime_opt(&sta.end))?,
         creation_date: None,
+        restricted_status: sta.restricted.map(restricted_status),
+        station_type: sta.station_type.clone(),
+        affiliation: sta.affiliation.clone(),
+        archive: sta.archive.clone(),
+        archive_network_code: sta.archive_network_code.clone(),
+        shared: sta.shared,
+        public_id: public_id(&sta.public_id),
+        comments: convert_comments(&sta.comments, path, w)?,
+        extensions: Extensions::default(),
         channels,
     })
 }
 
 fn convert_stream(
     stream: &Sc3mlStream,
+    path: &str,
     loc: &Sc3mlSensorLocation,
     sta: &Sc3mlStation,
-    sensors: &HashMap<&str, &Sc3mlSensor>,
-    dataloggers: &HashMap<&str, &Sc3mlDatalogger>,
-    responses: &HashMap<&str, ResponseDef>,
+    lookup: &Lookup,
+    w: &mut Warnings,
 ) -> Result<Channel> {
     // Use sensorLocation coordinates if available, else fall back to station
     let latitude = loc.latitude.unwrap_or(sta.latitude);
     let longitude = loc.longitude.unwrap_or(sta.longitude);
     let elevation = loc.elevation.unwrap_or(sta.elevation);
 
-    // Compute sample rate
-    let sample_rate = if stream.sample_rate_denominator > 0 {
-        stream.sample_rate_numerator as f64 / stream.sample_rate_denominator as f64
-    } else {
-        0.0
-    };
+    let sample_rate = fraction_rate(stream.sample_rate_numerator, stream.sample_rate_denominator);
 
     // Resolve sensor equipment
     let sensor = stream
         .sensor
         .as_deref()
-        .and_then(|id| sensors.get(id))
-        .map(|s| convert_sensor_equipment(s, stream));
+        .and_then(|id| lookup.sensors.get(id))
+        .map(|s| convert_sensor_equipment(s, stream, path, lookup, w))
+        .transpose()?;
 
     // Resolve datalogger equipment
     let data_logger = stream
         .datalogger
         .as_deref()
-        .and_then(|id| dataloggers.get(id))
-        .map(|d| convert_datalogger_equipment(d, stream));
+        .and_then(|id| lookup.dataloggers.get(id))
+        .map(|d| convert_datalogger_equipment(d, stream, path, lookup, w))
+        .transpose()?;
+
+    // SC3ML maxClockDrift is in seconds per second; FDSN uses seconds per sample
+    let clock_drift = stream
+        .datalogger
+        .as_deref()
+        .and_then(|id| lookup.dataloggers.get(id))
+        .and_then(|d| d.max_clock_drift)
+        .filter(|_| sample_rate > 0.0)
+        .map(|drift| drift / sample_rate);
+
+    // Calibrations of the installed units override nominal gains
+    let start = w.optional(path, parse_datetime_opt(&stream.start))?;
+    let calibrations = StreamCalibrations {
+        sensor: select_calibration(sensor.as_ref(), stream.sensor_channel, start),
+        datalogger: select_calibration(data_logger.as_ref(), stream.datalogger_channel, start),
+    };
 
-    // Build response
-    let response = build_response(stream, sensors, dataloggers, responses)?;
+    // A response missing a stage would be wrong, not just incomplete: in
+    // lenient mode a failing stage drops the whole response
+    let response = w
+        .item(build_response(stream, path, lookup, &calibrations))?
+        .flatten();
 
     Ok(Channel {
         code: stream.code.clone(),
@@ -183,72 +312,531 @@ fn convert_stream(
         depth: stream.depth,
         azimuth: stream.azimuth,
         dip: stream.dip,
+        latitude_uncertainty: None,
+        longitude_uncertainty: None,
+        elevation_uncertainty: None,
+        depth_uncertainty: None,
+        azimuth_uncertainty: None,
+        dip_uncertainty: None,
         sample_rate,
-        start_date: parse_datetime_opt(&stream.start)?,
-        end_date: parse_datetime_opt(&stream.end)?,
+        channel_types: stream
+            .flags
+            .as_deref()
+            .map(parse_stream_flags)
+            .transpose()?
+            .unwrap_or_default(),
+        storage_format: stream.format.clone(),
+        clock_drift,
+        calibration_units: None,
+        water_level: None,
+        start_date: start,
+        end_date: w.optional(path, parse_datetime_opt(&stream.end))?,
+        restricted_status: stream.restricted.map(restricted_status),
+        shared: stream.shared,
+        sensor_location_id: public_id(&loc.public_id),
+        comments: convert_comments(&stream.comments, path, w)?,
+        // Filled in by `convert_sensor_location`
+        sensor_location_comments: vec![],
         sensor,
+        pre_amplifier: None,
         data_logger,
+        equipment: vec![],
+        aux_device: None,
+        aux_source: None,
         response,
+        extensions: Extensions::default(),
+    })
+}
+
+/// Convert the streams and aux streams of a sensor location, in document order.
+fn convert_sensor_location(
+    loc: &Sc3mlSensorLocation,
+    path: &str,
+    sta: &Sc3mlStation,
+    lookup: &Lookup,
+    w: &mut Warnings,
+) -> Result<Vec<Channel>> {
+    let comments = convert_comments(&loc.comments, &format!("{path}.{}", loc.code), w)?;
+    // Positions count streams and aux streams separately, see `position_span`
+    let (mut streams, mut aux_streams) = (0, 0);
+    let mut channels = Vec::new();
+    for child in &loc.streams {
+        let ch = match child {
+            Sc3mlLocationStream::Stream(stream) => {
+                let path = format!("{path}.{}.{}", loc.code, stream.code);
+                streams += 1;
+                w.nested(streams - 1, |w| {
+                    convert_stream(stream, &path, loc, sta, lookup, w).map_err(|e| e.at_path(&path))
+                })?
+            }
+            Sc3mlLocationStream::Aux(aux) => {
+                let path = format!("{path}.{}.{}", loc.code, aux.code);
+                aux_streams += 1;
+                w.nested(aux_streams - 1, |w| {
+                    convert_aux_stream(aux, &path, loc, sta, &lookup.aux_devices, w)
+                        .map_err(|e| e.at_path(&path))
+                })?
+            }
+            Sc3mlLocationStream::Other => continue,
+        };
+        channels.extend(ch.map(|ch| Channel {
+            sensor_location_comments: comments.clone(),
+            ..ch
+        }));
+    }
+    Ok(channels)
+}
+
+/// Convert an `auxStream` into a channel without sensor, data logger or response.
+fn convert_aux_stream(
+    aux: &Sc3mlAuxStream,
+    path: &str,
+    loc: &Sc3mlSensorLocation,
+    sta: &Sc3mlStation,
+    aux_devices: &HashMap<&str, &Sc3mlAuxDevice>,
+    w: &mut Warnings,
+) -> Result<Channel> {
+    let device = aux.device.as_deref().and_then(|id| aux_devices.get(id));
+    let source = aux.source.as_deref().map(|name| {
+        device
+            .and_then(|d| d.sources.iter().find(|s| s.name == name))
+            .ok_or(name)
+    });
+
+    let sample_rate = match source {
+        Some(Ok(Sc3mlAuxSource {
+            sample_rate_numerator: Some(num),
+            sample_rate_denominator: Some(denom),
+            ..
+        })) if *denom > 0 => *num as f64 / *denom as f64,
+        _ => 0.0,
+    };
+    let start_date = w.optional(path, parse_datetime_opt(&aux.start))?;
+
+    Ok(Channel {
+        code: aux.code.clone(),
+        location_code: loc.code.clone(),
+        latitude: loc.latitude.unwrap_or(sta.latitude),
+        longitude: loc.longitude.unwrap_or(sta.longitude),
+        elevation: loc.elevation.unwrap_or(sta.elevation),
+        depth: location_depth(loc, start_date),
+        // SC3ML gives auxiliary streams no orientation; 0/0 is how FDSN
+        // describes a non-directional channel
+        azimuth: 0.0,
+        dip: 0.0,
+        latitude_uncertainty: None,
+        longitude_uncertainty: None,
+        elevation_uncertainty: None,
+        depth_uncertainty: None,
+        azimuth_uncertainty: None,
+        dip_uncertainty: None,
+        sample_rate,
+        channel_types: aux
+            .flags
+            .as_deref()
+            .map(parse_stream_flags)
+            .transpose()?
+            .unwrap_or_default(),
+        storage_format: aux.format.clone(),
+        clock_drift: None,
+        calibration_units: None,
+        water_level: None,
+        start_date,
+        end_date: w.optional(path, parse_datetime_opt(&aux.end))?,
+        restricted_status: aux.restricted.map(restricted_status),
+        shared: aux.shared,
+        sensor_location_id: public_id(&loc.public_id),
+        comments: vec![],
+        sensor_location_comments: vec![],
+        sensor: None,
+        pre_amplifier: None,
+        data_logger: None,
+        equipment: vec![],
+        // Keep the reference even if the device definition is missing
+        aux_device: Some(match device {
+            Some(d) => Equipment {
+                equipment_type: Some("AuxDevice".into()),
+                description: d.description.clone(),
+                manufacturer: d.manufacturer.clone(),
+                model: d.model.clone().or_else(|| d.name.clone()),
+                serial_number: aux.device_serial_number.clone(),
+                remark: d.remark.clone(),
+                resource_id: public_id(&d.public_id),
+                ..Default::default()
+            },
+            None => Equipment {
+                equipment_type: Some("AuxDevice".into()),
+                serial_number: aux.device_serial_number.clone(),
+                resource_id: aux.device.clone(),
+                ..Default::default()
+            },
+        }),
+        aux_source: source.map(|source| match source {
+            Ok(s) => AuxSource {
+                name: s.name.clone(),
+                description: s.description.clone(),
+                unit: s.unit.clone(),
+                conversion: s.conversion.clone(),
+                remark: s.remark.clone(),
+            },
+            Err(name) => AuxSource {
+                name: name.into(),
+                ..Default::default()
+            },
+        }),
+        response: None,
+        extensions: Extensions::default(),
     })
 }
 
+/// Depth of the instruments at a sensor location, for an auxiliary stream
+/// starting at `start`: SC3ML only records depth on streams, so take it from
+/// the stream whose epoch covers that time, else from the first stream.
+fn location_depth(loc: &Sc3mlSensorLocation, start: Option<DateTime<Utc>>) -> f64 {
+    let covers = |stream: &&Sc3mlStream| {
+        let epoch_start = parse_datetime_opt(&stream.start).ok().flatten();
+        let epoch_end = parse_datetime_opt(&stream.end).ok().flatten();
+        match start {
+            Some(t) => epoch_start.is_none_or(|s| s <= t) && epoch_end.is_none_or(|e| t < e),
+            None => false,
+        }
+    };
+    loc.seismic_streams()
+        .find(covers)
+        .or_else(|| loc.seismic_streams().next())
+        .map_or(0.0, |stream| stream.depth)
+}
+
+fn convert_comments(
+    comments: &[Sc3mlComment],
+    path: &str,
+    w: &mut Warnings,
+) -> Result<Vec<Comment>> {
+    comments
+        .iter()
+        .map(|c| {
+            Ok(Comment {
+                value: c.text.clone(),
+                id: c.id.clone(),
+                subject: None,
+                begin_effective_time: w.optional(path, parse_datetime_opt(&c.start))?,
+                end_effective_time: w.optional(path, parse_datetime_opt(&c.end))?,
+                author: c.creation_info.as_ref().and_then(|ci| ci.author.clone()),
+            })
+        })
+        .collect()
+}
+
+/// Keep a publicID in the model; empty IDs are treated as absent.
+fn public_id(id: &str) -> Option<String> {
+    (!id.is_empty()).then(|| id.to_string())
+}
+
+/// SC3ML only knows restricted or not.
+fn restricted_status(restricted: bool) -> RestrictedStatus {
+    if restricted {
+        RestrictedStatus::Closed
+    } else {
+        RestrictedStatus::Open
+    }
+}
+
+/// Parse SEED-style stream flags (e.g. `"GC"`) into channel types.
+fn parse_stream_flags(flags: &str) -> Result<Vec<ChannelType>> {
+    flags
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            'T' => Ok(ChannelType::Triggered),
+            'C' => Ok(ChannelType::Continuous),
+            'H' => Ok(ChannelType::Health),
+            'G' => Ok(ChannelType::Geophysical),
+            'W' => Ok(ChannelType::Weather),
+            'F' => Ok(ChannelType::Flag),
+            'S' => Ok(ChannelType::Synthesized),
+            'I' => Ok(ChannelType::Input),
+            'E' => Ok(ChannelType::Experimental),
+            'M' => Ok(ChannelType::Maintenance),
+            'B' => Ok(ChannelType::Beam),
+            _ => Err(StationXmlError::InvalidData(format!(
+                "unknown stream flag '{c}' in '{flags}'"
+            ))),
+        })
+        .collect()
+}
+
 // ─── Equipment conversion ────────────────────────────────────────────
 
-fn convert_sensor_equipment(sensor: &Sc3mlSensor, stream: &Sc3mlStream) -> Equipment {
-    Equipment {
+fn convert_sensor_equipment(
+    sensor: &Sc3mlSensor,
+    stream: &Sc3mlStream,
+    path: &str,
+    lookup: &Lookup,
+    w: &mut Warnings,
+) -> Result<Equipment> {
+    let (calibrations, calibration_dates) = convert_calibrations(
+        &sensor.calibrations,
+        stream.sensor_serial_number.as_deref(),
+        lookup.installed_serials(&sensor.public_id),
+        &format!("{path} sensor"),
+        w,
+    )?;
+    Ok(Equipment {
         equipment_type: sensor.sensor_type.clone().or(sensor.description.clone()),
         description: sensor.description.clone(),
         manufacturer: sensor.manufacturer.clone(),
         vendor: None,
         model: sensor.model.clone(),
         serial_number: stream.sensor_serial_number.clone(),
+        channel: stream.sensor_channel,
         installation_date: None,
         removal_date: None,
-    }
+        calibration_dates,
+        calibrations,
+        decimations: vec![],
+        remark: sensor.remark.clone(),
+        resource_id: public_id(&sensor.public_id),
+    })
 }
 
-fn convert_datalogger_equipment(dl: &Sc3mlDatalogger, stream: &Sc3mlStream) -> Equipment {
-    Equipment {
+fn convert_datalogger_equipment(
+    dl: &Sc3mlDatalogger,
+    stream: &Sc3mlStream,
+    path: &str,
+    lookup: &Lookup,
+    w: &mut Warnings,
+) -> Result<Equipment> {
+    let (calibrations, calibration_dates) = convert_calibrations(
+        &dl.calibrations,
+        stream.datalogger_serial_number.as_deref(),
+        lookup.installed_serials(&dl.public_id),
+        &format!("{path} datalogger"),
+        w,
+    )?;
+
+    // Keep the decimations this stream does not use, so the definition
+    // survives a round-trip; the stream's own one becomes its response. Like
+    // a response, a decimation with a failing filter is dropped as a whole
+    let mut decimations = Vec::new();
+    for decim in dl.decimations.iter().filter(|d| {
+        (d.sample_rate_numerator, d.sample_rate_denominator)
+            != (stream.sample_rate_numerator, stream.sample_rate_denominator)
+    }) {
+        let decimation = convert_decimation(decim, lookup).map_err(|e| {
+            e.at_path(&format!(
+                "{path} datalogger decimation {}/{}",
+                decim.sample_rate_numerator, decim.sample_rate_denominator
+            ))
+        });
+        decimations.extend(w.item(decimation)?);
+    }
+
+    Ok(Equipment {
         equipment_type: Some("Datalogger".into()),
         description: dl.description.clone(),
         manufacturer: None,
         vendor: None,
         model: dl.name.clone(),
         serial_number: stream.datalogger_serial_number.clone(),
+        channel: stream.datalogger_channel,
         installation_date: None,
         removal_date: None,
+        calibration_dates,
+        calibrations,
+        decimations,
+        remark: dl.remark.clone(),
+        resource_id: public_id(&dl.public_id),
+    })
+}
+
+/// Convert a datalogger decimation into its filter stages.
+fn convert_decimation(decim: &Sc3mlDecimation, lookup: &Lookup) -> Result<DataloggerDecimation> {
+    let chains = resolve_decimation(decim, lookup);
+    let mut stages = Vec::new();
+    for paz in chains.analogue {
+        stages.push(convert_paz_to_stage(
+            paz,
+            stages.len() as u32 + 1,
+            "V",
+            "V",
+        )?);
+    }
+    for (fir, input_rate) in chains.firs {
+        stages.push(convert_fir_to_stage(
+            fir,
+            stages.len() as u32 + 1,
+            input_rate,
+        )?);
+    }
+    Ok(DataloggerDecimation {
+        sample_rate: fraction_rate(decim.sample_rate_numerator, decim.sample_rate_denominator),
+        stages,
+    })
+}
+
+/// Filter chains of a datalogger decimation, resolved to their definitions.
+struct DecimationChains<'a> {
+    analogue: Vec<&'a Sc3mlResponsePaz>,
+    /// FIR filters with their input sample rates
+    firs: Vec<(&'a Sc3mlResponseFir, f64)>,
+    /// Input rate of the first FIR: the digitizer's own (ADC) rate
+    adc_rate: f64,
+}
+
+fn resolve_decimation<'a>(decim: &Sc3mlDecimation, lookup: &Lookup<'a>) -> DecimationChains<'a> {
+    let analogue = decim
+        .analogue_filter_chain
+        .iter()
+        .flat_map(|chain| chain.split_whitespace())
+        .filter_map(|ref_id| match lookup.responses.get(ref_id) {
+            Some(ResponseDef::Paz(paz)) => Some(*paz),
+            _ => None,
+        })
+        .collect();
+    let firs: Vec<&Sc3mlResponseFir> = decim
+        .digital_filter_chain
+        .iter()
+        .flat_map(|chain| chain.split_whitespace())
+        .filter_map(|ref_id| match lookup.responses.get(ref_id) {
+            Some(ResponseDef::Fir(fir)) => Some(*fir),
+            _ => None,
+        })
+        .collect();
+
+    // Walk the chain backward from the output rate: each FIR's input
+    // rate is its output rate times its decimation factor
+    let mut input_rates = vec![0.0; firs.len()];
+    let mut rate = fraction_rate(decim.sample_rate_numerator, decim.sample_rate_denominator);
+    for (fir, input_rate) in firs.iter().zip(&mut input_rates).rev() {
+        rate *= fir.decimation_factor.unwrap_or(1) as f64;
+        *input_rate = rate;
+    }
+
+    DecimationChains {
+        analogue,
+        firs: firs.into_iter().zip(input_rates).collect(),
+        adc_rate: rate,
+    }
+}
+
+/// Sample rate in Hz of an SC3ML numerator/denominator pair; 0 if undefined.
+fn fraction_rate(numerator: u32, denominator: u32) -> f64 {
+    if denominator > 0 {
+        numerator as f64 / denominator as f64
+    } else {
+        0.0
     }
 }
 
+/// Convert the calibrations of the unit with `serial_number` into records
+/// and calibration dates.
+///
+/// A definition is shared by every unit of its model, so of the records of
+/// installed units only those of the unit on this stream are kept. Records
+/// of units not installed on any stream (`installed` lists those that are)
+/// belong to no channel; every user of the definition keeps them so they
+/// survive a round-trip. Every own record's start is a calibration date;
+/// own records carrying nothing but a start are only that (the writer emits
+/// one for each [`Equipment::calibration_dates`] entry without a record).
+fn convert_calibrations(
+    calibrations: &[Sc3mlCalibration],
+    serial_number: Option<&str>,
+    installed: &[&str],
+    path: &str,
+    w: &mut Warnings,
+) -> Result<(Vec<Calibration>, Vec<DateTime<Utc>>)> {
+    let mut records = Vec::new();
+    let mut dates = Vec::new();
+    for c in calibrations {
+        let own = Some(c.serial_number.as_str()) == serial_number;
+        if !own && installed.contains(&c.serial_number.as_str()) {
+            continue;
+        }
+        // A bad date loses only that bound in lenient mode, not the channel
+        let path = format!("{path} calibration {}", c.serial_number);
+        let record = Calibration {
+            serial_number: c.serial_number.clone(),
+            channel: c.channel,
+            start: w.optional(&path, parse_datetime_opt(&c.start))?,
+            end: w.optional(&path, parse_datetime_opt(&c.end))?,
+            gain: c.gain,
+            gain_frequency: c.gain_frequency,
+            remark: c.remark.clone(),
+        };
+        if !own {
+            records.push(record);
+            continue;
+        }
+        if let Some(start) = record.start
+            && !dates.contains(&start)
+        {
+            dates.push(start);
+        }
+        if !record.is_date_only() {
+            records.push(record);
+        }
+    }
+    Ok((records, dates))
+}
+
+/// Calibrations in effect for one stream.
+struct StreamCalibrations<'a> {
+    sensor: Option<&'a Calibration>,
+    datalogger: Option<&'a Calibration>,
+}
+
+/// Pick the most recent calibration of `eq` for `channel` valid at `time`.
+fn select_calibration(
+    eq: Option<&Equipment>,
+    channel: Option<u32>,
+    time: Option<DateTime<Utc>>,
+) -> Option<&Calibration> {
+    let eq = eq?;
+    eq.calibrations
+        .iter()
+        .filter(|c| c.gain.is_some())
+        .filter(|c| eq.serial_number.as_ref() == Some(&c.serial_number))
+        // A record for one component never applies to a stream whose
+        // component is unknown
+        .filter(|c| c.channel.is_none() || c.channel == channel)
+        .filter(|c| time.is_none_or(|t| c.is_valid_at(t)))
+        .max_by_key(|c| c.start)
+}
+
 // ─── Response building ───────────────────────────────────────────────
 
 fn build_response(
     stream: &Sc3mlStream,
-    sensors: &HashMap<&str, &Sc3mlSensor>,
-    dataloggers: &HashMap<&str, &Sc3mlDatalogger>,
-    responses: &HashMap<&str, ResponseDef>,
+    path: &str,
+    lookup: &Lookup,
+    calibrations: &StreamCalibrations,
 ) -> Result<Option<Response>> {
     let mut stages: Vec<ResponseStage> = Vec::new();
     let mut stage_number: u32 = 1;
+    let stage_path = |number: u32| format!("{path} stage {number}");
 
     // Resolve sensor's response → responsePAZ (stage 1: sensor transfer function)
     let sensor_paz = stream
         .sensor
         .as_deref()
-        .and_then(|id| sensors.get(id))
+        .and_then(|id| lookup.sensors.get(id))
         .and_then(|s| s.response.as_deref())
-        .and_then(|resp_id| responses.get(resp_id));
+        .and_then(|resp_id| lookup.responses.get(resp_id));
 
     // Resolve sensor unit for input_units
     let sensor_unit = stream
         .sensor
         .as_deref()
-        .and_then(|id| sensors.get(id))
+        .and_then(|id| lookup.sensors.get(id))
         .and_then(|s| s.unit.as_deref())
         .unwrap_or("M/S");
 
     if let Some(ResponseDef::Paz(paz)) = sensor_paz {
-        let pz_stage = convert_paz_to_stage(paz, stage_number, sensor_unit, "V")?;
+        let mut pz_stage = convert_paz_to_stage(paz, stage_number, sensor_unit, "V")
+            .map_err(|e| e.at_path(&stage_path(stage_number)))?;
+        if let Some(cal) = calibrations.sensor {
+            apply_calibration(&mut pz_stage, cal);
+        }
         stages.push(pz_stage);
         stage_number += 1;
     }
@@ -257,7 +845,7 @@ fn build_response(
     let dl = stream
         .datalogger
         .as_deref()
-        .and_then(|id| dataloggers.get(id));
+        .and_then(|id| lookup.dataloggers.get(id));
 
     if let Some(dl) = dl {
         // Find matching decimation for this stream's sample rate
@@ -266,32 +854,42 @@ fn build_response(
                 && d.sample_rate_denominator == stream.sample_rate_denominator
         });
 
+        let output_rate =
+            fraction_rate(stream.sample_rate_numerator, stream.sample_rate_denominator);
+
+        // A calibration of this unit replaces the nominal datalogger gain,
+        // which is kept for writing the shared definition back
+        let cal = calibrations.datalogger;
+        let dl_gain = cal.and_then(|c| c.gain).or(dl.gain);
+        let dl_gain_frequency = cal.and_then(|c| c.gain_frequency).unwrap_or(0.0);
+        let nominal_gain = cal
+            .and_then(|c| c.gain)
+            .and(dl.gain)
+            .map(|value| StageGain {
+                value,
+                frequency: 0.0,
+            });
+
         if let Some(decim) = decim {
+            let chains = resolve_decimation(decim, lookup);
+
             // Analogue filter chain → PAZ stages
-            if let Some(chain) = &decim.analogue_filter_chain {
-                for ref_id in chain.split_whitespace() {
-                    if let Some(ResponseDef::Paz(paz)) = responses.get(ref_id) {
-                        let pz_stage = convert_paz_to_stage(paz, stage_number, "V", "V")?;
-                        stages.push(pz_stage);
-                        stage_number += 1;
-                    }
-                }
+            for paz in chains.analogue {
+                let pz_stage = convert_paz_to_stage(paz, stage_number, "V", "V")
+                    .map_err(|e| e.at_path(&stage_path(stage_number)))?;
+                stages.push(pz_stage);
+                stage_number += 1;
             }
 
-            // Datalogger gain stage (V → COUNTS)
-            if let Some(dl_gain) = dl.gain {
-                let sample_rate = if stream.sample_rate_denominator > 0 {
-                    stream.sample_rate_numerator as f64 / stream.sample_rate_denominator as f64
-                } else {
-                    0.0
-                };
-
+            // Datalogger gain stage (V → COUNTS), sampling at the ADC rate
+            if let Some(dl_gain) = dl_gain {
                 stages.push(ResponseStage {
                     number: stage_number,
                     stage_gain: Some(StageGain {
                         value: dl_gain,
-                        frequency: 0.0,
+                        frequency: dl_gain_frequency,
                     }),
+                    nominal_gain,
                     poles_zeros: None,
                     coefficients: Some(Coefficients {
                         input_units: Units {
@@ -308,7 +906,7 @@ fn build_response(
                     }),
                     fir: None,
                     decimation: Some(Decimation {
-                        input_sample_rate: sample_rate,
+                        input_sample_rate: chains.adc_rate,
                         factor: 1,
                         offset: 0,
                         delay: 0.0,
@@ -319,23 +917,21 @@ fn build_response(
             }
 
             // Digital filter chain → FIR stages
-            if let Some(chain) = &decim.digital_filter_chain {
-                for ref_id in chain.split_whitespace() {
-                    if let Some(ResponseDef::Fir(fir)) = responses.get(ref_id) {
-                        let fir_stage = convert_fir_to_stage(fir, stage_number)?;
-                        stages.push(fir_stage);
-                        stage_number += 1;
-                    }
-                }
+            for (fir, input_rate) in chains.firs {
+                let fir_stage = convert_fir_to_stage(fir, stage_number, input_rate)
+                    .map_err(|e| e.at_path(&stage_path(stage_number)))?;
+                stages.push(fir_stage);
+                stage_number += 1;
             }
-        } else if let Some(dl_gain) = dl.gain {
+        } else if let Some(dl_gain) = dl_gain {
             // No matching decimation but datalogger has gain
             stages.push(ResponseStage {
                 number: stage_number,
                 stage_gain: Some(StageGain {
                     value: dl_gain,
-                    frequency: 0.0,
+                    frequency: dl_gain_frequency,
                 }),
+                nominal_gain,
                 poles_zeros: None,
                 coefficients: Some(Coefficients {
                     input_units: Units {
@@ -351,7 +947,14 @@ fn build_response(
                     denominators: vec![],
                 }),
                 fir: None,
-                decimation: None,
+                // Without a decimation entry, assume the stream rate is the ADC rate
+                decimation: Some(Decimation {
+                    input_sample_rate: output_rate,
+                    factor: 1,
+                    offset: 0,
+                    delay: 0.0,
+                    correction: 0.0,
+                }),
             });
             stage_number += 1;
         }
@@ -388,6 +991,18 @@ fn build_response(
     }))
 }
 
+/// Replace a stage's nominal gain with a calibrated one, keeping the
+/// nominal gain in [`ResponseStage::nominal_gain`].
+fn apply_calibration(stage: &mut ResponseStage, cal: &Calibration) {
+    if let Some(value) = cal.gain {
+        let frequency = cal
+            .gain_frequency
+            .or(stage.stage_gain.as_ref().map(|g| g.frequency))
+            .unwrap_or(0.0);
+        stage.nominal_gain = stage.stage_gain.replace(StageGain { value, frequency });
+    }
+}
+
 // ─── PAZ → ResponseStage ────────────────────────────────────────────
 
 fn convert_paz_to_stage(
@@ -429,6 +1044,7 @@ fn convert_paz_to_stage(
             value: g,
             frequency: paz.gain_frequency.unwrap_or(1.0),
         }),
+        nominal_gain: None,
         poles_zeros: Some(PolesZeros {
             input_units: Units {
                 name: input_unit.into(),
@@ -443,6 +1059,7 @@ fn convert_paz_to_stage(
             normalization_frequency: paz.normalization_frequency.unwrap_or(1.0),
             zeros,
             poles,
+            resource_id: public_id(&paz.public_id),
         }),
         coefficients: None,
         fir: None,
@@ -452,7 +1069,11 @@ fn convert_paz_to_stage(
 
 // ─── FIR → ResponseStage ────────────────────────────────────────────
 
-fn convert_fir_to_stage(fir: &Sc3mlResponseFir, number: u32) -> Result<ResponseStage> {
+fn convert_fir_to_stage(
+    fir: &Sc3mlResponseFir,
+    number: u32,
+    input_sample_rate: f64,
+) -> Result<ResponseStage> {
     let symmetry = match fir.symmetry.as_deref().unwrap_or("A") {
         "A" => Symmetry::None,
         "B" => Symmetry::Odd,
@@ -472,7 +1093,14 @@ fn convert_fir_to_stage(fir: &Sc3mlResponseFir, number: u32) -> Result<ResponseS
         .unwrap_or_default();
 
     let decimation_factor = fir.decimation_factor.unwrap_or(1);
-    let input_sample_rate = 0.0; // Not available in SC3ML FIR definition
+    // SC3ML gives delay and correction in input samples; FDSN in seconds
+    let to_seconds = |samples: f64| {
+        if input_sample_rate > 0.0 {
+            samples / input_sample_rate
+        } else {
+            0.0
+        }
+    };
 
     Ok(ResponseStage {
         number,
@@ -480,6 +1108,7 @@ fn convert_fir_to_stage(fir: &Sc3mlResponseFir, number: u32) -> Result<ResponseS
             value: g,
             frequency: fir.gain_frequency.unwrap_or(0.0),
         }),
+        nominal_gain: None,
         poles_zeros: None,
         coefficients: None,
         fir: Some(FIR {
@@ -493,13 +1122,14 @@ fn convert_fir_to_stage(fir: &Sc3mlResponseFir, number: u32) -> Result<ResponseS
             },
             symmetry,
             numerator_coefficients: coefficients,
+            resource_id: public_id(&fir.public_id),
         }),
         decimation: Some(Decimation {
             input_sample_rate,
             factor: decimation_factor,
             offset: 0,
-            delay: fir.delay.unwrap_or(0.0),
-            correction: fir.correction.unwrap_or(0.0),
+            delay: to_seconds(fir.delay.unwrap_or(0.0)),
+            correction: to_seconds(fir.correction.unwrap_or(0.0)),
         }),
     })
 }
@@ -556,6 +1186,8 @@ fn parse_complex_array(s: &str) -> Result<Vec<PoleZero>> {
             number,
             real,
             imaginary,
+            real_uncertainty: None,
+            imaginary_uncertainty: None,
         });
         number += 1;
     }
@@ -815,6 +1447,364 @@ mod tests {
         assert!((fir.numerator_coefficients[0] - 0.1).abs() < 1e-6);
         let dec = s3.decimation.as_ref().unwrap();
         assert_eq!(dec.factor, 5);
+        assert_eq!(dec.input_sample_rate, 200.0);
+
+        // The gain stage samples at the ADC rate
+        let dec = s2.decimation.as_ref().unwrap();
+        assert_eq!((dec.input_sample_rate, dec.factor), (200.0, 1));
+    }
+
+    #[test]
+    fn read_calibrations_override_gains() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<seiscomp xmlns="http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/0.13" version="0.13">
+  <Inventory>
+    <sensor publicID="Sensor#1" response="PAZ#1">
+      <model>STS-2</model>
+      <calibration serialNumber="1234" channel="0">
+        <start>2020-01-01T00:00:00Z</start>
+        <end>2024-01-01T00:00:00Z</end>
+        <gain>1400</gain>
+      </calibration>
+      <calibration serialNumber="1234" channel="0">
+        <start>2024-01-01T00:00:00Z</start>
+        <gain>1510</gain>
+        <gainFrequency>5</gainFrequency>
+      </calibration>
+      <calibration serialNumber="9999">
+        <gain>1</gain>
+      </calibration>
+    </sensor>
+    <datalogger publicID="DL#1">
+      <gain>400000</gain>
+      <calibration serialNumber="PB001">
+        <start>2023-01-01T00:00:00Z</start>
+        <gain>401234</gain>
+      </calibration>
+    </datalogger>
+    <responsePAZ publicID="PAZ#1">
+      <type>A</type>
+      <gain>1500</gain>
+      <gainFrequency>1</gainFrequency>
+      <normalizationFactor>1</normalizationFactor>
+      <normalizationFrequency>1</normalizationFrequency>
+      <numberOfZeros>0</numberOfZeros>
+      <numberOfPoles>0</numberOfPoles>
+    </responsePAZ>
+    <network publicID="Net/XX" code="XX">
+      <station publicID="Sta/XX/A" code="A">
+        <latitude>0</latitude>
+        <longitude>0</longitude>
+        <elevation>0</elevation>
+        <sensorLocation publicID="Loc/XX/A/" code="">
+          <stream code="HHZ" datalogger="DL#1" sensor="Sensor#1">
+            <start>2024-06-01T00:00:00Z</start>
+            <dataloggerSerialNumber>PB001</dataloggerSerialNumber>
+            <sensorSerialNumber>1234</sensorSerialNumber>
+            <sampleRateNumerator>100</sampleRateNumerator>
+            <sampleRateDenominator>1</sampleRateDenominator>
+            <sensorChannel>0</sensorChannel>
+          </stream>
+        </sensorLocation>
+      </station>
+    </network>
+  </Inventory>
+</seiscomp>"#;
+        let inv = read_from_str(xml).unwrap();
+        let ch = &inv.networks[0].stations[0].channels[0];
+
+        // The installed unit's records, plus those of the uninstalled 9999
+        let sensor = ch.sensor.as_ref().unwrap();
+        let serials: Vec<&str> = sensor
+            .calibrations
+            .iter()
+            .map(|c| c.serial_number.as_str())
+            .collect();
+        assert_eq!(serials, ["1234", "1234", "9999"]);
+        assert_eq!(ch.data_logger.as_ref().unwrap().calibrations.len(), 1);
+        assert_eq!(sensor.calibration_dates.len(), 2);
+
+        // Calibrated gains, with the nominal ones kept aside
+        let stages = &ch.response.as_ref().unwrap().stages;
+        let sensor_gain = stages[0].stage_gain.as_ref().unwrap();
+        assert_eq!((sensor_gain.value, sensor_gain.frequency), (1510.0, 5.0));
+        let nominal = stages[0].nominal_gain.as_ref().unwrap();
+        assert_eq!((nominal.value, nominal.frequency), (1500.0, 1.0));
+        assert_eq!(stages[1].stage_gain.as_ref().unwrap().value, 401234.0);
+        assert_eq!(stages[1].nominal_gain.as_ref().unwrap().value, 400000.0);
+
+        // Written back into the shared definitions with their nominal gains
+        let out = crate::sc3ml::writer::write_to_string(&inv).unwrap();
+        assert_eq!(out.matches("<calibration ").count(), 4, "{out}");
+        assert!(
+            out.contains("<gain>1500</gain><gainFrequency>1</gainFrequency>"),
+            "{out}"
+        );
+        assert!(out.contains("<gain>400000</gain>"), "{out}");
+        assert!(!out.contains("<gain>1510</gain><gainFrequency>5</gainFrequency><normalization"));
+        let inv2 = read_from_str(&out).unwrap();
+        let ch2 = &inv2.networks[0].stations[0].channels[0];
+        assert_eq!(
+            ch2.sensor.as_ref().unwrap().calibrations,
+            sensor.calibrations
+        );
+        assert_eq!(ch2.response, ch.response);
+
+        // Schema versions without calibration records drop them
+        let opts =
+            crate::sc3ml::Sc3mlWriteOptions::default().version(crate::sc3ml::Sc3mlVersion::V0_9);
+        let old = crate::sc3ml::writer::write_to_string_with(&inv, &opts).unwrap();
+        assert!(!old.contains("<calibration "), "{old}");
+    }
+
+    #[test]
+    fn component_calibrations_survive_a_write() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<seiscomp xmlns="http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/0.13" version="0.13">
+  <Inventory>
+    <sensor publicID="Sensor#1" response="PAZ#1">
+      <model>STS-2</model>
+      <calibration serialNumber="1234" channel="0">
+        <gain>10</gain>
+      </calibration>
+      <calibration serialNumber="1234" channel="1">
+        <gain>99</gain>
+      </calibration>
+    </sensor>
+    <responsePAZ publicID="PAZ#1">
+      <type>A</type>
+      <gain>1500</gain>
+      <gainFrequency>1</gainFrequency>
+      <normalizationFactor>1</normalizationFactor>
+      <normalizationFrequency>1</normalizationFrequency>
+      <numberOfZeros>0</numberOfZeros>
+      <numberOfPoles>0</numberOfPoles>
+    </responsePAZ>
+    <network publicID="Net/XX" code="XX">
+      <station publicID="Sta/XX/A" code="A">
+        <latitude>0</latitude>
+        <longitude>0</longitude>
+        <elevation>0</elevation>
+        <sensorLocation publicID="Loc/XX/A/" code="">
+          <stream code="SHZ" sensor="Sensor#1">
+            <start>2024-06-01T00:00:00Z</start>
+            <sensorSerialNumber>1234</sensorSerialNumber>
+            <sampleRateNumerator>100</sampleRateNumerator>
+            <sampleRateDenominator>1</sampleRateDenominator>
+            <sensorChannel>0</sensorChannel>
+          </stream>
+          <stream code="SHX" sensor="Sensor#1">
+            <start>2024-06-01T00:00:00Z</start>
+            <sensorSerialNumber>1234</sensorSerialNumber>
+            <sampleRateNumerator>100</sampleRateNumerator>
+            <sampleRateDenominator>1</sampleRateDenominator>
+          </stream>
+        </sensorLocation>
+      </station>
+    </network>
+  </Inventory>
+</seiscomp>"#;
+        let sensor_gain = |inv: &Inventory, i: usize| {
+            let ch = &inv.networks[0].stations[0].channels[i];
+            ch.response.as_ref().unwrap().stages[0]
+                .stage_gain
+                .as_ref()
+                .unwrap()
+                .value
+        };
+        let inv = read_from_str(xml).unwrap();
+        let channels = &inv.networks[0].stations[0].channels;
+        assert_eq!(channels[0].sensor.as_ref().unwrap().channel, Some(0));
+        assert_eq!(sensor_gain(&inv, 0), 10.0);
+        // A stream of unknown component gets no component's calibration
+        assert_eq!(channels[1].sensor.as_ref().unwrap().channel, None);
+        assert_eq!(sensor_gain(&inv, 1), 1500.0);
+
+        let out = crate::sc3ml::writer::write_to_string(&inv).unwrap();
+        assert_eq!(
+            out.matches("<sensorChannel>0</sensorChannel>").count(),
+            1,
+            "{out}"
+        );
+        let inv2 = read_from_str(&out).unwrap();
+        assert_eq!(sensor_gain(&inv2, 0), 10.0);
+        assert_eq!(sensor_gain(&inv2, 1), 1500.0);
+    }
+
+    #[test]
+    fn read_aux_streams() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<seiscomp xmlns="http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/0.13" version="0.13">
+  <Inventory>
+    <auxDevice publicID="AuxDevice#Baro" name="Setra 278">
+      <description>Barometer</description>
+      <model>Setra 278</model>
+      <manufacturer>Setra</manufacturer>
+      <source name="pressure">
+        <unit>hPa</unit>
+        <conversion>600 + counts * 0.0001</conversion>
+        <sampleRateNumerator>1</sampleRateNumerator>
+        <sampleRateDenominator>1</sampleRateDenominator>
+      </source>
+    </auxDevice>
+    <network publicID="Net/XX" code="XX">
+      <station publicID="Sta/XX/A" code="A">
+        <latitude>1</latitude>
+        <longitude>2</longitude>
+        <elevation>3</elevation>
+        <sensorLocation publicID="Loc/XX/A/" code="">
+          <auxStream code="LDO" device="AuxDevice#Baro">
+            <start>2024-06-01T00:00:00Z</start>
+            <deviceSerialNumber>B-17</deviceSerialNumber>
+            <source>pressure</source>
+            <flags>H</flags>
+          </auxStream>
+        </sensorLocation>
+      </station>
+    </network>
+  </Inventory>
+</seiscomp>"#;
+        let inv = read_from_str(xml).unwrap();
+        let ch = &inv.networks[0].stations[0].channels[0];
+        assert_eq!(ch.code, "LDO");
+        assert_eq!(ch.sample_rate, 1.0);
+        assert_eq!(ch.latitude, 1.0);
+        assert_eq!(ch.channel_types, vec![ChannelType::Health]);
+        assert!(ch.sensor.is_none() && ch.response.is_none());
+        let device = ch.aux_device.as_ref().unwrap();
+        assert_eq!(device.model.as_deref(), Some("Setra 278"));
+        assert_eq!(device.serial_number.as_deref(), Some("B-17"));
+        let source = ch.aux_source.as_ref().unwrap();
+        assert_eq!(source.name, "pressure");
+        assert_eq!(source.unit.as_deref(), Some("hPa"));
+
+        let out = crate::sc3ml::writer::write_to_string(&inv).unwrap();
+        assert!(
+            out.contains(r#"<auxDevice publicID="AuxDevice#Baro""#),
+            "{out}"
+        );
+        assert!(out.contains(r#"<auxStream code="LDO" device="AuxDevice#Baro">"#));
+        let inv2 = read_from_str(&out).unwrap();
+        assert_eq!(inv2.networks[0].stations[0].channels[0], *ch);
+    }
+
+    #[test]
+    fn aux_streams_keep_document_order_and_location_depth() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<seiscomp xmlns="http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/0.13" version="0.13">
+  <Inventory>
+    <auxDevice publicID="AuxDevice#Baro" name="Setra 278">
+      <model>Setra 278</model>
+      <remark>Mounted in the vault</remark>
+      <source name="pressure"/>
+    </auxDevice>
+    <network publicID="Net/XX" code="XX">
+      <station publicID="Sta/XX/A" code="A">
+        <latitude>1</latitude>
+        <longitude>2</longitude>
+        <elevation>3</elevation>
+        <sensorLocation publicID="Loc/XX/A/00" code="00">
+          <comment><text>vault</text></comment>
+          <stream code="HHZ">
+            <start>2020-01-01T00:00:00Z</start>
+            <end>2024-01-01T00:00:00Z</end>
+            <depth>5</depth>
+          </stream>
+          <auxStream code="LDO" device="AuxDevice#Baro">
+            <start>2024-06-01T00:00:00Z</start>
+            <source>pressure</source>
+          </auxStream>
+          <stream code="HHZ">
+            <start>2024-01-01T00:00:00Z</start>
+            <depth>12.5</depth>
+          </stream>
+        </sensorLocation>
+      </station>
+    </network>
+  </Inventory>
+</seiscomp>"#;
+        let inv = read_from_str(xml).unwrap();
+        let channels = &inv.networks[0].stations[0].channels;
+        let codes: Vec<&str> = channels.iter().map(|ch| ch.code.as_str()).collect();
+        assert_eq!(codes, ["HHZ", "LDO", "HHZ"]);
+        // The aux stream sits at the depth of the stream running when it starts
+        assert_eq!(channels[1].depth, 12.5);
+        let device = channels[1].aux_device.as_ref().unwrap();
+        assert_eq!(device.remark.as_deref(), Some("Mounted in the vault"));
+
+        let out = crate::sc3ml::writer::write_to_string(&inv).unwrap();
+        assert!(
+            out.contains("<remark>Mounted in the vault</remark>"),
+            "{out}"
+        );
+        let aux = out.find("<auxStream ").unwrap();
+        let streams: Vec<usize> = out.match_indices("<stream ").map(|(i, _)| i).collect();
+        assert!(streams[0] < aux && aux < streams[1], "{out}");
+        let inv2 = read_from_str(&out).unwrap();
+        assert_eq!(inv2.networks[0].stations[0].channels, *channels);
+    }
+
+    #[test]
+    fn read_fir_chain_input_rates() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<seiscomp xmlns="http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/0.13" version="0.13">
+  <Inventory>
+    <datalogger publicID="DL">
+      <gain>400000</gain>
+      <decimation sampleRateNumerator="40" sampleRateDenominator="1">
+        <digitalFilterChain>FIR#A FIR#B</digitalFilterChain>
+      </decimation>
+    </datalogger>
+    <responseFIR publicID="FIR#A">
+      <gain>1</gain>
+      <decimationFactor>2</decimationFactor>
+      <delay>10</delay>
+      <correction>10</correction>
+      <numberOfCoefficients>1</numberOfCoefficients>
+      <symmetry>A</symmetry>
+      <coefficients>1</coefficients>
+    </responseFIR>
+    <responseFIR publicID="FIR#B">
+      <gain>1</gain>
+      <decimationFactor>5</decimationFactor>
+      <delay>20</delay>
+      <correction>20</correction>
+      <numberOfCoefficients>1</numberOfCoefficients>
+      <symmetry>A</symmetry>
+      <coefficients>1</coefficients>
+    </responseFIR>
+    <network publicID="Net/XX" code="XX">
+      <station publicID="Sta/XX/A" code="A">
+        <latitude>0</latitude>
+        <longitude>0</longitude>
+        <elevation>0</elevation>
+        <sensorLocation publicID="Loc/XX/A/" code="">
+          <stream code="HHZ" datalogger="DL">
+            <sampleRateNumerator>40</sampleRateNumerator>
+            <sampleRateDenominator>1</sampleRateDenominator>
+          </stream>
+        </sensorLocation>
+      </station>
+    </network>
+  </Inventory>
+</seiscomp>"#;
+        let inv = read_from_str(xml).unwrap();
+        let stages = &inv.networks[0].stations[0].channels[0]
+            .response
+            .as_ref()
+            .unwrap()
+            .stages;
+        let decs: Vec<_> = stages
+            .iter()
+            .map(|s| s.decimation.as_ref().unwrap())
+            .collect();
+        // ADC at 400 Hz → FIR#A (÷2) → 200 Hz → FIR#B (÷5) → 40 Hz
+        assert_eq!(decs[0].input_sample_rate, 400.0);
+        assert_eq!(decs[1].input_sample_rate, 400.0);
+        assert_eq!(decs[2].input_sample_rate, 200.0);
+        // Delays are converted from input samples to seconds
+        assert_eq!(decs[1].delay, 10.0 / 400.0);
+        assert_eq!(decs[2].correction, 20.0 / 200.0);
     }
 
     #[test]
@@ -865,6 +1855,34 @@ mod tests {
         assert!((sta.channels[1].sample_rate - 100.0).abs() < 1e-6);
     }
 
+    #[test]
+    fn read_clock_drift_per_sample() {
+        let xml = r#"<?xml version="1.0"?>
+<seiscomp version="0.13">
+  <Inventory>
+    <datalogger publicID="DL#1">
+      <maxClockDrift>0.01</maxClockDrift>
+    </datalogger>
+    <network publicID="Net/XX" code="XX">
+      <station publicID="Sta/T" code="T">
+        <latitude>0</latitude>
+        <longitude>0</longitude>
+        <elevation>0</elevation>
+        <sensorLocation publicID="Loc#1" code="">
+          <stream code="HHZ" datalogger="DL#1">
+            <sampleRateNumerator>100</sampleRateNumerator>
+            <sampleRateDenominator>1</sampleRateDenominator>
+          </stream>
+        </sensorLocation>
+      </station>
+    </network>
+  </Inventory>
+</seiscomp>"#;
+        let inv = read_from_str(xml).unwrap();
+        let ch = &inv.networks[0].stations[0].channels[0];
+        assert!((ch.clock_drift.unwrap() - 0.0001).abs() < 1e-12);
+    }
+
     #[test]
     fn read_from_bytes_works() {
         let xml = r#"<?xml version="1.0"?>
@@ -964,4 +1982,14 @@ mod tests {
         let s3 = &resp.stages[2];
         assert!((s3.stage_gain.as_ref().unwrap().value - 6553.6).abs() < 0.1);
     }
+
+    #[test]
+    fn parse_stream_flags_works() {
+        assert_eq!(
+            parse_stream_flags("GC").unwrap(),
+            vec![ChannelType::Geophysical, ChannelType::Continuous]
+        );
+        assert!(parse_stream_flags("").unwrap().is_empty());
+        assert!(parse_stream_flags("GX").is_err());
+    }
 }
@@ -5,7 +5,7 @@
 //! They are NOT part of the public API.
 //!
 //! SC3ML uses lowercase element names (unlike FDSN's PascalCase).
-//! Top-level definitions (sensor, datalogger, responsePAZ, responseFIR)
+//! Top-level definitions (auxDevice, sensor, datalogger, responsePAZ, responseFIR)
 //! are referenced by `publicID` from stream elements.
 
 use serde::{Deserialize, Serialize};
@@ -27,6 +27,8 @@ pub(crate) struct Sc3mlRoot {
 /// `<Inventory>` wrapper — contains all definitions and the network hierarchy.
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct Sc3mlInventory {
+    #[serde(rename = "auxDevice", default)]
+    pub aux_devices: Vec<Sc3mlAuxDevice>,
     #[serde(rename = "sensor", default)]
     pub sensors: Vec<Sc3mlSensor>,
     #[serde(rename = "datalogger", default)]
@@ -70,6 +72,73 @@ pub(crate) struct Sc3mlSensor {
     pub unit: Option<String>,
     #[serde(rename = "remark", default, skip_serializing_if = "Option::is_none")]
     pub remark: Option<String>,
+    #[serde(rename = "calibration", default)]
+    pub calibrations: Vec<Sc3mlCalibration>,
+}
+
+/// `<auxDevice publicID="..." name="...">`
+///
+/// State-of-health or environmental device (e.g. a barometer or a
+/// digitizer's internal monitors), referenced from `auxStream` elements.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Sc3mlAuxDevice {
+    #[serde(rename = "@publicID")]
+    pub public_id: String,
+    #[serde(rename = "@name", default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(
+        rename = "description",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(rename = "model", default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(
+        rename = "manufacturer",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub manufacturer: Option<String>,
+    #[serde(rename = "remark", default, skip_serializing_if = "Option::is_none")]
+    pub remark: Option<String>,
+    #[serde(rename = "source", default)]
+    pub sources: Vec<Sc3mlAuxSource>,
+}
+
+/// `<source name="...">` — one output of an auxiliary device.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Sc3mlAuxSource {
+    #[serde(rename = "@name")]
+    pub name: String,
+    #[serde(
+        rename = "description",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(rename = "unit", default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(
+        rename = "conversion",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub conversion: Option<String>,
+    #[serde(
+        rename = "sampleRateNumerator",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sample_rate_numerator: Option<u32>,
+    #[serde(
+        rename = "sampleRateDenominator",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sample_rate_denominator: Option<u32>,
+    #[serde(rename = "remark", default, skip_serializing_if = "Option::is_none")]
+    pub remark: Option<String>,
 }
 
 /// `<datalogger publicID="..." name="...">`
@@ -93,14 +162,40 @@ pub(crate) struct Sc3mlDatalogger {
         skip_serializing_if = "Option::is_none"
     )]
     pub max_clock_drift: Option<f64>,
+    #[serde(rename = "calibration", default)]
+    pub calibrations: Vec<Sc3mlCalibration>,
     #[serde(rename = "decimation", default)]
     pub decimations: Vec<Sc3mlDecimation>,
     #[serde(rename = "remark", default, skip_serializing_if = "Option::is_none")]
     pub remark: Option<String>,
 }
 
+/// `<calibration serialNumber="..." channel="0">` inside a sensor or datalogger
+/// (SeisComP `SensorCalibration` / `DataloggerCalibration`).
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Sc3mlCalibration {
+    #[serde(rename = "@serialNumber")]
+    pub serial_number: String,
+    #[serde(rename = "@channel", default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<u32>,
+    #[serde(rename = "start", default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+    #[serde(rename = "end", default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(rename = "gain", default, skip_serializing_if = "Option::is_none")]
+    pub gain: Option<f64>,
+    #[serde(
+        rename = "gainFrequency",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub gain_frequency: Option<f64>,
+    #[serde(rename = "remark", default, skip_serializing_if = "Option::is_none")]
+    pub remark: Option<String>,
+}
+
 /// `<decimation sampleRateNumerator="40" sampleRateDenominator="1">`
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct Sc3mlDecimation {
     #[serde(rename = "@sampleRateNumerator")]
     pub sample_rate_numerator: u32,
@@ -232,6 +327,8 @@ pub(crate) struct Sc3mlNetwork {
     pub public_id: String,
     #[serde(rename = "@code")]
     pub code: String,
+    #[serde(rename = "comment", default)]
+    pub comments: Vec<Sc3mlComment>,
     #[serde(rename = "start", default, skip_serializing_if = "Option::is_none")]
     pub start: Option<String>,
     #[serde(rename = "end", default, skip_serializing_if = "Option::is_none")]
@@ -242,6 +339,20 @@ pub(crate) struct Sc3mlNetwork {
         skip_serializing_if = "Option::is_none"
     )]
     pub description: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub network_type: Option<String>,
+    #[serde(rename = "netClass", default, skip_serializing_if = "Option::is_none")]
+    pub net_class: Option<String>,
+    #[serde(rename = "archive", default, skip_serializing_if = "Option::is_none")]
+    pub archive: Option<String>,
+    #[serde(
+        rename = "restricted",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub restricted: Option<bool>,
+    #[serde(rename = "shared", default, skip_serializing_if = "Option::is_none")]
+    pub shared: Option<bool>,
     #[serde(rename = "station", default)]
     pub stations: Vec<Sc3mlStation>,
 }
@@ -253,6 +364,8 @@ pub(crate) struct Sc3mlStation {
     pub public_id: String,
     #[serde(rename = "@code")]
     pub code: String,
+    #[serde(rename = "comment", default)]
+    pub comments: Vec<Sc3mlComment>,
     #[serde(rename = "start", default, skip_serializing_if = "Option::is_none")]
     pub start: Option<String>,
     #[serde(rename = "end", default, skip_serializing_if = "Option::is_none")]
@@ -273,6 +386,30 @@ pub(crate) struct Sc3mlStation {
     pub place: Option<String>,
     #[serde(rename = "country", default, skip_serializing_if = "Option::is_none")]
     pub country: Option<String>,
+    #[serde(
+        rename = "affiliation",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub affiliation: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub station_type: Option<String>,
+    #[serde(rename = "archive", default, skip_serializing_if = "Option::is_none")]
+    pub archive: Option<String>,
+    #[serde(
+        rename = "archiveNetworkCode",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub archive_network_code: Option<String>,
+    #[serde(
+        rename = "restricted",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub restricted: Option<bool>,
+    #[serde(rename = "shared", default, skip_serializing_if = "Option::is_none")]
+    pub shared: Option<bool>,
     #[serde(rename = "sensorLocation", default)]
     pub sensor_locations: Vec<Sc3mlSensorLocation>,
 }
@@ -286,6 +423,8 @@ pub(crate) struct Sc3mlSensorLocation {
     pub public_id: String,
     #[serde(rename = "@code")]
     pub code: String,
+    #[serde(rename = "comment", default)]
+    pub comments: Vec<Sc3mlComment>,
     #[serde(rename = "start", default, skip_serializing_if = "Option::is_none")]
     pub start: Option<String>,
     #[serde(rename = "end", default, skip_serializing_if = "Option::is_none")]
@@ -296,8 +435,39 @@ pub(crate) struct Sc3mlSensorLocation {
     pub longitude: Option<f64>,
     #[serde(rename = "elevation", default, skip_serializing_if = "Option::is_none")]
     pub elevation: Option<f64>,
-    #[serde(rename = "stream", default)]
-    pub streams: Vec<Sc3mlStream>,
+    /// `stream` and `auxStream` children, in document order
+    #[serde(rename = "$value", default)]
+    pub streams: Vec<Sc3mlLocationStream>,
+}
+
+impl Sc3mlSensorLocation {
+    /// The `stream` children, skipping auxiliary streams.
+    pub fn seismic_streams(&self) -> impl Iterator<Item = &Sc3mlStream> {
+        self.streams.iter().filter_map(|s| match s {
+            Sc3mlLocationStream::Stream(stream) => Some(stream),
+            _ => None,
+        })
+    }
+
+    /// Mutable access to the `stream` children.
+    pub fn seismic_streams_mut(&mut self) -> impl Iterator<Item = &mut Sc3mlStream> {
+        self.streams.iter_mut().filter_map(|s| match s {
+            Sc3mlLocationStream::Stream(stream) => Some(stream),
+            _ => None,
+        })
+    }
+}
+
+/// A child of `<sensorLocation>` after its own fields.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Sc3mlLocationStream {
+    #[serde(rename = "stream")]
+    Stream(Sc3mlStream),
+    #[serde(rename = "auxStream")]
+    Aux(Sc3mlAuxStream),
+    /// Any other element, skipped on read
+    #[serde(other)]
+    Other,
 }
 
 /// `<stream code="..." datalogger="..." sensor="...">`
@@ -316,6 +486,8 @@ pub(crate) struct Sc3mlStream {
     pub datalogger: Option<String>,
     #[serde(rename = "@sensor", default, skip_serializing_if = "Option::is_none")]
     pub sensor: Option<String>,
+    #[serde(rename = "comment", default)]
+    pub comments: Vec<Sc3mlComment>,
     #[serde(rename = "start", default, skip_serializing_if = "Option::is_none")]
     pub start: Option<String>,
     #[serde(rename = "end", default, skip_serializing_if = "Option::is_none")]
@@ -364,6 +536,80 @@ pub(crate) struct Sc3mlStream {
         skip_serializing_if = "Option::is_none"
     )]
     pub sensor_channel: Option<u32>,
+    #[serde(rename = "format", default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(rename = "flags", default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<String>,
+    #[serde(
+        rename = "restricted",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub restricted: Option<bool>,
+    #[serde(rename = "shared", default, skip_serializing_if = "Option::is_none")]
+    pub shared: Option<bool>,
+}
+
+/// `<auxStream code="..." device="...">`
+///
+/// A channel recorded by an auxiliary device; `device` references an
+/// `auxDevice` by its `publicID` and `source` names one of its outputs.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Sc3mlAuxStream {
+    #[serde(rename = "@code")]
+    pub code: String,
+    #[serde(rename = "@device", default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(rename = "start", default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+    #[serde(rename = "end", default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(
+        rename = "deviceSerialNumber",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub device_serial_number: Option<String>,
+    #[serde(rename = "source", default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(rename = "format", default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(rename = "flags", default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<String>,
+    #[serde(
+        rename = "restricted",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub restricted: Option<bool>,
+    #[serde(rename = "shared", default, skip_serializing_if = "Option::is_none")]
+    pub shared: Option<bool>,
+}
+
+/// `<comment>` on network, station or stream.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Sc3mlComment {
+    #[serde(rename = "text")]
+    pub text: String,
+    #[serde(rename = "id", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "start", default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+    #[serde(rename = "end", default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(
+        rename = "creationInfo",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub creation_info: Option<Sc3mlCreationInfo>,
+}
+
+/// `<creationInfo>` — only the author is modelled.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Sc3mlCreationInfo {
+    #[serde(rename = "author", default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
 }
 
 #[cfg(test)]
@@ -518,7 +764,7 @@ mod tests {
         let loc = &sta.sensor_locations[0];
         assert_eq!(loc.code, "00");
 
-        let stream = &loc.streams[0];
+        let stream = loc.seismic_streams().next().unwrap();
         assert_eq!(stream.code, "SHZ");
         assert_eq!(stream.datalogger.as_deref(), Some("DL#1"));
         assert_eq!(stream.sensor.as_deref(), Some("Sensor#1"));
@@ -4,18 +4,33 @@
 //! structure. Deduplicates sensors, dataloggers, and response definitions
 //! by generating stable `publicID` identifiers.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
 
-use crate::datetime::format_datetime_opt;
 use crate::error::Result;
 use crate::inventory::*;
+use crate::style::{self, TimeFormat};
 
+use super::public_id::{PublicIdKind, PublicIdRequest, PublicIdStrategy};
 use super::types::*;
+use super::{Sc3mlVersion, Sc3mlWriteOptions};
 
-/// Serialize an [`Inventory`] to an SC3ML XML string.
+/// Serialize an [`Inventory`] to an SC3ML XML string (latest schema version).
 pub(crate) fn write_to_string(inventory: &Inventory) -> Result<String> {
-    let sc3ml = inventory_to_sc3ml(inventory);
-    let body = quick_xml::se::to_string(&sc3ml)?;
+    write_to_string_with(inventory, &Sc3mlWriteOptions::default())
+}
+
+/// Serialize an [`Inventory`] to an SC3ML XML string.
+pub(crate) fn write_to_string_with(
+    inventory: &Inventory,
+    options: &Sc3mlWriteOptions,
+) -> Result<String> {
+    let mut sc3ml = inventory_to_sc3ml(inventory, options);
+    apply_version(&mut sc3ml, options.version);
+    let mut body = String::new();
+    style::serialize(&sc3ml, &options.style, &mut body)?;
+    let body = style::restyle(body, &options.style)?;
     let mut xml = String::with_capacity(body.len() + 50);
     xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
     xml.push('\n');
@@ -27,10 +42,13 @@ pub(crate) fn write_to_string(inventory: &Inventory) -> Result<String> {
 
 /// Collected top-level definitions during hierarchy traversal.
 struct Definitions {
+    aux_devices: Vec<Sc3mlAuxDevice>,
     sensors: Vec<Sc3mlSensor>,
     dataloggers: Vec<Sc3mlDatalogger>,
     response_paz: Vec<Sc3mlResponsePaz>,
     response_fir: Vec<Sc3mlResponseFir>,
+    /// Map: dedup key → publicID (for auxiliary devices)
+    aux_device_map: HashMap<String, String>,
     /// Map: dedup key → publicID (for sensors)
     sensor_map: HashMap<String, String>,
     /// Map: dedup key → publicID (for dataloggers)
@@ -41,32 +59,88 @@ struct Definitions {
     fir_map: HashMap<String, String>,
     /// Counter for unique IDs
     id_counter: u32,
+    /// How publicIDs are derived
+    strategy: PublicIdStrategy,
+    /// Every publicID handed out so far
+    used_ids: HashSet<String>,
+    /// Preserved publicIDs present in the inventory, never handed to generated IDs
+    reserved: HashSet<String>,
+    /// Formatting of datetimes
+    times: TimeFormat,
+    /// Sentinel written as the end of open epochs
+    open_end: Option<DateTime<Utc>>,
 }
 
 impl Definitions {
-    fn new() -> Self {
+    fn new(options: &Sc3mlWriteOptions, reserved: HashSet<String>) -> Self {
         Self {
+            aux_devices: Vec::new(),
             sensors: Vec::new(),
             dataloggers: Vec::new(),
             response_paz: Vec::new(),
             response_fir: Vec::new(),
+            aux_device_map: HashMap::new(),
             sensor_map: HashMap::new(),
             datalogger_map: HashMap::new(),
             paz_map: HashMap::new(),
             fir_map: HashMap::new(),
             id_counter: 1,
+            strategy: options.public_ids.clone(),
+            used_ids: HashSet::new(),
+            reserved,
+            times: options.style.times,
+            open_end: options.open_end,
+        }
+    }
+
+    /// Reuse `preferred` (a publicID preserved from a previous read) if it is
+    /// still free; otherwise generate one, appending `/2`, `/3`, … if the
+    /// generated ID is already taken.
+    fn public_id(&mut self, preferred: Option<&str>, req: &PublicIdRequest) -> String {
+        if let Some(id) = preferred.filter(|id| !self.used_ids.contains(*id)) {
+            self.used_ids.insert(id.to_string());
+            return id.to_string();
+        }
+        let id = self.strategy.generate(req, &mut self.id_counter);
+        let mut unique = id.clone();
+        let mut n = 1;
+        while self.used_ids.contains(&unique) || self.reserved.contains(&unique) {
+            n += 1;
+            unique = format!("{id}/{n}");
         }
+        self.used_ids.insert(unique.clone());
+        unique
     }
+}
+
+/// The channel an equipment definition is created or extended for.
+struct Origin<'a> {
+    net_code: &'a str,
+    sta_code: &'a str,
+    ch: &'a Channel,
+}
 
-    fn next_id(&mut self) -> u32 {
-        let id = self.id_counter;
-        self.id_counter += 1;
-        id
+/// ID request for a shared equipment or response definition, described by
+/// its content key alone so the channel that first uses it does not matter.
+fn definition_request<'a>(
+    kind: PublicIdKind,
+    model: Option<&'a str>,
+    content: &'a str,
+) -> PublicIdRequest<'a> {
+    PublicIdRequest {
+        kind,
+        network: None,
+        station: None,
+        location: None,
+        channel: None,
+        start: None,
+        model,
+        content,
     }
 }
 
-fn inventory_to_sc3ml(inv: &Inventory) -> Sc3mlRoot {
-    let mut defs = Definitions::new();
+fn inventory_to_sc3ml(inv: &Inventory, options: &Sc3mlWriteOptions) -> Sc3mlRoot {
+    let mut defs = Definitions::new(options, preserved_ids(inv));
 
     // First pass: collect all definitions from channels
     let networks: Vec<Sc3mlNetwork> = inv
@@ -76,9 +150,10 @@ fn inventory_to_sc3ml(inv: &Inventory) -> Sc3mlRoot {
         .collect();
 
     Sc3mlRoot {
-        xmlns: Some("http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/0.13".into()),
-        version: Some("0.13".into()),
+        xmlns: Some(Sc3mlVersion::default().namespace()),
+        version: Some(Sc3mlVersion::default().as_str().into()),
         inventory: Sc3mlInventory {
+            aux_devices: defs.aux_devices,
             sensors: defs.sensors,
             dataloggers: defs.dataloggers,
             response_paz: defs.response_paz,
@@ -88,61 +163,177 @@ fn inventory_to_sc3ml(inv: &Inventory) -> Sc3mlRoot {
     }
 }
 
+/// Stamp the target schema version and drop elements it does not have.
+fn apply_version(root: &mut Sc3mlRoot, version: Sc3mlVersion) {
+    root.xmlns = Some(version.namespace());
+    root.version = Some(version.as_str().into());
+
+    if version < Sc3mlVersion::V0_7 {
+        for fir in &mut root.inventory.response_fir {
+            fir.gain_frequency = None;
+        }
+    }
+    let drop_comments = version < Sc3mlVersion::V0_10;
+    if version < Sc3mlVersion::V0_10 {
+        for sensor in &mut root.inventory.sensors {
+            sensor.calibrations.clear();
+        }
+        for dl in &mut root.inventory.dataloggers {
+            dl.calibrations.clear();
+        }
+    }
+    for net in &mut root.inventory.networks {
+        if drop_comments {
+            net.comments.clear();
+        }
+        for sta in &mut net.stations {
+            if drop_comments {
+                sta.comments.clear();
+                for loc in &mut sta.sensor_locations {
+                    loc.comments.clear();
+                }
+            }
+            for stream in sta
+                .sensor_locations
+                .iter_mut()
+                .flat_map(|loc| loc.seismic_streams_mut())
+            {
+                if drop_comments {
+                    stream.comments.clear();
+                }
+                if version < Sc3mlVersion::V0_9 {
+                    stream.gain_unit = None;
+                }
+            }
+        }
+    }
+}
+
+/// Collect every publicID preserved in the inventory.
+fn preserved_ids(inv: &Inventory) -> HashSet<String> {
+    let mut ids = HashSet::new();
+    for net in &inv.networks {
+        ids.extend(net.public_id.clone());
+        for sta in &net.stations {
+            ids.extend(sta.public_id.clone());
+            for ch in &sta.channels {
+                ids.extend(ch.sensor_location_id.clone());
+                for eq in [&ch.sensor, &ch.data_logger, &ch.aux_device]
+                    .into_iter()
+                    .flatten()
+                {
+                    ids.extend(eq.resource_id.clone());
+                }
+                for stage in ch.response.iter().flat_map(|r| &r.stages) {
+                    ids.extend(
+                        stage
+                            .poles_zeros
+                            .as_ref()
+                            .and_then(|pz| pz.resource_id.clone()),
+                    );
+                    ids.extend(stage.fir.as_ref().and_then(|fir| fir.resource_id.clone()));
+                }
+            }
+        }
+    }
+    ids
+}
+
 // ─── Hierarchy conversion ────────────────────────────────────────────
 
 fn convert_network(net: &Network, defs: &mut Definitions) -> Sc3mlNetwork {
-    let id = defs.next_id();
+    let public_id = defs.public_id(
+        net.public_id.as_deref(),
+        &PublicIdRequest {
+            kind: PublicIdKind::Network,
+            network: Some(&net.code),
+            station: None,
+            location: None,
+            channel: None,
+            start: net.start_date,
+            model: None,
+            content: &net.code,
+        },
+    );
     Sc3mlNetwork {
-        public_id: format!("Network/{}", net.code),
+        public_id,
         code: net.code.clone(),
-        start: format_datetime_opt(&net.start_date),
-        end: format_datetime_opt(&net.end_date),
+        comments: convert_comments(&net.comments, defs.times),
+        start: defs.times.format_opt(&net.start_date),
+        end: defs.times.format_opt(&net.end_date.or(defs.open_end)),
         description: net.description.clone(),
+        network_type: net.network_type.clone(),
+        net_class: net.network_class.clone(),
+        archive: net.archive.clone(),
+        restricted: net.restricted_status.map(is_restricted),
+        shared: net.shared,
         stations: net
             .stations
             .iter()
-            .map(|sta| convert_station(sta, &net.code, defs, id))
+            .map(|sta| convert_station(sta, &net.code, defs))
             .collect(),
     }
 }
 
-fn convert_station(
-    sta: &Station,
-    net_code: &str,
-    defs: &mut Definitions,
-    _net_id: u32,
-) -> Sc3mlStation {
-    // Group channels by location_code → sensorLocations
-    let mut loc_groups: Vec<(String, Vec<&Channel>)> = Vec::new();
+fn convert_station(sta: &Station, net_code: &str, defs: &mut Definitions) -> Sc3mlStation {
+    let public_id = defs.public_id(
+        sta.public_id.as_deref(),
+        &PublicIdRequest {
+            kind: PublicIdKind::Station,
+            network: Some(net_code),
+            station: Some(&sta.code),
+            location: None,
+            channel: None,
+            start: sta.start_date,
+            model: None,
+            content: &sta.code,
+        },
+    );
+
+    // Group channels by location_code (and preserved publicID) → sensorLocations
+    let mut loc_groups: Vec<(String, Option<&str>, Vec<&Channel>)> = Vec::new();
     for ch in &sta.channels {
+        let loc_id = ch.sensor_location_id.as_deref();
         if let Some(group) = loc_groups
             .iter_mut()
-            .find(|(code, _)| *code == ch.location_code)
+            .find(|(code, id, _)| *code == ch.location_code && *id == loc_id)
         {
-            group.1.push(ch);
+            group.2.push(ch);
         } else {
-            loc_groups.push((ch.location_code.clone(), vec![ch]));
+            loc_groups.push((ch.location_code.clone(), loc_id, vec![ch]));
         }
     }
 
     let sensor_locations: Vec<Sc3mlSensorLocation> = loc_groups
         .into_iter()
-        .map(|(loc_code, channels)| {
+        .map(|(loc_code, _, channels)| {
             convert_sensor_location(sta, net_code, &loc_code, &channels, defs)
         })
         .collect();
 
     Sc3mlStation {
-        public_id: format!("Station/{net_code}/{}", sta.code),
+        public_id,
         code: sta.code.clone(),
-        start: format_datetime_opt(&sta.start_date),
-        end: format_datetime_opt(&sta.end_date),
-        description: sta.description.clone().or(Some(sta.site.name.clone())),
+        comments: convert_comments(&sta.comments, defs.times),
+        start: defs.times.format_opt(&sta.start_date),
+        end: defs.times.format_opt(&sta.end_date.or(defs.open_end)),
+        // SeisComP uses the station description as the FDSN site name, so
+        // the site name only stands in for a missing description
+        description: sta
+            .description
+            .clone()
+            .or_else(|| (!sta.site.name.is_empty()).then(|| sta.site.name.clone())),
         latitude: sta.latitude,
         longitude: sta.longitude,
         elevation: sta.elevation,
-        place: sta.site.town.clone().or(sta.site.region.clone()),
+        place: sta.site.town.clone().or_else(|| sta.site.region.clone()),
         country: sta.site.country.clone(),
+        affiliation: sta.affiliation.clone(),
+        station_type: sta.station_type.clone(),
+        archive: sta.archive.clone(),
+        archive_network_code: sta.archive_network_code.clone(),
+        restricted: sta.restricted_status.map(is_restricted),
+        shared: sta.shared,
         sensor_locations,
     }
 }
@@ -160,16 +351,37 @@ fn convert_sensor_location(
     let longitude = first.map(|ch| ch.longitude);
     let elevation = first.map(|ch| ch.elevation);
 
-    let loc_id = defs.next_id();
-    let streams: Vec<Sc3mlStream> = channels
+    let preferred = first.and_then(|ch| ch.sensor_location_id.as_deref());
+    let public_id = defs.public_id(
+        preferred,
+        &PublicIdRequest {
+            kind: PublicIdKind::SensorLocation,
+            network: Some(net_code),
+            station: Some(&sta.code),
+            location: Some(loc_code),
+            channel: None,
+            start: first.and_then(|ch| ch.start_date),
+            model: None,
+            content: loc_code,
+        },
+    );
+    let streams = channels
         .iter()
-        .map(|ch| convert_stream(ch, sta, net_code, loc_code, defs))
+        .map(|ch| match &ch.aux_device {
+            Some(device) => {
+                Sc3mlLocationStream::Aux(convert_aux_stream(ch, device, sta, net_code, defs))
+            }
+            None => Sc3mlLocationStream::Stream(convert_stream(ch, sta, net_code, loc_code, defs)),
+        })
         .collect();
 
     Sc3mlSensorLocation {
-        public_id: format!("SensorLocation/{net_code}/{}/{loc_code}/{loc_id}", sta.code),
+        public_id,
         code: loc_code.into(),
-        start: format_datetime_opt(&first.and_then(|ch| ch.start_date)),
+        comments: first.map_or_else(Vec::new, |ch| {
+            convert_comments(&ch.sensor_location_comments, defs.times)
+        }),
+        start: defs.times.format_opt(&first.and_then(|ch| ch.start_date)),
         end: None,
         latitude,
         longitude,
@@ -178,6 +390,32 @@ fn convert_sensor_location(
     }
 }
 
+fn convert_aux_stream(
+    ch: &Channel,
+    device: &Equipment,
+    sta: &Station,
+    net_code: &str,
+    defs: &mut Definitions,
+) -> Sc3mlAuxStream {
+    let origin = Origin {
+        net_code,
+        sta_code: &sta.code,
+        ch,
+    };
+    Sc3mlAuxStream {
+        code: ch.code.clone(),
+        device: Some(get_or_create_aux_device(device, &origin, defs)),
+        start: defs.times.format_opt(&ch.start_date),
+        end: defs.times.format_opt(&ch.end_date.or(defs.open_end)),
+        device_serial_number: device.serial_number.clone(),
+        source: ch.aux_source.as_ref().map(|s| s.name.clone()),
+        format: ch.storage_format.clone(),
+        flags: format_stream_flags(&ch.channel_types),
+        restricted: ch.restricted_status.map(is_restricted),
+        shared: ch.shared,
+    }
+}
+
 fn convert_stream(
     ch: &Channel,
     sta: &Station,
@@ -185,17 +423,23 @@ fn convert_stream(
     _loc_code: &str,
     defs: &mut Definitions,
 ) -> Sc3mlStream {
+    let origin = Origin {
+        net_code,
+        sta_code: &sta.code,
+        ch,
+    };
+
     // Resolve or create sensor definition
     let sensor_public_id = ch
         .sensor
         .as_ref()
-        .map(|eq| get_or_create_sensor(eq, ch, sta, net_code, defs));
+        .map(|eq| get_or_create_sensor(eq, &origin, defs));
 
     // Resolve or create datalogger definition
     let datalogger_public_id = ch
         .data_logger
         .as_ref()
-        .map(|eq| get_or_create_datalogger(eq, ch, sta, net_code, defs));
+        .map(|eq| get_or_create_datalogger(eq, &origin, defs));
 
     // Compute sample rate as numerator/denominator
     let (num, denom) = float_to_fraction(ch.sample_rate);
@@ -218,8 +462,9 @@ fn convert_stream(
         code: ch.code.clone(),
         datalogger: datalogger_public_id,
         sensor: sensor_public_id,
-        start: format_datetime_opt(&ch.start_date),
-        end: format_datetime_opt(&ch.end_date),
+        comments: convert_comments(&ch.comments, defs.times),
+        start: defs.times.format_opt(&ch.start_date),
+        end: defs.times.format_opt(&ch.end_date.or(defs.open_end)),
         sample_rate_numerator: num,
         sample_rate_denominator: denom,
         depth: ch.depth,
@@ -233,13 +478,87 @@ fn convert_stream(
             .as_ref()
             .and_then(|eq| eq.serial_number.clone()),
         sensor_serial_number: ch.sensor.as_ref().and_then(|eq| eq.serial_number.clone()),
-        datalogger_channel: None,
-        sensor_channel: None,
+        datalogger_channel: ch.data_logger.as_ref().and_then(|eq| eq.channel),
+        sensor_channel: ch.sensor.as_ref().and_then(|eq| eq.channel),
+        format: ch.storage_format.clone(),
+        flags: format_stream_flags(&ch.channel_types),
+        restricted: ch.restricted_status.map(is_restricted),
+        shared: ch.shared,
+    }
+}
+
+fn convert_comments(comments: &[Comment], times: TimeFormat) -> Vec<Sc3mlComment> {
+    comments
+        .iter()
+        .map(|c| Sc3mlComment {
+            text: c.value.clone(),
+            id: c.id.clone(),
+            start: times.format_opt(&c.begin_effective_time),
+            end: times.format_opt(&c.end_effective_time),
+            creation_info: c.author.as_ref().map(|author| Sc3mlCreationInfo {
+                author: Some(author.clone()),
+            }),
+        })
+        .collect()
+}
+
+/// Calibration records of a unit: its [`Calibration`]s, plus a record with
+/// only a start for each calibration date none of them starts at. Dates of a
+/// unit without serial number cannot be attributed and are left out.
+fn calibration_records(eq: &Equipment, times: TimeFormat) -> Vec<Sc3mlCalibration> {
+    let mut records: Vec<Sc3mlCalibration> = eq
+        .calibrations
+        .iter()
+        .map(|c| Sc3mlCalibration {
+            serial_number: c.serial_number.clone(),
+            channel: c.channel,
+            start: times.format_opt(&c.start),
+            end: times.format_opt(&c.end),
+            gain: c.gain,
+            gain_frequency: c.gain_frequency,
+            remark: c.remark.clone(),
+        })
+        .collect();
+    if let Some(serial) = &eq.serial_number {
+        for date in &eq.calibration_dates {
+            let recorded = eq
+                .calibrations
+                .iter()
+                .any(|c| c.serial_number == *serial && c.start == Some(*date));
+            if !recorded {
+                records.push(Sc3mlCalibration {
+                    serial_number: serial.clone(),
+                    channel: None,
+                    start: Some(times.format(date)),
+                    end: None,
+                    gain: None,
+                    gain_frequency: None,
+                    remark: None,
+                });
+            }
+        }
+    }
+    records
+}
+
+/// Add the calibrations of another unit sharing a sensor/datalogger definition.
+fn merge_calibrations(target: &mut Vec<Sc3mlCalibration>, eq: &Equipment, times: TimeFormat) {
+    for cal in calibration_records(eq, times) {
+        if !target.contains(&cal) {
+            target.push(cal);
+        }
     }
 }
 
 // ─── Deduplication helpers ───────────────────────────────────────────
 
+/// Dedup key for a definition identified by a preserved publicID.
+///
+/// The `#` prefix keeps these apart from content-based keys.
+fn preserved_key(public_id: Option<&str>) -> Option<String> {
+    public_id.map(|id| format!("#{id}"))
+}
+
 /// Generate a dedup key for a sensor based on model + manufacturer.
 fn sensor_dedup_key(eq: &Equipment) -> String {
     format!(
@@ -258,29 +577,72 @@ fn datalogger_dedup_key(eq: &Equipment) -> String {
     )
 }
 
-fn get_or_create_sensor(
-    eq: &Equipment,
-    ch: &Channel,
-    _sta: &Station,
-    _net_code: &str,
-    defs: &mut Definitions,
-) -> String {
+fn get_or_create_aux_device(eq: &Equipment, origin: &Origin, defs: &mut Definitions) -> String {
+    let ch = origin.ch;
+    let key = sensor_dedup_key(eq);
+    let source = ch.aux_source.as_ref().map(|s| {
+        let (num, denom) = float_to_fraction(ch.sample_rate);
+        let has_rate = ch.sample_rate > 0.0;
+        Sc3mlAuxSource {
+            name: s.name.clone(),
+            description: s.description.clone(),
+            unit: s.unit.clone(),
+            conversion: s.conversion.clone(),
+            sample_rate_numerator: has_rate.then_some(num),
+            sample_rate_denominator: has_rate.then_some(denom),
+            remark: s.remark.clone(),
+        }
+    });
+
+    // Channels of one device share the definition, which lists every source
+    let lookup = preserved_key(eq.resource_id.as_deref()).unwrap_or_else(|| key.clone());
+    if let Some(public_id) = defs.aux_device_map.get(&lookup).cloned() {
+        if let Some(device) = defs
+            .aux_devices
+            .iter_mut()
+            .find(|d| d.public_id == public_id)
+            && let Some(source) = source
+            && !device.sources.iter().any(|s| s.name == source.name)
+        {
+            device.sources.push(source);
+        }
+        return public_id;
+    }
+
+    let public_id = defs.public_id(
+        eq.resource_id.as_deref(),
+        &definition_request(PublicIdKind::AuxDevice, eq.model.as_deref(), &key),
+    );
+    defs.aux_devices.push(Sc3mlAuxDevice {
+        public_id: public_id.clone(),
+        name: eq.model.clone(),
+        description: eq.description.clone(),
+        model: eq.model.clone(),
+        manufacturer: eq.manufacturer.clone(),
+        remark: eq.remark.clone(),
+        sources: source.into_iter().collect(),
+    });
+    defs.aux_device_map.insert(lookup, public_id.clone());
+    public_id
+}
+
+fn get_or_create_sensor(eq: &Equipment, origin: &Origin, defs: &mut Definitions) -> String {
+    let ch = origin.ch;
     let key = sensor_dedup_key(eq);
 
-    // Check for existing with matching response
-    if let Some(public_id) = defs.sensor_map.get(&key) {
-        return public_id.clone();
+    // A preserved publicID identifies the definition; otherwise match by model
+    let lookup = preserved_key(eq.resource_id.as_deref()).unwrap_or_else(|| key.clone());
+    if let Some(public_id) = defs.sensor_map.get(&lookup).cloned() {
+        if let Some(sensor) = defs.sensors.iter_mut().find(|s| s.public_id == public_id) {
+            merge_calibrations(&mut sensor.calibrations, eq, defs.times);
+        }
+        return public_id;
     }
 
-    let id = defs.next_id();
-    let public_id = format!(
-        "Sensor/{}",
-        eq.model
-            .as_deref()
-            .unwrap_or("unknown")
-            .replace([' ', '/'], "_")
+    let public_id = defs.public_id(
+        eq.resource_id.as_deref(),
+        &definition_request(PublicIdKind::Sensor, eq.model.as_deref(), &key),
     );
-    let public_id = format!("{public_id}_{id}");
 
     // Extract response PAZ from channel response stages (stage 1 is typically sensor)
     let response_paz_id = ch.response.as_ref().and_then(|resp| {
@@ -321,29 +683,17 @@ fn get_or_create_sensor(
         manufacturer: eq.manufacturer.clone(),
         sensor_type: eq.equipment_type.clone(),
         unit,
-        remark: None,
+        remark: eq.remark.clone(),
+        calibrations: calibration_records(eq, defs.times),
     });
 
-    defs.sensor_map.insert(key, public_id.clone());
+    defs.sensor_map.insert(lookup, public_id.clone());
+    defs.sensor_map.entry(key).or_insert(public_id.clone());
     public_id
 }
 
-fn get_or_create_datalogger(
-    eq: &Equipment,
-    ch: &Channel,
-    _sta: &Station,
-    _net_code: &str,
-    defs: &mut Definitions,
-) -> String {
-    // Don't dedup dataloggers — each channel may have different sample rates/filters
-    let id = defs.next_id();
-    let public_id = format!(
-        "Datalogger/{}_{id}",
-        eq.model
-            .as_deref()
-            .unwrap_or("unknown")
-            .replace([' ', '/'], "_")
-    );
+fn get_or_create_datalogger(eq: &Equipment, origin: &Origin, defs: &mut Definitions) -> String {
+    let ch = origin.ch;
 
     // Extract datalogger gain from response stages (typically stage with Coefficients V→COUNTS)
     let dl_gain = ch.response.as_ref().and_then(|resp| {
@@ -354,55 +704,157 @@ fn get_or_create_datalogger(
                     .as_ref()
                     .is_some_and(|cf| cf.output_units.name == "COUNTS")
             })
-            .and_then(|s| s.stage_gain.as_ref().map(|g| g.value))
+            .and_then(|s| definition_gain(s).map(|g| g.value))
     });
 
-    // Compute sample rate fraction
-    let (num, denom) = float_to_fraction(ch.sample_rate);
+    // FDSN ClockDrift is seconds per sample; SC3ML expects seconds per second
+    let max_clock_drift = ch.clock_drift.map(|drift| drift * ch.sample_rate);
+
+    // This stream's decimation, then those of the definition's other rates
+    let stages = ch.response.as_ref().map_or(&[][..], |r| &r.stages[..]);
+    // PAZ stages after the sensor's (anti-alias filters, pre-amplifiers)
+    // form the analogue filter chain
+    let analogue = stages.iter().filter(|s| s.poles_zeros.is_some()).skip(1);
+    let mut decimations: Vec<Sc3mlDecimation> =
+        convert_decimation(ch.sample_rate, analogue, stages, dl_gain.is_some(), defs)
+            .into_iter()
+            .collect();
+    for extra in &eq.decimations {
+        let analogue = extra.stages.iter().filter(|s| s.poles_zeros.is_some());
+        decimations.extend(convert_decimation(
+            extra.sample_rate,
+            analogue,
+            &extra.stages,
+            true,
+            defs,
+        ));
+    }
 
-    // Build decimation with filter chains
-    let mut decimations = Vec::new();
+    // Streams recorded by the same physical digitizer share one definition
+    // that lists a decimation per sample rate. A preserved publicID identifies
+    // the digitizer; otherwise use its serial number (or, lacking one, the
+    // station), plus the gain and clock drift that the definition carries.
+    let content = format!(
+        "{}|{}|{:?}|{:?}",
+        datalogger_dedup_key(eq),
+        match &eq.serial_number {
+            Some(serial) => format!("SN {serial}"),
+            None => format!("{}.{}", origin.net_code, origin.sta_code),
+        },
+        dl_gain.map(f64::to_bits),
+        max_clock_drift.map(|drift| format!("{drift:.6e}")),
+    );
+    let base = preserved_key(eq.resource_id.as_deref()).unwrap_or_else(|| content.clone());
+
+    // A definition can only be shared if it filters each common rate the same
+    // way; otherwise these are different digitizers (e.g. two serial-less
+    // units of one model at a station), so try `key|2`, `key|3`, …
+    let mut n = 1;
+    loop {
+        let lookup = if n == 1 {
+            base.clone()
+        } else {
+            format!("{base}|{n}")
+        };
+        let Some(public_id) = defs.datalogger_map.get(&lookup).cloned() else {
+            let content = if n == 1 {
+                content
+            } else {
+                format!("{content}|{n}")
+            };
+            let public_id = defs.public_id(
+                eq.resource_id.as_deref(),
+                &definition_request(PublicIdKind::Datalogger, eq.model.as_deref(), &content),
+            );
+            let mut dl = Sc3mlDatalogger {
+                public_id: public_id.clone(),
+                name: eq.model.clone(),
+                description: eq.description.clone(),
+                gain: dl_gain,
+                max_clock_drift,
+                calibrations: calibration_records(eq, defs.times),
+                decimations: Vec::new(),
+                remark: eq.remark.clone(),
+            };
+            merge_decimations(&mut dl.decimations, decimations);
+            defs.dataloggers.push(dl);
+            defs.datalogger_map.insert(lookup, public_id.clone());
+            return public_id;
+        };
+
+        if let Some(dl) = defs
+            .dataloggers
+            .iter_mut()
+            .find(|d| d.public_id == public_id)
+            && decimations.iter().all(|dec| {
+                dl.decimations
+                    .iter()
+                    .all(|d| !same_rate(d, dec) || d == dec)
+            })
+        {
+            merge_calibrations(&mut dl.calibrations, eq, defs.times);
+            merge_decimations(&mut dl.decimations, decimations);
+            return public_id;
+        }
+        n += 1;
+    }
+}
 
-    // Check for FIR stages → digital filter chain
-    let fir_refs: Vec<String> = ch
-        .response
-        .as_ref()
-        .map(|resp| {
-            resp.stages
-                .iter()
-                .filter_map(|s| s.fir.as_ref().map(|fir| get_or_create_fir(fir, s, defs)))
-                .collect()
+/// Build a datalogger decimation at `sample_rate` from the `analogue` PAZ
+/// stages and the FIR stages among `stages`. Without filters, the
+/// decimation is only written if `keep_empty` (the datalogger has a gain).
+fn convert_decimation<'a>(
+    sample_rate: f64,
+    analogue: impl Iterator<Item = &'a ResponseStage>,
+    stages: &[ResponseStage],
+    keep_empty: bool,
+    defs: &mut Definitions,
+) -> Option<Sc3mlDecimation> {
+    let fir_refs: Vec<String> = stages
+        .iter()
+        .filter_map(|s| s.fir.as_ref().map(|fir| get_or_create_fir(fir, s, defs)))
+        .collect();
+    let analogue_refs: Vec<String> = analogue
+        .filter_map(|s| {
+            s.poles_zeros
+                .as_ref()
+                .map(|pz| get_or_create_paz(pz, s, defs))
         })
-        .unwrap_or_default();
-
-    let digital_filter_chain = if fir_refs.is_empty() {
-        None
-    } else {
-        Some(fir_refs.join(" "))
-    };
+        .collect();
+    let chain = |refs: Vec<String>| (!refs.is_empty()).then(|| refs.join(" "));
+    let analogue_filter_chain = chain(analogue_refs);
+    let digital_filter_chain = chain(fir_refs);
 
-    if dl_gain.is_some() || digital_filter_chain.is_some() {
-        decimations.push(Sc3mlDecimation {
-            sample_rate_numerator: num,
-            sample_rate_denominator: denom,
-            analogue_filter_chain: None,
-            digital_filter_chain,
-        });
+    if !keep_empty && analogue_filter_chain.is_none() && digital_filter_chain.is_none() {
+        return None;
     }
+    let (num, denom) = float_to_fraction(sample_rate);
+    Some(Sc3mlDecimation {
+        sample_rate_numerator: num,
+        sample_rate_denominator: denom,
+        analogue_filter_chain,
+        digital_filter_chain,
+    })
+}
 
-    defs.dataloggers.push(Sc3mlDatalogger {
-        public_id: public_id.clone(),
-        name: eq.model.clone(),
-        description: eq.description.clone(),
-        gain: dl_gain,
-        max_clock_drift: Some(0.0),
-        decimations,
-        remark: None,
-    });
+fn same_rate(a: &Sc3mlDecimation, b: &Sc3mlDecimation) -> bool {
+    a.sample_rate_numerator == b.sample_rate_numerator
+        && a.sample_rate_denominator == b.sample_rate_denominator
+}
 
-    defs.datalogger_map
-        .insert(datalogger_dedup_key(eq), public_id.clone());
-    public_id
+/// Add the decimations whose rate `target` does not list yet.
+fn merge_decimations(target: &mut Vec<Sc3mlDecimation>, decimations: Vec<Sc3mlDecimation>) {
+    for dec in decimations {
+        if !target.iter().any(|d| same_rate(d, &dec)) {
+            target.push(dec);
+        }
+    }
+}
+
+/// Gain of the shared definition a stage is written to: the nominal gain
+/// if the stage carries a calibrated one.
+fn definition_gain(stage: &ResponseStage) -> Option<&StageGain> {
+    stage.nominal_gain.as_ref().or(stage.stage_gain.as_ref())
 }
 
 fn get_or_create_paz(pz: &PolesZeros, stage: &ResponseStage, defs: &mut Definitions) -> String {
@@ -422,12 +874,15 @@ fn get_or_create_paz(pz: &PolesZeros, stage: &ResponseStage, defs: &mut Definiti
             .collect::<Vec<_>>(),
     );
 
-    if let Some(existing) = defs.paz_map.get(&key) {
+    let lookup = preserved_key(pz.resource_id.as_deref()).unwrap_or_else(|| key.clone());
+    if let Some(existing) = defs.paz_map.get(&lookup) {
         return existing.clone();
     }
 
-    let id = defs.next_id();
-    let public_id = format!("ResponsePAZ/{id}");
+    let public_id = defs.public_id(
+        pz.resource_id.as_deref(),
+        &definition_request(PublicIdKind::ResponsePaz, None, &key),
+    );
 
     let zeros_str = if pz.zeros.is_empty() {
         None
@@ -444,8 +899,8 @@ fn get_or_create_paz(pz: &PolesZeros, stage: &ResponseStage, defs: &mut Definiti
         public_id: public_id.clone(),
         name: None,
         paz_type: Some(format_pz_type(&pz.pz_transfer_function_type)),
-        gain: stage.stage_gain.as_ref().map(|g| g.value),
-        gain_frequency: stage.stage_gain.as_ref().map(|g| g.frequency),
+        gain: definition_gain(stage).map(|g| g.value),
+        gain_frequency: definition_gain(stage).map(|g| g.frequency),
         normalization_factor: Some(pz.normalization_factor),
         normalization_frequency: Some(pz.normalization_frequency),
         number_of_zeros: Some(pz.zeros.len() as u32),
@@ -455,27 +910,35 @@ fn get_or_create_paz(pz: &PolesZeros, stage: &ResponseStage, defs: &mut Definiti
         remark: None,
     });
 
-    defs.paz_map.insert(key, public_id.clone());
+    defs.paz_map.insert(lookup, public_id.clone());
+    defs.paz_map.entry(key).or_insert(public_id.clone());
     public_id
 }
 
-fn get_or_create_fir(fir: &FIR, stage: &ResponseStage, defs: &mut Definitions) -> String {
-    // Build dedup key from symmetry + coefficients
-    let key = format!(
+/// Build a FIR dedup key from symmetry + coefficients.
+fn fir_dedup_key(fir: &FIR) -> String {
+    format!(
         "{}|{:?}",
         format_symmetry(&fir.symmetry),
         fir.numerator_coefficients
             .iter()
             .map(|c| c.to_bits())
             .collect::<Vec<_>>(),
-    );
+    )
+}
+
+fn get_or_create_fir(fir: &FIR, stage: &ResponseStage, defs: &mut Definitions) -> String {
+    let key = fir_dedup_key(fir);
 
-    if let Some(existing) = defs.fir_map.get(&key) {
+    let lookup = preserved_key(fir.resource_id.as_deref()).unwrap_or_else(|| key.clone());
+    if let Some(existing) = defs.fir_map.get(&lookup) {
         return existing.clone();
     }
 
-    let id = defs.next_id();
-    let public_id = format!("ResponseFIR/{id}");
+    let public_id = defs.public_id(
+        fir.resource_id.as_deref(),
+        &definition_request(PublicIdKind::ResponseFir, None, &key),
+    );
 
     let coefficients = if fir.numerator_coefficients.is_empty() {
         None
@@ -486,23 +949,61 @@ fn get_or_create_fir(fir: &FIR, stage: &ResponseStage, defs: &mut Definitions) -
     defs.response_fir.push(Sc3mlResponseFir {
         public_id: public_id.clone(),
         name: None,
-        gain: stage.stage_gain.as_ref().map(|g| g.value),
-        gain_frequency: stage.stage_gain.as_ref().map(|g| g.frequency),
+        gain: definition_gain(stage).map(|g| g.value),
+        gain_frequency: definition_gain(stage).map(|g| g.frequency),
         decimation_factor: stage.decimation.as_ref().map(|d| d.factor),
-        delay: stage.decimation.as_ref().map(|d| d.delay),
-        correction: stage.decimation.as_ref().map(|d| d.correction),
+        // FDSN gives delay and correction in seconds; SC3ML in input samples
+        delay: stage
+            .decimation
+            .as_ref()
+            .map(|d| d.delay * d.input_sample_rate),
+        correction: stage
+            .decimation
+            .as_ref()
+            .map(|d| d.correction * d.input_sample_rate),
         number_of_coefficients: Some(fir.numerator_coefficients.len() as u32),
         symmetry: Some(format_symmetry(&fir.symmetry)),
         coefficients,
         remark: None,
     });
 
-    defs.fir_map.insert(key, public_id.clone());
+    defs.fir_map.insert(lookup, public_id.clone());
+    defs.fir_map.entry(key).or_insert(public_id.clone());
     public_id
 }
 
 // ─── Formatting helpers ──────────────────────────────────────────────
 
+/// SC3ML only knows restricted or not; partial counts as restricted.
+fn is_restricted(status: RestrictedStatus) -> bool {
+    status != RestrictedStatus::Open
+}
+
+/// Format channel types as SEED-style stream flags (e.g. `"GC"`).
+fn format_stream_flags(types: &[ChannelType]) -> Option<String> {
+    if types.is_empty() {
+        return None;
+    }
+    Some(
+        types
+            .iter()
+            .map(|t| match t {
+                ChannelType::Triggered => 'T',
+                ChannelType::Continuous => 'C',
+                ChannelType::Health => 'H',
+                ChannelType::Geophysical => 'G',
+                ChannelType::Weather => 'W',
+                ChannelType::Flag => 'F',
+                ChannelType::Synthesized => 'S',
+                ChannelType::Input => 'I',
+                ChannelType::Experimental => 'E',
+                ChannelType::Maintenance => 'M',
+                ChannelType::Beam => 'B',
+            })
+            .collect(),
+    )
+}
+
 fn format_pz_type(pz: &PzTransferFunction) -> String {
     match pz {
         PzTransferFunction::LaplaceRadians => "A".into(),
@@ -520,6 +1021,8 @@ fn format_symmetry(sym: &Symmetry) -> String {
 }
 
 /// Format complex numbers as SC3ML: `(real,imag) (real,imag)`
+///
+/// Response values ignore the style's float format and always round-trip.
 fn format_complex_array(pzs: &[PoleZero]) -> String {
     pzs.iter()
         .map(|pz| format!("({},{})", pz.real, pz.imaginary))
@@ -527,7 +1030,7 @@ fn format_complex_array(pzs: &[PoleZero]) -> String {
         .join(" ")
 }
 
-/// Format float array as space-separated values.
+/// Format float array as space-separated values, in round-trip form.
 fn format_float_array(values: &[f64]) -> String {
     values
         .iter()
@@ -574,25 +1077,54 @@ mod tests {
             source: "Test".into(),
             sender: None,
             created: Some(Utc::now()),
+            module: None,
+            module_uri: None,
+            schema_version: None,
+            extensions: Extensions::default(),
             networks: vec![Network {
                 code: "XX".into(),
                 description: Some("Test Network".into()),
                 start_date: None,
                 end_date: None,
+                restricted_status: None,
+                network_type: None,
+                network_class: None,
+                archive: None,
+                shared: None,
+                public_id: None,
+                comments: vec![],
+                extensions: Extensions::default(),
                 stations: vec![Station {
                     code: "PBUMI".into(),
                     description: None,
                     latitude: -7.7714,
                     longitude: 110.3776,
                     elevation: 150.0,
+                    latitude_uncertainty: None,
+                    longitude_uncertainty: None,
+                    elevation_uncertainty: None,
                     site: Site {
                         name: "Yogyakarta".into(),
                         country: Some("Indonesia".into()),
                         ..Default::default()
                     },
+                    water_level: None,
+                    vault: None,
+                    geology: None,
+                    equipment: vec![],
+                    external_references: vec![],
                     start_date: None,
                     end_date: None,
                     creation_date: None,
+                    restricted_status: None,
+                    station_type: None,
+                    affiliation: None,
+                    archive: None,
+                    archive_network_code: None,
+                    shared: None,
+                    public_id: None,
+                    comments: vec![],
+                    extensions: Extensions::default(),
                     channels: vec![
                         Channel {
                             code: "SHZ".into(),
@@ -603,9 +1135,25 @@ mod tests {
                             depth: 0.0,
                             azimuth: 0.0,
                             dip: -90.0,
+                            latitude_uncertainty: None,
+                            longitude_uncertainty: None,
+                            elevation_uncertainty: None,
+                            depth_uncertainty: None,
+                            azimuth_uncertainty: None,
+                            dip_uncertainty: None,
                             sample_rate: 100.0,
+                            channel_types: vec![],
+                            storage_format: None,
+                            clock_drift: None,
+                            calibration_units: None,
+                            water_level: None,
                             start_date: None,
                             end_date: None,
+                            restricted_status: None,
+                            shared: None,
+                            sensor_location_id: None,
+                            comments: vec![],
+                            sensor_location_comments: vec![],
                             sensor: Some(Equipment {
                                 equipment_type: Some("Geophone".into()),
                                 model: Some("GS-11D".into()),
@@ -613,11 +1161,16 @@ mod tests {
                                 serial_number: Some("1234".into()),
                                 ..Default::default()
                             }),
+                            pre_amplifier: None,
                             data_logger: Some(Equipment {
                                 equipment_type: Some("Datalogger".into()),
                                 model: Some("PB-24".into()),
                                 ..Default::default()
                             }),
+                            equipment: vec![],
+                            aux_device: None,
+                            aux_source: None,
+                            extensions: Extensions::default(),
                             response: Some(Response {
                                 instrument_sensitivity: Some(InstrumentSensitivity {
                                     value: 53687084.8,
@@ -638,6 +1191,7 @@ mod tests {
                                             value: 32.0,
                                             frequency: 15.0,
                                         }),
+                                        nominal_gain: None,
                                         poles_zeros: Some(PolesZeros {
                                             input_units: Units {
                                                 name: "M/S".into(),
@@ -656,11 +1210,15 @@ mod tests {
                                                     number: 0,
                                                     real: 0.0,
                                                     imaginary: 0.0,
+                                                    real_uncertainty: None,
+                                                    imaginary_uncertainty: None,
                                                 },
                                                 PoleZero {
                                                     number: 1,
                                                     real: 0.0,
                                                     imaginary: 0.0,
+                                                    real_uncertainty: None,
+                                                    imaginary_uncertainty: None,
                                                 },
                                             ],
                                             poles: vec![
@@ -668,13 +1226,18 @@ mod tests {
                                                     number: 0,
                                                     real: -22.2111,
                                                     imaginary: 22.2111,
+                                                    real_uncertainty: None,
+                                                    imaginary_uncertainty: None,
                                                 },
                                                 PoleZero {
                                                     number: 1,
                                                     real: -22.2111,
                                                     imaginary: -22.2111,
+                                                    real_uncertainty: None,
+                                                    imaginary_uncertainty: None,
                                                 },
                                             ],
+                                            resource_id: None,
                                         }),
                                         coefficients: None,
                                         fir: None,
@@ -686,6 +1249,7 @@ mod tests {
                                             value: 1677721.4,
                                             frequency: 15.0,
                                         }),
+                                        nominal_gain: None,
                                         poles_zeros: None,
                                         coefficients: Some(Coefficients {
                                             input_units: Units {
@@ -721,9 +1285,25 @@ mod tests {
                             depth: 0.0,
                             azimuth: 0.0,
                             dip: 0.0,
+                            latitude_uncertainty: None,
+                            longitude_uncertainty: None,
+                            elevation_uncertainty: None,
+                            depth_uncertainty: None,
+                            azimuth_uncertainty: None,
+                            dip_uncertainty: None,
                             sample_rate: 100.0,
+                            channel_types: vec![],
+                            storage_format: None,
+                            clock_drift: None,
+                            calibration_units: None,
+                            water_level: None,
                             start_date: None,
                             end_date: None,
+                            restricted_status: None,
+                            shared: None,
+                            sensor_location_id: None,
+                            comments: vec![],
+                            sensor_location_comments: vec![],
                             sensor: Some(Equipment {
                                 equipment_type: Some("Geophone".into()),
                                 model: Some("GS-11D".into()),
@@ -731,7 +1311,12 @@ mod tests {
                                 serial_number: Some("1235".into()),
                                 ..Default::default()
                             }),
+                            pre_amplifier: None,
                             data_logger: None,
+                            equipment: vec![],
+                            aux_device: None,
+                            aux_source: None,
+                            extensions: Extensions::default(),
                             response: None,
                         },
                     ],
@@ -794,6 +1379,14 @@ mod tests {
         assert!(xml.contains("<gainUnit>M/S</gainUnit>"));
     }
 
+    #[test]
+    fn write_clock_drift_per_second() {
+        let mut inv = make_test_inventory();
+        inv.networks[0].stations[0].channels[0].clock_drift = Some(0.0001);
+        let xml = write_to_string(&inv).unwrap();
+        assert!(xml.contains("<maxClockDrift>0.01</maxClockDrift>"));
+    }
+
     #[test]
     fn float_to_fraction_integer() {
         assert_eq!(float_to_fraction(100.0), (100, 1));
@@ -819,11 +1412,15 @@ mod tests {
                 number: 0,
                 real: -0.037,
                 imaginary: 0.037,
+                real_uncertainty: None,
+                imaginary_uncertainty: None,
             },
             PoleZero {
                 number: 1,
                 real: -0.037,
                 imaginary: -0.037,
+                real_uncertainty: None,
+                imaginary_uncertainty: None,
             },
         ];
         let s = format_complex_array(&pzs);
@@ -849,4 +1446,139 @@ mod tests {
             "Expected 1 sensor definition, found {sensor_count} in: {xml}"
         );
     }
+
+    #[test]
+    fn write_default_version() {
+        let inv = make_test_inventory();
+        let xml = write_to_string(&inv).unwrap();
+        assert!(xml.contains(r#"xmlns="http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/0.13""#));
+        assert!(xml.contains("<gainUnit>"));
+    }
+
+    #[test]
+    fn write_older_version_drops_newer_elements() {
+        let inv = make_test_inventory();
+        let opts = Sc3mlWriteOptions::default().version(Sc3mlVersion::V0_6);
+        let xml = write_to_string_with(&inv, &opts).unwrap();
+        assert!(xml.contains(r#"xmlns="http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/0.6""#));
+        assert!(xml.contains(r#"version="0.6""#));
+        assert!(!xml.contains("<gainUnit>"));
+
+        let inv2 = crate::sc3ml::reader::read_from_str(&xml).unwrap();
+        assert_eq!(inv2.schema_version.as_deref(), Some("0.6"));
+        assert_eq!(inv2.networks[0].stations[0].channels.len(), 2);
+    }
+
+    #[test]
+    fn preserved_datalogger_id_is_shared() {
+        let mut inv = make_test_inventory();
+        let channels = &mut inv.networks[0].stations[0].channels;
+        channels[0].data_logger.as_mut().unwrap().resource_id = Some("Datalogger#1".into());
+        channels[1].data_logger = channels[0].data_logger.clone();
+        channels[1].response = channels[0].response.clone();
+        channels[1].sample_rate = 50.0;
+        let xml = write_to_string(&inv).unwrap();
+        assert_eq!(xml.matches("<datalogger ").count(), 1, "{xml}");
+        assert_eq!(xml.matches(r#"datalogger="Datalogger#1""#).count(), 2);
+        assert_eq!(xml.matches("<decimation ").count(), 2, "{xml}");
+    }
+
+    #[test]
+    fn datalogger_shared_per_digitizer() {
+        let mut inv = make_test_inventory();
+        let sta = &mut inv.networks[0].stations[0];
+        sta.channels[1].data_logger = sta.channels[0].data_logger.clone();
+        sta.channels[1].response = sta.channels[0].response.clone();
+        sta.channels[1].sample_rate = 50.0;
+        let mut other = sta.clone();
+        other.code = "OTHER".into();
+        for ch in &mut other.channels {
+            ch.data_logger.as_mut().unwrap().serial_number = None;
+        }
+        inv.networks[0].stations.push(other);
+
+        let xml = write_to_string(&inv).unwrap();
+        // PBUMI's two streams share one digitizer; OTHER (no serial) gets its own
+        assert_eq!(xml.matches("<datalogger ").count(), 2, "{xml}");
+        assert_eq!(xml.matches("<decimation ").count(), 4, "{xml}");
+
+        let inv2 = crate::sc3ml::reader::read_from_str(&xml).unwrap();
+        let ch = &inv2.networks[0].stations[0].channels[1];
+        assert_eq!(ch.sample_rate, 50.0);
+        assert!(ch.response.as_ref().unwrap().stages.len() > 1);
+    }
+
+    fn fir_response(coefficients: &[f64]) -> Response {
+        let counts = || Units {
+            name: "COUNTS".into(),
+            description: None,
+        };
+        Response {
+            instrument_sensitivity: None,
+            stages: vec![ResponseStage {
+                number: 1,
+                stage_gain: Some(StageGain {
+                    value: 1.0,
+                    frequency: 0.0,
+                }),
+                nominal_gain: None,
+                poles_zeros: None,
+                coefficients: None,
+                fir: Some(FIR {
+                    input_units: counts(),
+                    output_units: counts(),
+                    symmetry: Symmetry::None,
+                    numerator_coefficients: coefficients.to_vec(),
+                    resource_id: None,
+                }),
+                decimation: Some(Decimation {
+                    input_sample_rate: 100.0,
+                    factor: 1,
+                    offset: 0,
+                    delay: 0.0,
+                    correction: 0.0,
+                }),
+            }],
+        }
+    }
+
+    #[test]
+    fn same_rate_with_other_filters_gets_own_datalogger() {
+        // Two serial-less digitizers of one model at one station
+        let centaur = Equipment {
+            model: Some("Centaur".into()),
+            ..Default::default()
+        };
+        let inv = Inventory::builder()
+            .source("Test")
+            .network("XX", |net| {
+                net.station("STA", |sta| {
+                    sta.channel("HHZ", "00", |ch| {
+                        ch.sample_rate(100.0)
+                            .data_logger(centaur.clone())
+                            .response(fir_response(&[0.25, 0.5, 0.25]))
+                    })
+                    .channel("EHZ", "00", |ch| {
+                        ch.sample_rate(100.0)
+                            .data_logger(centaur.clone())
+                            .response(fir_response(&[0.1, 0.2, 0.3, 0.4]))
+                    })
+                })
+            })
+            .build();
+
+        let xml = write_to_string(&inv).unwrap();
+        assert_eq!(xml.matches("<datalogger ").count(), 2, "{xml}");
+
+        let inv2 = crate::sc3ml::reader::read_from_str(&xml).unwrap();
+        let firs: Vec<Vec<f64>> = inv2.networks[0].stations[0]
+            .channels
+            .iter()
+            .map(|ch| {
+                let stage = &ch.response.as_ref().unwrap().stages[0];
+                stage.fir.as_ref().unwrap().numerator_coefficients.clone()
+            })
+            .collect();
+        assert_eq!(firs, [vec![0.25, 0.5, 0.25], vec![0.1, 0.2, 0.3, 0.4]]);
+    }
 }
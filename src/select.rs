@@ -0,0 +1,246 @@
+//! Sub-selection of an inventory by NSLC codes and time window.
+//!
+//! Patterns follow FDSN web service conventions: comma-separated lists of
+//! codes, `*` and `?` wildcards, and `--` for an empty location code.
+//!
+//! ```
+//! use stationxml_rs::{Inventory, Selection};
+//!
+//! let inv = Inventory::builder()
+//!     .network("XX", |net| {
+//!         net.station("PBUMI", |sta| {
+//!             sta.channel("SHZ", "00", |ch| ch).channel("HNZ", "00", |ch| ch)
+//!         })
+//!     })
+//!     .build();
+//!
+//! let sel = Selection::default().channel("SH?");
+//! let picked = inv.select(&sel);
+//! assert_eq!(picked.networks[0].stations[0].channels.len(), 1);
+//! ```
+
+use chrono::{DateTime, Utc};
+
+use crate::inventory::*;
+
+/// Filter applied by [`Inventory::select`].
+///
+/// Unset fields match everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Selection {
+    /// Network code pattern
+    pub network: Option<String>,
+    /// Station code pattern
+    pub station: Option<String>,
+    /// Location code pattern (`--` matches the empty code)
+    pub location: Option<String>,
+    /// Channel code pattern
+    pub channel: Option<String>,
+    /// Keep epochs that end at or after this time
+    pub start: Option<DateTime<Utc>>,
+    /// Keep epochs that start at or before this time
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl Selection {
+    /// Set the network pattern.
+    pub fn network(mut self, pattern: impl Into<String>) -> Self {
+        self.network = Some(pattern.into());
+        self
+    }
+
+    /// Set the station pattern.
+    pub fn station(mut self, pattern: impl Into<String>) -> Self {
+        self.station = Some(pattern.into());
+        self
+    }
+
+    /// Set the location pattern.
+    pub fn location(mut self, pattern: impl Into<String>) -> Self {
+        self.location = Some(pattern.into());
+        self
+    }
+
+    /// Set the channel pattern.
+    pub fn channel(mut self, pattern: impl Into<String>) -> Self {
+        self.channel = Some(pattern.into());
+        self
+    }
+
+    /// Keep only epochs active at some point in `[start, end]`.
+    pub fn time_window(mut self, start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    /// Selection from an `NET.STA.LOC.CHA` string; empty or missing parts match everything.
+    pub fn from_nslc(nslc: &str) -> Self {
+        let mut parts = nslc
+            .split('.')
+            .map(|p| (!p.is_empty()).then(|| p.to_string()));
+        Self {
+            network: parts.next().flatten(),
+            station: parts.next().flatten(),
+            location: parts.next().flatten(),
+            channel: parts.next().flatten(),
+            ..Self::default()
+        }
+    }
+
+    fn overlaps(&self, start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) -> bool {
+        let starts_in_time = match (start, self.end) {
+            (Some(start), Some(until)) => start <= until,
+            _ => true,
+        };
+        let ends_in_time = match (end, self.start) {
+            (Some(end), Some(from)) => end >= from,
+            _ => true,
+        };
+        starts_in_time && ends_in_time
+    }
+}
+
+impl Inventory {
+    /// Copy of this inventory restricted to the networks, stations and
+    /// channels matching `selection`.
+    ///
+    /// Stations left without channels are dropped when a location or channel
+    /// pattern is set; networks left without stations are dropped when any
+    /// station-level criterion is set.
+    pub fn select(&self, selection: &Selection) -> Inventory {
+        let channel_level = selection.location.is_some() || selection.channel.is_some();
+        let station_level = channel_level || selection.station.is_some();
+        let networks = self
+            .networks
+            .iter()
+            .filter(|net| {
+                matches_opt(selection.network.as_deref(), &net.code)
+                    && selection.overlaps(net.start_date, net.end_date)
+            })
+            .filter_map(|net| {
+                let stations: Vec<Station> = net
+                    .stations
+                    .iter()
+                    .filter(|sta| {
+                        matches_opt(selection.station.as_deref(), &sta.code)
+                            && selection.overlaps(sta.start_date, sta.end_date)
+                    })
+                    .filter_map(|sta| {
+                        let channels: Vec<Channel> = sta
+                            .channels
+                            .iter()
+                            .filter(|ch| {
+                                matches_opt(selection.location.as_deref(), &ch.location_code)
+                                    && matches_opt(selection.channel.as_deref(), &ch.code)
+                                    && selection.overlaps(ch.start_date, ch.end_date)
+                            })
+                            .cloned()
+                            .collect();
+                        (!channel_level || !channels.is_empty()).then(|| Station {
+                            channels,
+                            ..sta.without_channels()
+                        })
+                    })
+                    .collect();
+                (!station_level || !stations.is_empty()).then(|| Network {
+                    stations,
+                    ..net.without_stations()
+                })
+            })
+            .collect();
+        Inventory {
+            networks,
+            ..self.without_networks()
+        }
+    }
+}
+
+fn matches_opt(pattern: Option<&str>, code: &str) -> bool {
+    pattern.is_none_or(|pattern| matches_pattern(pattern, code))
+}
+
+/// Match `code` against a comma-separated list of wildcard patterns.
+fn matches_pattern(pattern: &str, code: &str) -> bool {
+    pattern.split(',').map(str::trim).any(|p| match p {
+        "--" => code.is_empty(),
+        _ => wildcard(p.as_bytes(), code.as_bytes()),
+    })
+}
+
+/// `*`/`?` glob match, ASCII case-insensitive.
+fn wildcard(pattern: &[u8], text: &[u8]) -> bool {
+    match (pattern.first(), text.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            wildcard(&pattern[1..], text) || (!text.is_empty() && wildcard(pattern, &text[1..]))
+        }
+        (Some(b'?'), Some(_)) => wildcard(&pattern[1..], &text[1..]),
+        (Some(p), Some(t)) if p.eq_ignore_ascii_case(t) => wildcard(&pattern[1..], &text[1..]),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(year: i32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    fn inventory() -> Inventory {
+        Inventory::builder()
+            .network("XX", |net| {
+                net.station("PBUMI", |sta| {
+                    sta.channel("SHZ", "00", |ch| {
+                        ch.start_date(date(2020)).end_date(date(2022))
+                    })
+                    .channel("SHZ", "00", |ch| ch.start_date(date(2022)))
+                    .channel("HNZ", "", |ch| ch)
+                })
+                .station("OTHER", |sta| sta.channel("BHZ", "10", |ch| ch))
+            })
+            .network("YY", |net| net.station("FAR", |sta| sta))
+            .build()
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(matches_pattern("SH?", "SHZ"));
+        assert!(matches_pattern("B*,SH*", "SHN"));
+        assert!(matches_pattern("*", ""));
+        assert!(matches_pattern("--", ""));
+        assert!(!matches_pattern("--", "00"));
+        assert!(matches_pattern("pb*", "PBUMI"));
+        assert!(!matches_pattern("SH?", "SH"));
+    }
+
+    #[test]
+    fn select_by_codes() {
+        let inv = inventory();
+        let sel = Selection::from_nslc("XX..--.");
+        let picked = inv.select(&sel);
+        assert_eq!(picked.networks.len(), 1);
+        assert_eq!(picked.networks[0].stations.len(), 1);
+        assert_eq!(picked.networks[0].stations[0].channels[0].code, "HNZ");
+    }
+
+    #[test]
+    fn select_by_time() {
+        let inv = inventory();
+        let sel = Selection::default()
+            .channel("SHZ")
+            .time_window(Some(date(2023)), None);
+        let channels = &inv.select(&sel).networks[0].stations[0].channels;
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].start_date, Some(date(2022)));
+    }
+
+    #[test]
+    fn empty_selection_keeps_everything() {
+        let inv = inventory();
+        assert_eq!(inv.select(&Selection::default()), inv);
+    }
+}
@@ -0,0 +1,723 @@
+//! Output styling shared by the XML writers.
+//!
+//! By default both writers emit a compact document on a single line, with
+//! floats in Rust's shortest round-trip form and attributes in schema order.
+//! [`XmlStyle`] trades that for output that diffs well under version
+//! control: indentation, a stable float precision and sorted attributes.
+//! The float precision applies to coordinates and other metadata only:
+//! response values, sample rates and clock drifts are always written in
+//! round-trip form, since rounding a FIR coefficient or a pole changes the
+//! instrument response.
+//! Datetimes keep their fractional seconds unless [`TimeFormat`] fixes the
+//! precision.
+
+use std::io;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use serde::ser::{self, Serialize, Serializer};
+
+use crate::datetime::format_datetime;
+use crate::error::{Result, StationXmlError};
+
+/// How floating-point values are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FloatFormat {
+    /// Shortest representation that parses back to the same `f64` (`0.1`, `1e-12`…)
+    #[default]
+    Shortest,
+    /// Rounded to this many significant digits, then written in shortest form
+    /// (`Significant(6)`: `0.123456789` → `0.123457`, `98.0` → `98`)
+    Significant(u8),
+    /// This many digits after the decimal point, trailing zeros kept
+    /// (`Fixed(3)`: `98.0` → `98.000`)
+    Fixed(u8),
+}
+
+impl FloatFormat {
+    /// Format `value`. Non-finite values are written as-is.
+    pub fn format(&self, value: f64) -> String {
+        match *self {
+            _ if !value.is_finite() => value.to_string(),
+            Self::Shortest => value.to_string(),
+            Self::Significant(digits) => {
+                let digits = usize::from(digits.max(1));
+                let rounded: f64 = format!("{:.*e}", digits - 1, value)
+                    .parse()
+                    .unwrap_or(value);
+                rounded.to_string()
+            }
+            Self::Fixed(decimals) => format!("{:.*}", usize::from(decimals), value),
+        }
+    }
+}
+
+/// How datetimes are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TimeFormat {
+    /// Whole seconds, or as many fractional digits as the value needs in
+    /// groups of three (`00:00:00Z`, `00:00:00.500Z`, `00:00:00.000001Z`)
+    #[default]
+    Auto,
+    /// Always six fractional digits (`00:00:00.000000Z`); nanoseconds are
+    /// truncated
+    Microseconds,
+}
+
+impl TimeFormat {
+    /// Format `dt` as RFC 3339 with a `Z` suffix.
+    pub fn format(&self, dt: &DateTime<Utc>) -> String {
+        match self {
+            Self::Auto => format_datetime(dt),
+            Self::Microseconds => dt.to_rfc3339_opts(SecondsFormat::Micros, true),
+        }
+    }
+
+    pub(crate) fn format_opt(&self, dt: &Option<DateTime<Utc>>) -> Option<String> {
+        dt.as_ref().map(|dt| self.format(dt))
+    }
+}
+
+/// Order of attributes within each start tag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AttributeOrder {
+    /// The order the schema types declare them in
+    #[default]
+    Schema,
+    /// Namespace declarations first, then the remaining attributes by name
+    Alphabetical,
+}
+
+/// Formatting options for written XML.
+///
+/// ```
+/// use stationxml_rs::{Fdsn, FdsnWriteOptions, FloatFormat, Inventory, XmlStyle};
+///
+/// let inv = Inventory::builder()
+///     .source("Test")
+///     .network("XX", |net| net.station("STA", |sta| sta.latitude(-7.123456789)))
+///     .build();
+/// let style = XmlStyle::pretty().floats(FloatFormat::Significant(6));
+/// let xml = Fdsn::write_to_string_with(&inv, &FdsnWriteOptions::default().style(style)).unwrap();
+/// assert!(xml.contains("\n  <Network code=\"XX\">\n    <Station"));
+/// assert!(xml.contains("<Latitude>-7.12346</Latitude>"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct XmlStyle {
+    /// Spaces per nesting level; `None` writes everything on one line
+    pub indent: Option<usize>,
+    /// Float formatting of coordinates and other metadata; response values
+    /// are always written in shortest round-trip form
+    pub floats: FloatFormat,
+    /// Attribute ordering
+    pub attributes: AttributeOrder,
+    /// Datetime precision
+    pub times: TimeFormat,
+}
+
+impl XmlStyle {
+    /// Two-space indentation, otherwise the defaults.
+    pub fn pretty() -> Self {
+        Self::default().indent(2)
+    }
+
+    /// Indent nested elements by `spaces` per level.
+    pub fn indent(mut self, spaces: usize) -> Self {
+        self.indent = Some(spaces);
+        self
+    }
+
+    /// Set the float formatting of coordinates and other metadata.
+    pub fn floats(mut self, floats: FloatFormat) -> Self {
+        self.floats = floats;
+        self
+    }
+
+    /// Set the attribute ordering.
+    pub fn attributes(mut self, order: AttributeOrder) -> Self {
+        self.attributes = order;
+        self
+    }
+
+    /// Set the datetime precision.
+    pub fn times(mut self, times: TimeFormat) -> Self {
+        self.times = times;
+        self
+    }
+
+    /// True if serialized XML needs a [`restyle`] pass.
+    pub(crate) fn needs_restyle(&self) -> bool {
+        self.indent.is_some() || self.attributes != AttributeOrder::Schema
+    }
+
+    /// Event writer over `out` that applies the indentation.
+    pub(crate) fn writer<W: io::Write>(&self, out: W) -> Writer<W> {
+        match self.indent {
+            Some(spaces) => Writer::new_with_indent(out, b' ', spaces),
+            None => Writer::new(out),
+        }
+    }
+}
+
+/// Serialize `value` compactly into `out`, formatting floats per `style`.
+///
+/// Indentation and attribute order are applied afterwards by [`restyle`] /
+/// [`write_events`], so that post-processing such as extension splicing can
+/// work on compact XML.
+pub(crate) fn serialize<T: Serialize>(value: &T, style: &XmlStyle, out: &mut String) -> Result<()> {
+    let ser = quick_xml::se::Serializer::new(out);
+    match style.floats {
+        FloatFormat::Shortest => value.serialize(ser)?,
+        floats => Styled::new(value, floats).serialize(ser)?,
+    };
+    Ok(())
+}
+
+/// Indent and reorder attributes of compact `xml` per `style`.
+pub(crate) fn restyle(xml: String, style: &XmlStyle) -> Result<String> {
+    if !style.needs_restyle() {
+        return Ok(xml);
+    }
+    let mut writer = style.writer(Vec::with_capacity(xml.len() * 2));
+    write_events(&mut writer, &xml, style)?;
+    String::from_utf8(writer.into_inner()).map_err(|e| StationXmlError::InvalidData(e.to_string()))
+}
+
+/// Copy every event of compact `xml` to `writer`, reordering attributes per `style`.
+pub(crate) fn write_events<W: io::Write>(
+    writer: &mut Writer<W>,
+    xml: &str,
+    style: &XmlStyle,
+) -> Result<()> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Eof => return Ok(()),
+            event => write_event(writer, event, style)?,
+        }
+    }
+}
+
+/// Write one event, reordering its attributes per `style`.
+pub(crate) fn write_event<W: io::Write>(
+    writer: &mut Writer<W>,
+    event: Event,
+    style: &XmlStyle,
+) -> Result<()> {
+    let event = match (style.attributes, event) {
+        (AttributeOrder::Alphabetical, Event::Start(e)) => Event::Start(sorted(&e)?),
+        (AttributeOrder::Alphabetical, Event::Empty(e)) => Event::Empty(sorted(&e)?),
+        (_, event) => event,
+    };
+    writer.write_event(event)?;
+    Ok(())
+}
+
+fn sorted(e: &BytesStart) -> Result<BytesStart<'static>> {
+    let mut attributes = e
+        .attributes()
+        .map(|attr| attr.map_err(|err| xml_error(err.into())))
+        .collect::<Result<Vec<_>>>()?;
+    attributes.sort_by_key(|attr| (attr.key.as_namespace_binding().is_none(), attr.key.0));
+    let mut start = e.to_owned();
+    start.clear_attributes();
+    start.extend_attributes(attributes);
+    Ok(start.into_owned())
+}
+
+pub(crate) fn xml_error(e: quick_xml::Error) -> StationXmlError {
+    StationXmlError::InvalidData(format!("XML error: {e}"))
+}
+
+// ─── Float formatting serializer ────────────────────────────────────
+//
+// Neither quick-xml nor serde_json has a hook for writing floats. These
+// wrappers pass everything through to the inner serializer unchanged, except
+// `f32`/`f64` values, which a `FloatPolicy` writes. For XML, `FloatFormat`
+// writes them as strings; fields holding response values switch to the
+// shortest form.
+
+/// Struct fields (serialized names) whose values describe an instrument
+/// response or the sampling: FDSN `Response`, `SampleRate` and `ClockDrift`,
+/// and the SC3ML response and datalogger definitions, calibrations and
+/// stream gains.
+const RESPONSE_FIELDS: &[&str] = &[
+    "Response",
+    "SampleRate",
+    "ClockDrift",
+    "responsePAZ",
+    "responseFIR",
+    "datalogger",
+    "calibration",
+    "gain",
+    "gainFrequency",
+];
+
+/// How a float-formatting serializer writes floats.
+pub(crate) trait FloatPolicy: Copy {
+    /// Write `value` to `serializer`.
+    fn serialize_f64<S: Serializer>(
+        self,
+        value: f64,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>;
+
+    /// The policy for the value of struct field `key`.
+    fn field(self, key: &'static str) -> Self;
+}
+
+impl FloatPolicy for FloatFormat {
+    fn serialize_f64<S: Serializer>(
+        self,
+        value: f64,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.format(value))
+    }
+
+    fn field(self, key: &'static str) -> Self {
+        if RESPONSE_FIELDS.contains(&key) {
+            Self::Shortest
+        } else {
+            self
+        }
+    }
+}
+
+/// `value`, serialized with floats written per `floats`.
+pub(crate) struct Styled<'a, T: ?Sized, F> {
+    value: &'a T,
+    floats: F,
+}
+
+impl<'a, T: ?Sized, F> Styled<'a, T, F> {
+    pub(crate) fn new(value: &'a T, floats: F) -> Self {
+        Self { value, floats }
+    }
+}
+
+impl<T: Serialize + ?Sized, F: FloatPolicy> Serialize for Styled<'_, T, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.value.serialize(FloatSerializer {
+            inner: serializer,
+            floats: self.floats,
+        })
+    }
+}
+
+struct FloatSerializer<S, F> {
+    inner: S,
+    floats: F,
+}
+
+impl<S, F: FloatPolicy> FloatSerializer<S, F> {
+    fn wrap<'a, T: ?Sized>(&self, value: &'a T) -> Styled<'a, T, F> {
+        Styled {
+            value,
+            floats: self.floats,
+        }
+    }
+}
+
+macro_rules! forward {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(fn $method(self, v: $ty) -> std::result::Result<S::Ok, S::Error> {
+            self.inner.$method(v)
+        })*
+    };
+}
+
+impl<S: Serializer, F: FloatPolicy> Serializer for FloatSerializer<S, F> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Compound<S::SerializeSeq, F>;
+    type SerializeTuple = Compound<S::SerializeTuple, F>;
+    type SerializeTupleStruct = Compound<S::SerializeTupleStruct, F>;
+    type SerializeTupleVariant = Compound<S::SerializeTupleVariant, F>;
+    type SerializeMap = Compound<S::SerializeMap, F>;
+    type SerializeStruct = Compound<S::SerializeStruct, F>;
+    type SerializeStructVariant = Compound<S::SerializeStructVariant, F>;
+
+    forward!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str),
+    );
+
+    fn serialize_f32(self, v: f32) -> std::result::Result<S::Ok, S::Error> {
+        self.serialize_f64(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> std::result::Result<S::Ok, S::Error> {
+        self.floats.serialize_f64(v, self.inner)
+    }
+
+    fn serialize_none(self) -> std::result::Result<S::Ok, S::Error> {
+        self.inner.serialize_none()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(
+        self,
+        value: &T,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_some(&value)
+    }
+
+    fn serialize_unit(self) -> std::result::Result<S::Ok, S::Error> {
+        self.inner.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        self.inner.serialize_unit_variant(name, index, variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_newtype_struct(name, &value)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let value = self.wrap(value);
+        self.inner
+            .serialize_newtype_variant(name, index, variant, &value)
+    }
+
+    fn serialize_seq(
+        self,
+        len: Option<usize>,
+    ) -> std::result::Result<Self::SerializeSeq, S::Error> {
+        let floats = self.floats;
+        let inner = self.inner.serialize_seq(len)?;
+        Ok(Compound { inner, floats })
+    }
+
+    fn serialize_tuple(self, len: usize) -> std::result::Result<Self::SerializeTuple, S::Error> {
+        let floats = self.floats;
+        let inner = self.inner.serialize_tuple(len)?;
+        Ok(Compound { inner, floats })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> std::result::Result<Self::SerializeTupleStruct, S::Error> {
+        let floats = self.floats;
+        let inner = self.inner.serialize_tuple_struct(name, len)?;
+        Ok(Compound { inner, floats })
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> std::result::Result<Self::SerializeTupleVariant, S::Error> {
+        let floats = self.floats;
+        let inner = self
+            .inner
+            .serialize_tuple_variant(name, index, variant, len)?;
+        Ok(Compound { inner, floats })
+    }
+
+    fn serialize_map(
+        self,
+        len: Option<usize>,
+    ) -> std::result::Result<Self::SerializeMap, S::Error> {
+        let floats = self.floats;
+        let inner = self.inner.serialize_map(len)?;
+        Ok(Compound { inner, floats })
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> std::result::Result<Self::SerializeStruct, S::Error> {
+        let floats = self.floats;
+        let inner = self.inner.serialize_struct(name, len)?;
+        Ok(Compound { inner, floats })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> std::result::Result<Self::SerializeStructVariant, S::Error> {
+        let floats = self.floats;
+        let inner = self
+            .inner
+            .serialize_struct_variant(name, index, variant, len)?;
+        Ok(Compound { inner, floats })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+struct Compound<C, F> {
+    inner: C,
+    floats: F,
+}
+
+impl<C, F: FloatPolicy> Compound<C, F> {
+    fn wrap<'a, T: ?Sized>(&self, value: &'a T) -> Styled<'a, T, F> {
+        Styled {
+            value,
+            floats: self.floats,
+        }
+    }
+}
+
+impl<C: ser::SerializeSeq, F: FloatPolicy> ser::SerializeSeq for Compound<C, F> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> std::result::Result<(), C::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_element(&value)
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeTuple, F: FloatPolicy> ser::SerializeTuple for Compound<C, F> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> std::result::Result<(), C::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_element(&value)
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeTupleStruct, F: FloatPolicy> ser::SerializeTupleStruct for Compound<C, F> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> std::result::Result<(), C::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_field(&value)
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeTupleVariant, F: FloatPolicy> ser::SerializeTupleVariant for Compound<C, F> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> std::result::Result<(), C::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_field(&value)
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeMap, F: FloatPolicy> ser::SerializeMap for Compound<C, F> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(
+        &mut self,
+        key: &T,
+    ) -> std::result::Result<(), C::Error> {
+        let key = self.wrap(key);
+        self.inner.serialize_key(&key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> std::result::Result<(), C::Error> {
+        let value = self.wrap(value);
+        self.inner.serialize_value(&value)
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeStruct, F: FloatPolicy> ser::SerializeStruct for Compound<C, F> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> std::result::Result<(), C::Error> {
+        let value = Styled::new(value, self.floats.field(key));
+        self.inner.serialize_field(key, &value)
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> std::result::Result<(), C::Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeStructVariant, F: FloatPolicy> ser::SerializeStructVariant
+    for Compound<C, F>
+{
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> std::result::Result<(), C::Error> {
+        let value = Styled::new(value, self.floats.field(key));
+        self.inner.serialize_field(key, &value)
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> std::result::Result<(), C::Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn float_formats() {
+        assert_eq!(FloatFormat::Shortest.format(0.1), "0.1");
+        assert_eq!(FloatFormat::Significant(6).format(0.123456789), "0.123457");
+        assert_eq!(FloatFormat::Significant(6).format(98.0), "98");
+        assert_eq!(FloatFormat::Significant(3).format(-123456.0), "-123000");
+        assert_eq!(FloatFormat::Fixed(3).format(98.0), "98.000");
+        assert_eq!(FloatFormat::Fixed(2).format(f64::NAN), "NaN");
+    }
+
+    #[test]
+    fn time_formats() {
+        let dt = "2020-01-01T00:00:00.5Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(TimeFormat::Auto.format(&dt), "2020-01-01T00:00:00.500Z");
+        assert_eq!(
+            TimeFormat::Microseconds.format(&dt),
+            "2020-01-01T00:00:00.500000Z"
+        );
+        let whole = "2020-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(TimeFormat::Auto.format(&whole), "2020-01-01T00:00:00Z");
+        let micros = "2020-01-01T00:00:00.000001Z"
+            .parse::<DateTime<Utc>>()
+            .unwrap();
+        assert_eq!(
+            TimeFormat::Auto.format(&micros),
+            "2020-01-01T00:00:00.000001Z"
+        );
+    }
+
+    #[test]
+    fn serializer_formats_floats_only() {
+        #[derive(serde::Serialize)]
+        struct Point {
+            #[serde(rename = "@id")]
+            id: u32,
+            #[serde(rename = "@scale")]
+            scale: f64,
+            #[serde(rename = "Value")]
+            values: Vec<f64>,
+            #[serde(rename = "Label")]
+            label: Option<String>,
+        }
+        let point = Point {
+            id: 7,
+            scale: 1.0 / 3.0,
+            values: vec![2.5, 10.0],
+            label: Some("1.23456789".into()),
+        };
+        let mut xml = String::new();
+        let style = XmlStyle::default().floats(FloatFormat::Fixed(2));
+        serialize(&point, &style, &mut xml).unwrap();
+        assert_eq!(
+            xml,
+            r#"<Point id="7" scale="0.33"><Value>2.50</Value><Value>10.00</Value><Label>1.23456789</Label></Point>"#
+        );
+    }
+
+    #[test]
+    fn restyle_sorts_attributes() {
+        let xml = r#"<a z="1" xmlns="urn:a" b="2"><c y="&amp;" x="3"/>text</a>"#.to_string();
+        let style = XmlStyle::default().attributes(AttributeOrder::Alphabetical);
+        assert_eq!(
+            restyle(xml, &style).unwrap(),
+            r#"<a xmlns="urn:a" b="2" z="1"><c x="3" y="&amp;"/>text</a>"#
+        );
+    }
+
+    #[test]
+    fn restyle_indents_without_touching_text() {
+        let xml = "<a><b>line 1\n  line 2 </b><c/></a>".to_string();
+        assert_eq!(
+            restyle(xml, &XmlStyle::pretty()).unwrap(),
+            "<a>\n  <b>line 1\n  line 2 </b>\n  <c/>\n</a>"
+        );
+    }
+}
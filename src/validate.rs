@@ -0,0 +1,257 @@
+//! Consistency checks on an [`Inventory`].
+//!
+//! Readers accept anything the schema allows; [`Inventory::validate`] looks
+//! for metadata that parses but is almost certainly wrong — reversed epochs,
+//! out-of-range coordinates, overlapping channel epochs, broken response
+//! stage chains.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::inventory::*;
+
+/// How serious a [`ValidationIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Suspicious, but may be intended
+    Warning,
+    /// Invalid metadata
+    Error,
+}
+
+/// A problem found by [`Inventory::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    /// Severity of the problem
+    pub severity: Severity,
+    /// Where it was found, e.g. `XX.PBUMI.00.SHZ` or `XX.PBUMI.00.SHZ stage 3`
+    pub path: String,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity}: {}: {}", self.path, self.message)
+    }
+}
+
+impl Inventory {
+    /// Check the inventory for inconsistencies. An empty result means no
+    /// problems were found.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Issues::default();
+        for net in &self.networks {
+            let path = net.code.clone();
+            issues.check_epoch(&path, net.start_date, net.end_date);
+            for sta in &net.stations {
+                validate_station(sta, &format!("{}.{}", net.code, sta.code), &mut issues);
+            }
+        }
+        issues.0
+    }
+}
+
+#[derive(Default)]
+struct Issues(Vec<ValidationIssue>);
+
+impl Issues {
+    fn push(&mut self, severity: Severity, path: &str, message: String) {
+        self.0.push(ValidationIssue {
+            severity,
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    fn check_epoch(
+        &mut self,
+        path: &str,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) {
+        if let (Some(start), Some(end)) = (start, end)
+            && end < start
+        {
+            self.push(
+                Severity::Error,
+                path,
+                format!("end date {end} is before start date {start}"),
+            );
+        }
+    }
+
+    fn check_range(&mut self, path: &str, name: &str, value: f64, min: f64, max: f64) {
+        if !(min..=max).contains(&value) {
+            self.push(
+                Severity::Error,
+                path,
+                format!("{name} {value} outside [{min}, {max}]"),
+            );
+        }
+    }
+}
+
+fn validate_station(sta: &Station, path: &str, issues: &mut Issues) {
+    issues.check_epoch(path, sta.start_date, sta.end_date);
+    issues.check_range(path, "latitude", sta.latitude, -90.0, 90.0);
+    issues.check_range(path, "longitude", sta.longitude, -180.0, 180.0);
+
+    for (i, ch) in sta.channels.iter().enumerate() {
+        let ch_path = format!("{path}.{}.{}", ch.location_code, ch.code);
+        validate_channel(ch, &ch_path, issues);
+
+        let outside_station = matches!((ch.start_date, sta.start_date), (Some(c), Some(s)) if c < s)
+            || matches!((ch.end_date, sta.end_date), (Some(c), Some(s)) if c > s)
+            || (ch.end_date.is_none() && sta.end_date.is_some());
+        if outside_station {
+            issues.push(
+                Severity::Warning,
+                &ch_path,
+                "channel epoch extends beyond its station epoch".into(),
+            );
+        }
+
+        let overlapping = sta.channels[..i].iter().any(|other| {
+            other.code == ch.code
+                && other.location_code == ch.location_code
+                && epochs_overlap(other, ch)
+        });
+        if overlapping {
+            issues.push(
+                Severity::Error,
+                &ch_path,
+                "epoch overlaps another epoch of the same channel".into(),
+            );
+        }
+    }
+}
+
+fn validate_channel(ch: &Channel, path: &str, issues: &mut Issues) {
+    issues.check_epoch(path, ch.start_date, ch.end_date);
+    issues.check_range(path, "latitude", ch.latitude, -90.0, 90.0);
+    issues.check_range(path, "longitude", ch.longitude, -180.0, 180.0);
+    issues.check_range(path, "azimuth", ch.azimuth, 0.0, 360.0);
+    issues.check_range(path, "dip", ch.dip, -90.0, 90.0);
+    if ch.sample_rate < 0.0 {
+        issues.push(
+            Severity::Error,
+            path,
+            format!("negative sample rate {}", ch.sample_rate),
+        );
+    }
+    if let Some(response) = &ch.response {
+        validate_response(response, path, issues);
+    }
+}
+
+fn validate_response(response: &Response, path: &str, issues: &mut Issues) {
+    for (i, stage) in response.stages.iter().enumerate() {
+        let expected = i as u32 + 1;
+        if stage.number != expected {
+            issues.push(
+                Severity::Error,
+                &format!("{path} stage {}", stage.number),
+                format!("stage numbered {}, expected {expected}", stage.number),
+            );
+        }
+    }
+
+    let units: Vec<(u32, &Units, &Units)> = response
+        .stages
+        .iter()
+        .filter_map(|stage| stage_units(stage).map(|(i, o)| (stage.number, i, o)))
+        .collect();
+    for pair in units.windows(2) {
+        let (_, _, output) = pair[0];
+        let (number, input, _) = pair[1];
+        if !output.name.eq_ignore_ascii_case(&input.name) {
+            issues.push(
+                Severity::Warning,
+                &format!("{path} stage {number}"),
+                format!(
+                    "input units {} do not match previous stage output units {}",
+                    input.name, output.name
+                ),
+            );
+        }
+    }
+}
+
+fn stage_units(stage: &ResponseStage) -> Option<(&Units, &Units)> {
+    if let Some(pz) = &stage.poles_zeros {
+        Some((&pz.input_units, &pz.output_units))
+    } else if let Some(cf) = &stage.coefficients {
+        Some((&cf.input_units, &cf.output_units))
+    } else {
+        stage
+            .fir
+            .as_ref()
+            .map(|fir| (&fir.input_units, &fir.output_units))
+    }
+}
+
+fn epochs_overlap(a: &Channel, b: &Channel) -> bool {
+    let a_before_b = matches!((a.end_date, b.start_date), (Some(end), Some(start)) if end <= start);
+    let b_before_a = matches!((b.end_date, a.start_date), (Some(end), Some(start)) if end <= start);
+    !a_before_b && !b_before_a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(year: i32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn valid_inventory_has_no_issues() {
+        let inv = Inventory::builder()
+            .network("XX", |net| {
+                net.station("PBUMI", |sta| {
+                    sta.latitude(-7.7)
+                        .longitude(110.4)
+                        .channel("SHZ", "00", |ch| {
+                            ch.dip(-90.0).start_date(date(2020)).end_date(date(2022))
+                        })
+                        .channel("SHZ", "00", |ch| ch.dip(-90.0).start_date(date(2022)))
+                })
+            })
+            .build();
+        assert_eq!(inv.validate(), vec![]);
+    }
+
+    #[test]
+    fn reports_problems() {
+        let inv = Inventory::builder()
+            .network("XX", |net| {
+                net.station("PBUMI", |sta| {
+                    sta.latitude(95.0)
+                        .end_date(date(2021))
+                        .channel("SHZ", "00", |ch| {
+                            ch.start_date(date(2022)).end_date(date(2020))
+                        })
+                        .channel("SHZ", "00", |ch| ch.azimuth(400.0))
+                })
+            })
+            .build();
+        let issues = inv.validate();
+        let messages: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        assert!(messages.contains(&"error: XX.PBUMI: latitude 95 outside [-90, 90]".to_string()));
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.contains("end date") && m.contains("XX.PBUMI.00.SHZ"))
+        );
+        assert!(messages.iter().any(|m| m.contains("azimuth 400")));
+        assert!(messages.iter().any(|m| m.contains("overlaps")));
+        assert!(messages.iter().any(|m| m.starts_with("warning:")));
+    }
+}
@@ -0,0 +1,276 @@
+//! Integration tests for the `stationxml` binary (`cli` feature).
+
+#![cfg(feature = "cli")]
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use stationxml_rs::*;
+
+const FDSN: &str = "tests/fixtures/fdsn_sample.xml";
+const SC3ML: &str = "tests/fixtures/sc3ml_sample.xml";
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_stationxml"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("stationxml-cli-{}-{name}", std::process::id()))
+}
+
+#[test]
+fn convert_fdsn_to_sc3ml_file() {
+    let out = temp_path("convert.sc3ml");
+    let output = run(&["convert", FDSN, "-o", out.to_str().unwrap()]);
+    assert!(output.status.success(), "{output:?}");
+
+    let converted = read_from_file_as::<Sc3ml>(&out).unwrap();
+    let original = read_from_file(FDSN).unwrap();
+    assert_eq!(converted.networks[0].stations[0].channels.len(), 3);
+    assert_eq!(converted.networks[0].code, original.networks[0].code);
+    std::fs::remove_file(out).unwrap();
+}
+
+#[test]
+fn convert_to_stdout_with_explicit_format() {
+    let output = run(&["convert", SC3ML, "--to", "fdsn", "--fdsn-version", "1.1"]);
+    assert!(output.status.success());
+    let xml = stdout(&output);
+    assert_eq!(detect_format(&xml), Some(Format::Fdsn));
+    assert!(xml.contains(r#"schemaVersion="1.1""#));
+}
+
+#[test]
+fn fdsn_version_requires_fdsn_output() {
+    // FDSN input converts to SC3ML by default
+    let output = run(&["convert", FDSN, "--fdsn-version", "1.1"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--fdsn-version"), "{stderr}");
+    assert!(output.stdout.is_empty());
+
+    let output = run(&["convert", SC3ML, "--to", "json", "--fdsn-version", "1.1"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn info_reports_counts() {
+    let output = run(&["info", FDSN]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert!(text.contains("FDSN StationXML 1.2"), "{text}");
+    assert!(
+        text.contains("1 networks, 1 stations, 3 channels"),
+        "{text}"
+    );
+}
+
+#[test]
+fn select_filters_channels() {
+    let output = run(&["select", FDSN, "--nslc", "XX.PBUMI.*.SHZ", "--pretty"]);
+    assert!(output.status.success());
+    let inv = read_from_str(&stdout(&output)).unwrap();
+    let channels = &inv.networks[0].stations[0].channels;
+    assert_eq!(channels.len(), 1);
+    assert_eq!(channels[0].code, "SHZ");
+}
+
+#[test]
+fn validate_reports_success_and_failure() {
+    let output = run(&["validate", FDSN, SC3ML]);
+    assert!(output.status.success(), "{}", stdout(&output));
+
+    let broken = temp_path("broken.xml");
+    std::fs::write(&broken, "<FDSNStationXML><Network/></FDSNStationXML>").unwrap();
+    let output = run(&["validate", broken.to_str().unwrap()]);
+    assert!(!output.status.success());
+    std::fs::remove_file(broken).unwrap();
+}
+
+#[test]
+fn missing_file_fails() {
+    let output = run(&["info", "does/not/exist.xml"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("exist.xml"));
+}
+
+#[test]
+fn diff_reports_changes() {
+    let output = run(&["diff", FDSN, FDSN]);
+    assert!(output.status.success());
+    assert!(stdout(&output).is_empty());
+
+    let changed = temp_path("diff.xml");
+    let xml = std::fs::read_to_string(FDSN).unwrap().replacen(
+        "<SampleRate>100</SampleRate>",
+        "<SampleRate>50</SampleRate>",
+        1,
+    );
+    std::fs::write(&changed, xml).unwrap();
+
+    let output = run(&["diff", FDSN, changed.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    let text = stdout(&output);
+    assert!(text.contains("~ XX.PBUMI.00.SHZ"), "{text}");
+    assert!(text.contains("sample_rate: 100.0 -> 50.0"), "{text}");
+
+    let output = run(&["diff", "--json", FDSN, changed.to_str().unwrap()]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["changes"][0]["nslc"], "XX.PBUMI.00.SHZ");
+    std::fs::remove_file(changed).unwrap();
+
+    // Trouble is 2, as with diff(1)
+    let output = run(&["diff", FDSN, "tests/fixtures/missing.xml"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn merge_combines_formats() {
+    let other = temp_path("other.sc3ml");
+    let xml = std::fs::read_to_string(SC3ML)
+        .unwrap()
+        .replace(r#"code="PBUMI""#, r#"code="OTHER""#);
+    std::fs::write(&other, xml).unwrap();
+
+    let out = temp_path("merged.xml");
+    let output = run(&[
+        "merge",
+        FDSN,
+        other.to_str().unwrap(),
+        "-o",
+        out.to_str().unwrap(),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    // Only the shared network header can conflict
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        stderr.lines().collect::<Vec<_>>(),
+        [format!("stationxml: {}: replaces XX", other.display())],
+    );
+
+    let merged = read_from_file_as::<Fdsn>(&out).unwrap();
+    let stations: Vec<&str> = merged.networks[0]
+        .stations
+        .iter()
+        .map(|sta| sta.code.as_str())
+        .collect();
+    assert_eq!(stations, ["PBUMI", "OTHER"]);
+    std::fs::remove_file(other).unwrap();
+    std::fs::remove_file(out).unwrap();
+}
+
+#[test]
+fn lenient_skips_broken_channels() {
+    let broken = temp_path("lenient.xml");
+    let xml = std::fs::read_to_string(FDSN).unwrap();
+    let (head, tail) = xml.split_at(xml.find(r#"code="SHN""#).unwrap());
+    let xml = format!(
+        "{head}{}",
+        tail.replacen("<Latitude>-7.7714</Latitude>", "", 1)
+    );
+    std::fs::write(&broken, xml).unwrap();
+
+    let output = run(&["info", broken.to_str().unwrap()]);
+    assert!(!output.status.success());
+
+    let output = run(&["info", "--lenient", broken.to_str().unwrap()]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(stdout(&output).contains("1 networks, 1 stations, 2 channels"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("skipped XX.PBUMI.00.SHN"), "{stderr}");
+    std::fs::remove_file(broken).unwrap();
+}
+
+#[test]
+fn open_end_after_reads_sentinel_end_dates_as_open() {
+    let input = temp_path("sentinel.xml");
+    let xml = std::fs::read_to_string(FDSN).unwrap().replacen(
+        r#"<Network code="XX""#,
+        r#"<Network code="XX" endDate="2599-12-31T23:59:59""#,
+        1,
+    );
+    std::fs::write(&input, xml).unwrap();
+    let path = input.to_str().unwrap();
+
+    let output = run(&["convert", path, "--to", "json"]);
+    assert!(output.status.success(), "{output:?}");
+    let inv = Json::read_from_str(&stdout(&output)).unwrap();
+    assert!(inv.networks[0].end_date.is_some());
+
+    let output = run(&[
+        "convert",
+        path,
+        "--to",
+        "json",
+        "--open-end-after",
+        "2500-01-01",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    let inv = Json::read_from_str(&stdout(&output)).unwrap();
+    assert_eq!(inv.networks[0].end_date, None);
+
+    let output = run(&["info", path, "--open-end-after", "soon"]);
+    assert_eq!(output.status.code(), Some(2));
+    std::fs::remove_file(input).unwrap();
+}
+
+#[test]
+fn convert_to_json_and_back() {
+    let json = temp_path("inventory.json");
+    let output = run(&[
+        "convert",
+        SC3ML,
+        "--to",
+        "json",
+        "-o",
+        json.to_str().unwrap(),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        read_from_file_as::<Json>(&json).unwrap(),
+        read_from_file(SC3ML).unwrap()
+    );
+
+    let output = run(&["info", json.to_str().unwrap()]);
+    assert!(stdout(&output).contains(": JSON\n"), "{}", stdout(&output));
+    let output = run(&["convert", json.to_str().unwrap()]);
+    assert!(output.status.success());
+    assert_eq!(detect_format(&stdout(&output)), Some(Format::Fdsn));
+    std::fs::remove_file(json).unwrap();
+}
+
+#[test]
+fn convert_to_geojson() {
+    let output = run(&["convert", FDSN, "--to", "geojson"]);
+    assert!(output.status.success(), "{output:?}");
+    let map: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(map["type"], "FeatureCollection");
+    assert_eq!(map["features"][0]["id"], "XX.PBUMI/2024-06-01T00:00:00Z");
+    assert_eq!(
+        map["features"][0]["geometry"]["coordinates"],
+        serde_json::json!([110.3776, -7.7714, 150.0])
+    );
+
+    let output = run(&["convert", FDSN, "--to", "geojson", "--geojson-channels"]);
+    let map: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(map["features"].as_array().unwrap().len(), 3);
+    assert_eq!(
+        map["features"][2]["id"],
+        "XX.PBUMI.00.SHE/2024-06-01T00:00:00Z"
+    );
+}
+
+#[test]
+fn schema_prints_json_schema() {
+    let output = run(&["schema"]);
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema, Json::schema());
+}
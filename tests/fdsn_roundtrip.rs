@@ -11,6 +11,9 @@ fn read_fdsn_sample() {
     assert_eq!(inv.source, "Pena Bumi");
     assert_eq!(inv.sender.as_deref(), Some("stationxml-rs"));
     assert!(inv.created.is_some());
+    assert_eq!(inv.module.as_deref(), Some("SeisComP fdsnxml"));
+    assert_eq!(inv.module_uri.as_deref(), Some("https://www.seiscomp.de"));
+    assert_eq!(inv.schema_version.as_deref(), Some("1.2"));
 
     // Network
     assert_eq!(inv.networks.len(), 1);
@@ -18,16 +21,39 @@ fn read_fdsn_sample() {
     assert_eq!(net.code, "XX");
     assert_eq!(net.description.as_deref(), Some("Local Test Network"));
     assert!(net.start_date.is_some());
+    assert_eq!(net.restricted_status, Some(RestrictedStatus::Open));
 
     // Station
     assert_eq!(net.stations.len(), 1);
     let sta = &net.stations[0];
     assert_eq!(sta.code, "PBUMI");
     assert!((sta.latitude - (-7.7714)).abs() < 1e-6);
+    let lat_unc = sta.latitude_uncertainty.as_ref().unwrap();
+    assert_eq!(lat_unc.datum.as_deref(), Some("WGS84"));
+    assert_eq!(lat_unc.plus_error, Some(0.0001));
     assert!((sta.longitude - 110.3776).abs() < 1e-6);
     assert!((sta.elevation - 150.0).abs() < 1e-6);
     assert_eq!(sta.site.name, "Yogyakarta Seismic Shelter");
     assert_eq!(sta.site.country.as_deref(), Some("Indonesia"));
+    assert_eq!(
+        sta.description.as_deref(),
+        Some("Pena Bumi broadband test station")
+    );
+    assert_eq!(sta.vault.as_deref(), Some("Surface vault"));
+    assert_eq!(sta.comments.len(), 1);
+    assert_eq!(sta.comments[0].value, "Vault resealed after flooding");
+    assert_eq!(sta.comments[0].id.as_deref(), Some("1"));
+    assert_eq!(sta.comments[0].subject.as_deref(), Some("Maintenance"));
+    assert_eq!(sta.comments[0].author.as_deref(), Some("Field Team"));
+    assert!(sta.comments[0].begin_effective_time.is_some());
+    assert_eq!(sta.geology.as_deref(), Some("Volcanic tuff"));
+    assert_eq!(sta.equipment.len(), 1);
+    assert_eq!(sta.equipment[0].model.as_deref(), Some("u-blox M8T"));
+    assert_eq!(sta.external_references.len(), 1);
+    assert_eq!(
+        sta.external_references[0].uri,
+        "https://example.org/stations/PBUMI"
+    );
 
     // Channels
     assert_eq!(sta.channels.len(), 3);
@@ -38,6 +64,11 @@ fn read_fdsn_sample() {
     assert_eq!(shz.location_code, "00");
     assert!((shz.dip - (-90.0)).abs() < 1e-6);
     assert!((shz.sample_rate - 100.0).abs() < 1e-6);
+    assert_eq!(
+        shz.channel_types,
+        vec![ChannelType::Continuous, ChannelType::Geophysical]
+    );
+    assert_eq!(shz.clock_drift, Some(0.0001));
 
     // Sensor equipment
     let sensor = shz.sensor.as_ref().unwrap();
@@ -45,6 +76,10 @@ fn read_fdsn_sample() {
     assert_eq!(sensor.manufacturer.as_deref(), Some("Geospace"));
     assert_eq!(sensor.model.as_deref(), Some("GS-11D"));
     assert_eq!(sensor.serial_number.as_deref(), Some("1234"));
+    assert_eq!(sensor.resource_id.as_deref(), Some("GENERATOR:Sensor/1234"));
+    assert!(sensor.installation_date.is_some());
+    assert!(sensor.removal_date.is_none());
+    assert_eq!(sensor.calibration_dates.len(), 2);
 
     // Data logger
     let dl = shz.data_logger.as_ref().unwrap();
@@ -69,6 +104,11 @@ fn read_fdsn_sample() {
     assert_eq!(pz.poles.len(), 2);
     assert!((pz.poles[0].real - (-22.2111)).abs() < 1e-4);
     assert!((pz.poles[0].imaginary - 22.2111).abs() < 1e-4);
+    assert_eq!(
+        pz.poles[0].real_uncertainty.as_ref().unwrap().plus_error,
+        Some(0.05)
+    );
+    assert!(pz.poles[0].imaginary_uncertainty.is_none());
     let gain1 = stage1.stage_gain.as_ref().unwrap();
     assert!((gain1.value - 32.0).abs() < 1e-6);
 
@@ -186,6 +226,110 @@ fn write_contains_namespace() {
     assert!(xml_out.contains(r#"schemaVersion="1.2""#));
 }
 
+#[test]
+fn write_preserves_module() {
+    let inv = read_from_str(SAMPLE_XML).unwrap();
+    let xml_out = write_to_string::<Fdsn>(&inv).unwrap();
+    assert!(xml_out.contains("<Module>SeisComP fdsnxml</Module>"));
+    assert!(xml_out.contains("<ModuleURI>https://www.seiscomp.de</ModuleURI>"));
+}
+
+#[test]
+fn write_options_override_module() {
+    let inv = read_from_str(SAMPLE_XML).unwrap();
+    let opts = FdsnWriteOptions::default()
+        .module("stationxml-rs")
+        .module_uri("https://github.com/luhtfiimanal/stationxml-rs");
+    let xml_out = Fdsn::write_to_string_with(&inv, &opts).unwrap();
+    let inv2 = read_from_str(&xml_out).unwrap();
+    assert_eq!(inv2.module.as_deref(), Some("stationxml-rs"));
+    assert_eq!(
+        inv2.module_uri.as_deref(),
+        Some("https://github.com/luhtfiimanal/stationxml-rs")
+    );
+}
+
+#[test]
+fn write_schema_1_1() {
+    let mut inv = read_from_str(SAMPLE_XML).unwrap();
+    inv.networks[0].stations[0].channels[0].storage_format = Some("Steim2".into());
+    let opts = FdsnWriteOptions::default().version(FdsnVersion::V1_1);
+    let xml_out = Fdsn::write_to_string_with(&inv, &opts).unwrap();
+    assert!(xml_out.contains(r#"schemaVersion="1.1""#));
+    assert!(!xml_out.contains("<StorageFormat>"));
+    assert!(xml_out.contains(r#"subject="Maintenance""#));
+
+    let inv2 = read_from_str(&xml_out).unwrap();
+    assert_eq!(inv2.schema_version.as_deref(), Some("1.1"));
+    inv.networks[0].stations[0].channels[0].storage_format = None;
+    assert_eq!(inv2.networks, inv.networks);
+
+    // CreationDate is optional from 1.1 on
+    inv.networks[0].stations[0].creation_date = None;
+    let xml_out = Fdsn::write_to_string_with(&inv, &opts).unwrap();
+    assert!(!xml_out.contains("<CreationDate>"));
+}
+
+#[test]
+fn write_schema_1_0_keeps_storage_format() {
+    let mut inv = read_from_str(SAMPLE_XML).unwrap();
+    inv.networks[0].stations[0].channels[0].storage_format = Some("Steim2".into());
+    let opts = FdsnWriteOptions::default().version(FdsnVersion::V1_0);
+    let xml_out = Fdsn::write_to_string_with(&inv, &opts).unwrap();
+    assert!(xml_out.contains("<StorageFormat>Steim2</StorageFormat>"));
+}
+
+#[test]
+fn write_schema_1_0_drops_newer_elements() {
+    let mut inv = read_from_str(SAMPLE_XML).unwrap();
+    inv.networks[0].stations[0].channels[0].water_level = Some(3.5);
+    let opts = FdsnWriteOptions::default().version(FdsnVersion::V1_0);
+    let xml_out = Fdsn::write_to_string_with(&inv, &opts).unwrap();
+    assert!(xml_out.contains(r#"schemaVersion="1.0""#));
+    assert!(!xml_out.contains("<WaterLevel>"));
+}
+
+#[test]
+fn write_schema_1_0_keeps_documents_valid() {
+    let mut inv = read_from_str(SAMPLE_XML).unwrap();
+    let sta = &mut inv.networks[0].stations[0];
+    let gps = Equipment {
+        equipment_type: Some("GPS".into()),
+        ..Default::default()
+    };
+    sta.equipment = vec![gps.clone(), gps];
+    sta.creation_date = None;
+    sta.start_date = None;
+    sta.channels[1].sample_rate = 0.0;
+    let earliest = sta.channels.iter().filter_map(|ch| ch.start_date).min();
+
+    let opts = FdsnWriteOptions::default().version(FdsnVersion::V1_0);
+    let xml_out = Fdsn::write_to_string_with(&inv, &opts).unwrap();
+    assert!(!xml_out.contains("<SampleRate>0</SampleRate>"), "{xml_out}");
+    // Comment subjects are 1.1 and later
+    assert!(SAMPLE_XML.contains(r#"subject="Maintenance""#));
+    assert!(!xml_out.contains("subject="), "{xml_out}");
+
+    let inv2 = read_from_str(&xml_out).unwrap();
+    let sta2 = &inv2.networks[0].stations[0];
+    assert_eq!(sta2.equipment.len(), 1);
+    assert!(earliest.is_some());
+    assert_eq!(sta2.creation_date, earliest);
+    assert_eq!(sta2.channels[1].sample_rate, 0.0);
+
+    inv.networks[0].stations[0].channels.clear();
+    let xml_out = Fdsn::write_to_string_with(&inv, &opts).unwrap();
+    assert!(xml_out.contains("<CreationDate>1970-01-01T00:00:00Z</CreationDate>"));
+}
+
+#[test]
+fn write_schema_1_2_drops_storage_format() {
+    let mut inv = read_from_str(SAMPLE_XML).unwrap();
+    inv.networks[0].stations[0].channels[0].storage_format = Some("Steim2".into());
+    let xml_out = write_to_string::<Fdsn>(&inv).unwrap();
+    assert!(!xml_out.contains("<StorageFormat>"));
+}
+
 #[test]
 fn read_from_file_works() {
     let inv = read_from_file("tests/fixtures/fdsn_sample.xml").unwrap();
@@ -198,3 +342,354 @@ fn read_from_file_as_fdsn() {
     let inv = read_from_file_as::<Fdsn>("tests/fixtures/fdsn_sample.xml").unwrap();
     assert_eq!(inv.source, "Pena Bumi");
 }
+
+#[test]
+fn write_to_writer_matches_string() {
+    let inv = read_from_str(SAMPLE_XML).unwrap();
+    let opts = FdsnWriteOptions::default().version(FdsnVersion::V1_1);
+    let mut out = Vec::new();
+    Fdsn::write_to_writer_with(&inv, &mut out, &opts).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        Fdsn::write_to_string_with(&inv, &opts).unwrap()
+    );
+}
+
+#[test]
+fn write_pretty_roundtrip() {
+    let inv = read_from_str(SAMPLE_XML).unwrap();
+    let style = XmlStyle::pretty().attributes(AttributeOrder::Alphabetical);
+    let xml_out =
+        Fdsn::write_to_string_with(&inv, &FdsnWriteOptions::default().style(style)).unwrap();
+    assert!(xml_out.contains("\n  <Network code=\"XX\""));
+    assert!(xml_out.contains("\n    <Station code=\"PBUMI\""));
+    assert_eq!(read_from_str(&xml_out).unwrap(), inv);
+}
+
+#[test]
+fn write_fixed_precision_floats() {
+    let inv = read_from_str(SAMPLE_XML).unwrap();
+    let style = XmlStyle::default().floats(FloatFormat::Fixed(4));
+    let xml_out =
+        Fdsn::write_to_string_with(&inv, &FdsnWriteOptions::default().style(style)).unwrap();
+    assert!(
+        xml_out.contains("<Latitude>-7.7714</Latitude>"),
+        "{xml_out}"
+    );
+    assert!(xml_out.contains("<Elevation>150.0000</Elevation>"));
+}
+
+#[test]
+fn fixed_precision_keeps_sample_rate_and_clock_drift() {
+    let mut inv = read_from_str(SAMPLE_XML).unwrap();
+    let ch = &mut inv.networks[0].stations[0].channels[0];
+    ch.sample_rate = 0.01;
+    ch.clock_drift = Some(1e-4);
+    for digits in [1, 3] {
+        let style = XmlStyle::default().floats(FloatFormat::Fixed(digits));
+        let xml_out =
+            Fdsn::write_to_string_with(&inv, &FdsnWriteOptions::default().style(style)).unwrap();
+        assert!(
+            xml_out.contains("<SampleRate>0.01</SampleRate>"),
+            "{xml_out}"
+        );
+        assert!(xml_out.contains(">0.0001</ClockDrift>"), "{xml_out}");
+    }
+}
+
+#[test]
+fn conversion_error_reports_stage_and_line() {
+    let xml = SAMPLE_XML.replace("LAPLACE (RADIANS/SECOND)", "LAPLACE (FURLONGS/FORTNIGHT)");
+    let err = read_from_str(&xml).unwrap_err();
+    assert_eq!(err.path(), Some("XX.PBUMI.00.SHZ stage 1"));
+    assert_eq!(err.location().map(|l| l.line), Some(74));
+    assert!(matches!(err.root_cause(), StationXmlError::InvalidData(_)));
+    assert!(
+        err.to_string()
+            .starts_with("XX.PBUMI.00.SHZ stage 1 (line 74, column 11): invalid data"),
+        "{err}"
+    );
+}
+
+#[test]
+fn conversion_error_reports_the_failing_epoch() {
+    let channel = |start: &str| {
+        format!(
+            r#"<Channel code="HHZ" locationCode="00" startDate="{start}"><Latitude>0</Latitude><Longitude>0</Longitude><Elevation>0</Elevation><Depth>0</Depth><Azimuth>0</Azimuth><Dip>-90</Dip></Channel>"#
+        )
+    };
+    let xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<FDSNStationXML xmlns="http://www.fdsn.org/xml/station/1" schemaVersion="1.2">
+  <Source>Test</Source><Created>2024-01-01T00:00:00Z</Created>
+  <Network code="XX">
+    <Station code="A"><Latitude>0</Latitude><Longitude>0</Longitude><Elevation>0</Elevation><Site><Name>A</Name></Site>
+      {}
+      {}
+    </Station>
+  </Network>
+</FDSNStationXML>"#,
+        channel("2020-01-01T00:00:00Z"),
+        channel("2021-13-01T00:00:00Z"),
+    );
+    let err = read_from_str(&xml).unwrap_err();
+    assert_eq!(err.path(), Some("XX.A.00.HHZ"));
+    assert_eq!(err.location().map(|l| l.line), Some(7), "{err}");
+}
+
+#[test]
+fn deserialize_error_reports_channel_and_line() {
+    // Drop the second channel's required Latitude
+    let (head, tail) = SAMPLE_XML.split_at(SAMPLE_XML.find(r#"code="SHN""#).unwrap());
+    let xml = format!(
+        "{head}{}",
+        tail.replacen("<Latitude>-7.7714</Latitude>", "", 1)
+    );
+    let err = read_from_str(&xml).unwrap_err();
+    assert_eq!(err.path(), Some("XX.PBUMI.00.SHN"));
+    assert!(matches!(err.root_cause(), StationXmlError::XmlParse(_)));
+    let line = err.location().unwrap().line;
+    assert!((136..=150).contains(&line), "{err}");
+}
+
+#[test]
+fn syntax_error_reports_position() {
+    let xml = SAMPLE_XML.replace("</Site>", "</Sight>");
+    let err = read_from_str(&xml).unwrap_err();
+    let location = err.location().unwrap();
+    assert_eq!(&xml[location.offset..location.offset + 8], "</Sight>");
+    assert_eq!(err.path(), Some("XX.PBUMI"));
+}
+
+// ─── Lenient reading ────────────────────────────────────────────────
+
+fn read_lenient(xml: &str) -> ReadOutcome {
+    Fdsn::read_from_str_with(xml, &ReadOptions::default().lenient(true)).unwrap()
+}
+
+#[test]
+fn lenient_keeps_items_with_bad_optional_dates() {
+    let xml = SAMPLE_XML.replacen(
+        r#"<Channel code="SHN" locationCode="00""#,
+        r#"<Channel code="SHN" locationCode="00" endDate="2024-13-01""#,
+        1,
+    );
+    assert!(read_from_str(&xml).is_err());
+
+    let outcome = read_lenient(&xml);
+    assert_eq!(outcome.warnings.len(), 1);
+    let warning = &outcome.warnings[0];
+    assert_eq!(warning.path(), Some("XX.PBUMI.00.SHN"));
+    assert_eq!(warning.location().map(|l| l.line), Some(136));
+    let original = read_from_str(SAMPLE_XML).unwrap();
+    assert_eq!(outcome.inventory, original);
+}
+
+#[test]
+fn lenient_drops_response_with_bad_stage() {
+    let xml = SAMPLE_XML.replace("LAPLACE (RADIANS/SECOND)", "LAPLACE (FURLONGS/FORTNIGHT)");
+    assert!(read_from_str(&xml).is_err());
+
+    // Keeping the other stages and the sensitivity would give a wrong response
+    let outcome = read_lenient(&xml);
+    assert_eq!(outcome.warnings.len(), 1);
+    let warning = &outcome.warnings[0];
+    assert_eq!(warning.path(), Some("XX.PBUMI.00.SHZ stage 1"));
+    // Located at the dropped Response element
+    assert_eq!(warning.location().map(|l| l.line), Some(61));
+
+    let original = read_from_str(SAMPLE_XML).unwrap();
+    let channels = &outcome.inventory.networks[0].stations[0].channels;
+    assert_eq!(channels.len(), 3);
+    assert!(channels[0].response.is_none());
+    let expected = Channel {
+        response: None,
+        ..original.networks[0].stations[0].channels[0].clone()
+    };
+    assert_eq!(channels[0], expected);
+    assert_eq!(
+        channels[1..],
+        original.networks[0].stations[0].channels[1..]
+    );
+}
+
+#[test]
+fn lenient_drops_response_with_incomplete_stage() {
+    // Stage 2 of SHZ loses its required StageGain value
+    let (head, tail) = SAMPLE_XML.split_at(SAMPLE_XML.find(r#"<Stage number="2">"#).unwrap());
+    let xml = format!(
+        "{head}{}",
+        tail.replacen("<Value>", "<Unknown>", 1)
+            .replacen("</Value>", "</Unknown>", 1)
+    );
+
+    let outcome = read_lenient(&xml);
+    assert_eq!(outcome.warnings.len(), 1, "{:?}", outcome.warnings);
+    let channels = &outcome.inventory.networks[0].stations[0].channels;
+    assert_eq!(channels.len(), 3);
+    assert!(channels[0].response.is_none());
+    assert!(channels[0].sensor.is_some());
+}
+
+#[test]
+fn lenient_skips_incomplete_channel_and_keeps_extensions_aligned() {
+    // SHN loses its Latitude, SHE carries a foreign element
+    let (head, tail) = SAMPLE_XML.split_at(SAMPLE_XML.find(r#"code="SHN""#).unwrap());
+    let xml = format!(
+        "{head}{}",
+        tail.replacen("<Latitude>-7.7714</Latitude>", "", 1)
+    )
+    .replace(
+        r#"<FDSNStationXML xmlns="http://www.fdsn.org/xml/station/1""#,
+        r#"<FDSNStationXML xmlns="http://www.fdsn.org/xml/station/1" xmlns:qc="https://example.org/qc""#,
+    )
+    .replace(
+        r#"<Channel code="SHE" locationCode="00" startDate="2024-06-01T00:00:00Z">"#,
+        r#"<Channel code="SHE" locationCode="00" startDate="2024-06-01T00:00:00Z"><qc:Checked/>"#,
+    );
+
+    let outcome = read_lenient(&xml);
+    assert_eq!(outcome.warnings.len(), 1);
+    assert_eq!(outcome.warnings[0].path(), Some("XX.PBUMI.00.SHN"));
+    let channels = &outcome.inventory.networks[0].stations[0].channels;
+    let codes: Vec<&str> = channels.iter().map(|ch| ch.code.as_str()).collect();
+    assert_eq!(codes, ["SHZ", "SHE"]);
+    assert!(channels[0].extensions.is_empty());
+    assert_eq!(channels[1].extensions.elements[0].xml, "<qc:Checked/>");
+}
+
+#[test]
+fn lenient_locates_the_failing_epoch_of_repeated_channels() {
+    // Two SHZ epochs; only the second has an unparsable end date
+    let start = SAMPLE_XML.find(r#"<Channel code="SHZ""#).unwrap();
+    let end = SAMPLE_XML.find(r#"<Channel code="SHN""#).unwrap();
+    let epoch = &SAMPLE_XML[start..end];
+    let broken = epoch.replace(
+        r#"startDate="2024-06-01T00:00:00Z""#,
+        r#"startDate="2025-01-01T00:00:00Z" endDate="someday""#,
+    );
+    let xml = format!("{}{broken}{}", &SAMPLE_XML[..end], &SAMPLE_XML[end..]);
+
+    let outcome = read_lenient(&xml);
+    assert_eq!(outcome.warnings.len(), 1);
+    let line = outcome.warnings[0].location().unwrap().line;
+    assert!(xml.lines().nth(line - 1).unwrap().contains("someday"));
+    // The epoch is kept without its end date
+    let channels = &outcome.inventory.networks[0].stations[0].channels;
+    assert_eq!(channels.len(), 4);
+    assert_eq!(
+        channels[0].start_date,
+        read_from_str(SAMPLE_XML).unwrap().networks[0].stations[0].channels[0].start_date
+    );
+    assert_eq!(
+        channels[1].start_date.unwrap().to_string(),
+        "2025-01-01 00:00:00 UTC"
+    );
+    assert_eq!(channels[1].end_date, None);
+}
+
+#[test]
+fn lenient_gives_up_after_too_many_rereads() {
+    // Every channel lacks its Latitude; each costs one more reading
+    let document = |broken: usize| {
+        let channels: String = (0..broken)
+            .map(|i| format!(r#"<Channel code="C{i:02}" locationCode=""><Longitude>0</Longitude><Elevation>0</Elevation><Depth>0</Depth><Azimuth>0</Azimuth><Dip>0</Dip></Channel>"#))
+            .collect();
+        format!(
+            r#"<FDSNStationXML xmlns="http://www.fdsn.org/xml/station/1" schemaVersion="1.2"><Source>Test</Source><Created>2024-01-01T00:00:00Z</Created><Network code="XX"><Station code="A"><Latitude>0</Latitude><Longitude>0</Longitude><Elevation>0</Elevation><Site><Name>A</Name></Site>{channels}</Station></Network></FDSNStationXML>"#
+        )
+    };
+    let outcome = read_lenient(&document(100));
+    assert_eq!(outcome.warnings.len(), 100);
+    assert!(
+        outcome.inventory.networks[0].stations[0]
+            .channels
+            .is_empty()
+    );
+
+    let result = Fdsn::read_from_str_with(&document(101), &ReadOptions::default().lenient(true));
+    assert!(result.is_err());
+}
+
+#[test]
+fn lenient_still_rejects_malformed_xml() {
+    let xml = SAMPLE_XML.replace("</Site>", "</Sight>");
+    let result = Fdsn::read_from_str_with(&xml, &ReadOptions::default().lenient(true));
+    assert!(result.is_err());
+}
+
+fn sub_second_epochs() -> Inventory {
+    let time = |s: &str| s.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+    Inventory::builder()
+        .source("Test")
+        .network("XX", |net| {
+            net.station("PBUMI", |sta| {
+                sta.channel("SHZ", "00", |ch| {
+                    ch.sample_rate(100.0)
+                        .start_date(time("2020-01-01T00:00:00.5Z"))
+                        .end_date(time("2020-01-01T00:00:00.75Z"))
+                })
+                .channel("SHZ", "00", |ch| {
+                    ch.sample_rate(100.0)
+                        .start_date(time("2020-01-01T00:00:00.75Z"))
+                })
+            })
+        })
+        .build()
+}
+
+#[test]
+fn roundtrip_keeps_fractional_seconds() {
+    let inv = sub_second_epochs();
+    let xml = Fdsn::write_to_string(&inv).unwrap();
+    assert!(
+        xml.contains(r#"startDate="2020-01-01T00:00:00.500Z""#),
+        "{xml}"
+    );
+    let back = Fdsn::read_from_str(&xml).unwrap();
+    assert_eq!(back.networks, inv.networks);
+
+    let style = XmlStyle::default().times(TimeFormat::Microseconds);
+    let xml = Fdsn::write_to_string_with(&inv, &FdsnWriteOptions::default().style(style)).unwrap();
+    assert!(
+        xml.contains(r#"endDate="2020-01-01T00:00:00.750000Z""#),
+        "{xml}"
+    );
+    assert_eq!(Fdsn::read_from_str(&xml).unwrap().networks, inv.networks);
+}
+
+#[test]
+fn sentinel_end_dates_read_as_open_and_written_back() {
+    let time = |s: &str| s.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+    let xml = SAMPLE_XML
+        .replacen(
+            r#"<Channel code="SHZ" locationCode="00" startDate="2024-06-01T00:00:00Z""#,
+            r#"<Channel code="SHZ" locationCode="00" startDate="2024-06-01" endDate="3000-01-01""#,
+            1,
+        )
+        .replacen(
+            r#"<Channel code="SHN" locationCode="00" startDate="2024-06-01T00:00:00Z""#,
+            r#"<Channel code="SHN" locationCode="00" startDate="2024-06-01T00:00:00" endDate="2599-12-31T23:59:59""#,
+            1,
+        );
+    let plain = Fdsn::read_from_str(&xml).unwrap();
+    let channels = &plain.networks[0].stations[0].channels;
+    assert_eq!(channels[0].start_date, Some(time("2024-06-01T00:00:00Z")));
+    assert_eq!(channels[0].end_date, Some(time("3000-01-01T00:00:00Z")));
+
+    let opts = ReadOptions::default().open_end_after(time("2500-01-01T00:00:00Z"));
+    let inv = Fdsn::read_from_str_with(&xml, &opts).unwrap().inventory;
+    let channels = &inv.networks[0].stations[0].channels;
+    assert!(channels.iter().all(|ch| ch.end_date.is_none()));
+    assert_eq!(channels[1].start_date, channels[0].start_date);
+    assert_eq!(inv.networks, read_from_str(SAMPLE_XML).unwrap().networks);
+
+    let sentinel = time("2599-12-31T23:59:59Z");
+    let written =
+        Fdsn::write_to_string_with(&inv, &FdsnWriteOptions::default().open_end(sentinel)).unwrap();
+    let back = Fdsn::read_from_str(&written).unwrap();
+    let sta = &back.networks[0].stations[0];
+    assert_eq!(back.networks[0].end_date, Some(sentinel));
+    assert_eq!(sta.end_date, Some(sentinel));
+    assert!(sta.channels.iter().all(|ch| ch.end_date == Some(sentinel)));
+}
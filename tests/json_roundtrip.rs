@@ -0,0 +1,61 @@
+//! Integration tests for the JSON backend.
+
+use stationxml_rs::*;
+
+const FDSN_SAMPLE: &str = include_str!("fixtures/fdsn_sample.xml");
+const SC3ML_SAMPLE: &str = include_str!("fixtures/sc3ml_sample.xml");
+
+#[test]
+fn roundtrip_fdsn_sample() {
+    let inv = read_from_str(FDSN_SAMPLE).unwrap();
+    let json = write_to_string::<Json>(&inv).unwrap();
+    assert_eq!(detect_format(&json), Some(Format::Json));
+    assert_eq!(read_from_str(&json).unwrap(), inv);
+}
+
+#[test]
+fn roundtrip_sc3ml_sample() {
+    let inv = read_from_str(SC3ML_SAMPLE).unwrap();
+    let json = Json::write_to_string_with(&inv, &JsonWriteOptions::default().pretty(true)).unwrap();
+    assert!(json.contains("\n  \"schema_version\": \"1.0\""), "{json}");
+    assert_eq!(Json::read_from_str(&json).unwrap(), inv);
+
+    // And on to XML: nothing is lost on the way through JSON
+    let xml = write_to_string::<Sc3ml>(&Json::read_from_str(&json).unwrap()).unwrap();
+    assert_eq!(xml, write_to_string::<Sc3ml>(&inv).unwrap());
+}
+
+#[test]
+fn read_json_file_as() {
+    let inv = read_from_str(FDSN_SAMPLE).unwrap();
+    let path = std::env::temp_dir().join(format!("stationxml-{}.json", std::process::id()));
+    write_to_file::<Json>(&path, &inv).unwrap();
+    assert_eq!(read_from_file_as::<Json>(&path).unwrap(), inv);
+    assert_eq!(read_from_file(&path).unwrap(), inv);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn documented_field_names() {
+    let inv = read_from_str(FDSN_SAMPLE).unwrap();
+    let value: serde_json::Value =
+        serde_json::from_str(&write_to_string::<Json>(&inv).unwrap()).unwrap();
+    assert_eq!(value["schema"], JSON_SCHEMA_ID);
+    let channel = &value["inventory"]["networks"][0]["stations"][0]["channels"][0];
+    assert_eq!(channel["code"], "SHZ");
+    assert_eq!(channel["sample_rate"], 100.0);
+    assert_eq!(channel["start_date"], "2024-06-01T00:00:00Z");
+    assert_eq!(
+        channel["response"]["stages"][0]["poles_zeros"]["pz_transfer_function_type"],
+        "LaplaceRadians"
+    );
+}
+
+#[test]
+fn json_is_not_mistaken_for_xml() {
+    let err = read_from_str(r#"{"schema": "something/else"}"#).unwrap_err();
+    assert!(
+        err.to_string().contains("not a stationxml-rs/inventory"),
+        "{err}"
+    );
+}
@@ -17,6 +17,14 @@ fn read_sc3ml_sample() {
     assert_eq!(net.code, "XX");
     assert_eq!(net.description.as_deref(), Some("Local Test Network"));
     assert!(net.start_date.is_some());
+    assert_eq!(net.network_type.as_deref(), Some("BB"));
+    assert_eq!(net.network_class.as_deref(), Some("p"));
+    assert_eq!(net.archive.as_deref(), Some("PB"));
+    assert_eq!(net.restricted_status, Some(RestrictedStatus::Open));
+    assert_eq!(net.shared, Some(true));
+    assert_eq!(net.comments.len(), 1);
+    assert_eq!(net.comments[0].id.as_deref(), Some("ops"));
+    assert_eq!(net.comments[0].author.as_deref(), Some("sysop"));
 
     // Station
     assert_eq!(net.stations.len(), 1);
@@ -25,13 +33,26 @@ fn read_sc3ml_sample() {
     assert!((sta.latitude - (-7.7714)).abs() < 1e-6);
     assert!((sta.longitude - 110.3776).abs() < 1e-6);
     assert!((sta.elevation - 150.0).abs() < 1e-6);
+    assert_eq!(sta.site.name, "Yogyakarta Seismic Shelter");
+    assert_eq!(sta.site.town.as_deref(), Some("Yogyakarta"));
     assert_eq!(sta.site.country.as_deref(), Some("Indonesia"));
+    assert_eq!(sta.affiliation.as_deref(), Some("Pena Bumi"));
+    assert_eq!(sta.station_type.as_deref(), Some("surface"));
+    assert_eq!(sta.archive_network_code.as_deref(), Some("XX"));
+    assert_eq!(sta.restricted_status, Some(RestrictedStatus::Closed));
+    assert_eq!(sta.shared, Some(false));
 
     // Channels
     assert_eq!(sta.channels.len(), 3);
 
     // SHZ channel
     let shz = &sta.channels[0];
+    assert_eq!(shz.storage_format.as_deref(), Some("Steim2"));
+    assert_eq!(
+        shz.channel_types,
+        vec![ChannelType::Geophysical, ChannelType::Continuous]
+    );
+    assert_eq!(shz.shared, Some(true));
     assert_eq!(shz.code, "SHZ");
     assert_eq!(shz.location_code, "00");
     assert!((shz.dip - (-90.0)).abs() < 1e-6);
@@ -117,6 +138,43 @@ fn read_sc3ml_fir_stage() {
 
 // ─── Roundtrip tests ─────────────────────────────────────────────────
 
+#[test]
+fn fixed_float_style_keeps_responses_lossless() {
+    let mut inv = read_from_str(SC3ML_SAMPLE).unwrap();
+    let resp = inv.networks[0].stations[0].channels[0]
+        .response
+        .as_mut()
+        .unwrap();
+    for stage in &mut resp.stages {
+        if let Some(fir) = &mut stage.fir {
+            fir.numerator_coefficients = vec![1e-7, -2.5e-9, 0.4, 0.123456789];
+        }
+        if let Some(pz) = &mut stage.poles_zeros {
+            pz.poles[0].real = -0.0370184;
+        }
+    }
+    let style = XmlStyle::default().floats(FloatFormat::Fixed(4));
+
+    let xml = Sc3ml::write_to_string_with(&inv, &Sc3mlWriteOptions::default().style(style.clone()))
+        .unwrap();
+    assert!(xml.contains("<coefficients>0.0000001 -0.0000000025 0.4 0.123456789</coefficients>"));
+    let back = Sc3ml::read_from_str(&xml).unwrap();
+    assert_eq!(
+        back.networks[0].stations[0].channels[0].response,
+        inv.networks[0].stations[0].channels[0].response
+    );
+
+    let xml = Fdsn::write_to_string_with(&inv, &FdsnWriteOptions::default().style(style)).unwrap();
+    let shortest = write_to_string::<Fdsn>(&inv).unwrap();
+    assert_eq!(
+        Fdsn::read_from_str(&xml).unwrap().networks[0].stations[0].channels[0].response,
+        Fdsn::read_from_str(&shortest).unwrap().networks[0].stations[0].channels[0].response
+    );
+    // Coordinates still follow the style
+    let latitude = inv.networks[0].stations[0].latitude;
+    assert!(xml.contains(&format!("<Latitude>{latitude:.4}</Latitude>")));
+}
+
 #[test]
 fn roundtrip_sc3ml() {
     // Read SC3ML
@@ -177,6 +235,19 @@ fn roundtrip_sc3ml() {
 
 // ─── Cross-format tests ─────────────────────────────────────────────
 
+#[test]
+fn site_name_stands_in_for_missing_description() {
+    let mut inv = read_from_str(FDSN_SAMPLE).unwrap();
+    inv.networks[0].stations[0].description = None;
+    let xml = write_to_string::<Sc3ml>(&inv).unwrap();
+    let sta = &Sc3ml::read_from_str(&xml).unwrap().networks[0].stations[0];
+    assert_eq!(
+        sta.description.as_deref(),
+        Some("Yogyakarta Seismic Shelter")
+    );
+    assert_eq!(sta.site.name, "Yogyakarta Seismic Shelter");
+}
+
 #[test]
 fn cross_format_fdsn_to_sc3ml() {
     // Read FDSN fixture
@@ -197,6 +268,10 @@ fn cross_format_fdsn_to_sc3ml() {
     assert!((sta_fdsn.latitude - sta_sc3ml.latitude).abs() < 1e-6);
     assert!((sta_fdsn.longitude - sta_sc3ml.longitude).abs() < 1e-6);
     assert!((sta_fdsn.elevation - sta_sc3ml.elevation).abs() < 1e-6);
+    assert_eq!(sta_fdsn.description, sta_sc3ml.description);
+    // SC3ML has no separate site name: it reads back as the description
+    assert_eq!(sta_sc3ml.site.name, "Pena Bumi broadband test station");
+    assert_eq!(sta_fdsn.site.country, sta_sc3ml.site.country);
 
     // Channel count preserved
     assert_eq!(sta_fdsn.channels.len(), sta_sc3ml.channels.len());
@@ -226,6 +301,13 @@ fn cross_format_fdsn_to_sc3ml() {
         .as_ref()
         .unwrap();
     assert!((sens_f.value - sens_s.value).abs() < 0.1);
+
+    // Calibration dates travel as date-only calibration records
+    let sensor_f = sta_fdsn.channels[0].sensor.as_ref().unwrap();
+    let sensor_s = sta_sc3ml.channels[0].sensor.as_ref().unwrap();
+    assert_eq!(sensor_f.calibration_dates.len(), 2);
+    assert_eq!(sensor_s.calibration_dates, sensor_f.calibration_dates);
+    assert!(sensor_s.calibrations.is_empty());
 }
 
 #[test]
@@ -270,6 +352,59 @@ fn cross_format_sc3ml_to_fdsn() {
     assert_eq!(sens_s.input_units.name, sens_f.input_units.name);
 }
 
+#[test]
+fn cross_format_aux_stream_to_fdsn() {
+    let mut inv = read_from_str(SC3ML_SAMPLE).unwrap();
+    let mut ch = inv.networks[0].stations[0].channels[0].clone();
+    ch.code = "LDO".into();
+    ch.sensor = None;
+    ch.data_logger = None;
+    ch.response = None;
+    ch.aux_device = Some(Equipment {
+        equipment_type: Some("AuxDevice".into()),
+        model: Some("Setra 278".into()),
+        serial_number: Some("B-17".into()),
+        ..Default::default()
+    });
+    ch.aux_source = Some(AuxSource {
+        name: "pressure".into(),
+        description: Some("Barometric pressure".into()),
+        unit: Some("hPa".into()),
+        ..Default::default()
+    });
+    inv.networks[0].stations[0].channels.push(ch);
+
+    let fdsn_xml = write_to_string::<Fdsn>(&inv).unwrap();
+    assert!(
+        fdsn_xml.contains(
+            "<Description>Auxiliary source pressure: Barometric pressure (hPa)</Description>"
+        ),
+        "{fdsn_xml}"
+    );
+
+    let inv_fdsn = Fdsn::read_from_str(&fdsn_xml).unwrap();
+    let ch = inv_fdsn.networks[0].stations[0].channels.last().unwrap();
+    assert_eq!(ch.code, "LDO");
+    let device = ch.aux_device.as_ref().unwrap();
+    assert_eq!(device.equipment_type.as_deref(), Some("AuxDevice"));
+    assert_eq!(device.model.as_deref(), Some("Setra 278"));
+    assert_eq!(device.serial_number.as_deref(), Some("B-17"));
+    assert!(ch.equipment.is_empty());
+    assert_eq!(
+        ch.aux_source,
+        inv.networks[0].stations[0]
+            .channels
+            .last()
+            .unwrap()
+            .aux_source
+    );
+
+    // Back in SC3ML the channel is an auxiliary stream again
+    let sc3ml = write_to_string::<Sc3ml>(&inv_fdsn).unwrap();
+    assert!(sc3ml.contains(r#"<auxStream code="LDO""#), "{sc3ml}");
+    assert!(!sc3ml.contains(r#"<stream code="LDO""#), "{sc3ml}");
+}
+
 // ─── Auto-detect ─────────────────────────────────────────────────────
 
 #[test]
@@ -333,6 +468,230 @@ fn write_sc3ml_has_top_level_definitions() {
     );
 }
 
+#[test]
+fn roundtrip_sc3ml_preserves_network_and_station_metadata() {
+    let inv1 = read_from_str(SC3ML_SAMPLE).unwrap();
+    let xml_out = write_to_string::<Sc3ml>(&inv1).unwrap();
+    let inv2 = read_from_str(&xml_out).unwrap();
+
+    let (net1, net2) = (&inv1.networks[0], &inv2.networks[0]);
+    assert_eq!(net1.network_type, net2.network_type);
+    assert_eq!(net1.network_class, net2.network_class);
+    assert_eq!(net1.archive, net2.archive);
+    assert_eq!(net1.restricted_status, net2.restricted_status);
+    assert_eq!(net1.shared, net2.shared);
+    assert_eq!(net1.comments, net2.comments);
+
+    let (sta1, sta2) = (&net1.stations[0], &net2.stations[0]);
+    assert_eq!(sta1.affiliation, sta2.affiliation);
+    assert_eq!(sta1.station_type, sta2.station_type);
+    assert_eq!(sta1.archive, sta2.archive);
+    assert_eq!(sta1.archive_network_code, sta2.archive_network_code);
+    assert_eq!(sta1.restricted_status, sta2.restricted_status);
+    assert_eq!(sta1.shared, sta2.shared);
+
+    let (ch1, ch2) = (&sta1.channels[0], &sta2.channels[0]);
+    assert_eq!(ch1.storage_format, ch2.storage_format);
+    assert_eq!(ch1.channel_types, ch2.channel_types);
+    assert_eq!(ch1.restricted_status, ch2.restricted_status);
+    assert_eq!(ch1.shared, ch2.shared);
+}
+
+/// All `publicID="…"` values of a document, sorted.
+fn public_ids(xml: &str) -> Vec<String> {
+    let mut ids: Vec<String> = xml
+        .split(r#"publicID=""#)
+        .skip(1)
+        .map(|rest| rest[..rest.find('"').unwrap()].to_string())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn roundtrip_sc3ml_preserves_public_ids() {
+    let inv = read_from_str(SC3ML_SAMPLE).unwrap();
+    let net = &inv.networks[0];
+    assert_eq!(net.public_id.as_deref(), Some("Network/XX"));
+    assert_eq!(
+        net.stations[0].public_id.as_deref(),
+        Some("Station/XX/PBUMI")
+    );
+    let ch = &net.stations[0].channels[0];
+    assert_eq!(
+        ch.sensor_location_id.as_deref(),
+        Some("SensorLocation/XX/PBUMI/00")
+    );
+    assert_eq!(
+        ch.sensor.as_ref().unwrap().resource_id.as_deref(),
+        Some("Sensor#GS11D_Z")
+    );
+
+    let xml_out = write_to_string::<Sc3ml>(&inv).unwrap();
+    assert_eq!(public_ids(&xml_out), public_ids(SC3ML_SAMPLE));
+    assert!(xml_out.contains(r#"datalogger="Datalogger#PB24_Z""#));
+
+    // A channel added after reading gets a fresh ID; existing IDs stay put
+    let mut modified = inv.clone();
+    let sta = &mut modified.networks[0].stations[0];
+    let mut extra = sta.channels[0].clone();
+    extra.location_code = "10".into();
+    extra.sensor_location_id = None;
+    sta.channels.push(extra);
+    let ids = public_ids(&write_to_string::<Sc3ml>(&modified).unwrap());
+    for id in public_ids(SC3ML_SAMPLE) {
+        assert!(ids.contains(&id), "{id} missing from {ids:?}");
+    }
+    assert!(
+        ids.iter()
+            .any(|id| id.starts_with("SensorLocation/XX/PBUMI/10/"))
+    );
+}
+
+/// The FDSN sample without preserved equipment IDs, so the writer generates all of them.
+fn fdsn_without_resource_ids() -> Inventory {
+    let mut inv = read_from_str(FDSN_SAMPLE).unwrap();
+    for ch in &mut inv.networks[0].stations[0].channels {
+        for eq in [&mut ch.sensor, &mut ch.data_logger].into_iter().flatten() {
+            eq.resource_id = None;
+        }
+    }
+    inv
+}
+
+#[test]
+fn sequential_ids_are_default() {
+    let inv = fdsn_without_resource_ids();
+    let ids = public_ids(&write_to_string::<Sc3ml>(&inv).unwrap());
+    assert!(ids.contains(&"Network/XX".to_string()), "{ids:?}");
+    assert!(ids.iter().any(|id| id.starts_with("Sensor/GS-11D_")));
+}
+
+#[test]
+fn nslc_ids_do_not_depend_on_the_first_channel() {
+    let opts = Sc3mlWriteOptions::default().public_ids(PublicIdStrategy::Nslc);
+    let inv = fdsn_without_resource_ids();
+    let before = public_ids(&Sc3ml::write_to_string_with(&inv, &opts).unwrap());
+    assert!(before.contains(&"SensorLocation/XX/PBUMI/00/20240601T000000Z".to_string()));
+    assert!(before.iter().any(|id| id.starts_with("Sensor/GS-11D/")));
+
+    // A new channel using the same equipment, written before all others
+    let mut bigger = inv.clone();
+    let sta = &mut bigger.networks[0].stations[0];
+    let mut extra = sta.channels[0].clone();
+    extra.location_code = "10".into();
+    sta.channels.insert(0, extra);
+    let after = public_ids(&Sc3ml::write_to_string_with(&bigger, &opts).unwrap());
+    for id in &before {
+        assert!(after.contains(id), "{id} missing from {after:?}");
+    }
+    assert_eq!(after.len(), before.len() + 1, "{after:?}");
+}
+
+#[test]
+fn content_hash_ids_are_unique() {
+    let opts = Sc3mlWriteOptions::default().public_ids(PublicIdStrategy::ContentHash);
+    let inv = fdsn_without_resource_ids();
+    let xml = Sc3ml::write_to_string_with(&inv, &opts).unwrap();
+    let mut ids = public_ids(&xml);
+    let count = ids.len();
+    ids.dedup();
+    assert_eq!(ids.len(), count, "{ids:?}");
+    assert_eq!(xml, Sc3ml::write_to_string_with(&inv, &opts).unwrap());
+
+    // References resolve to the hashed definitions
+    let inv2 = Sc3ml::read_from_str(&xml).unwrap();
+    let ch = &inv2.networks[0].stations[0].channels[0];
+    assert!(ch.sensor.is_some() && ch.data_logger.is_some());
+}
+
+#[test]
+fn custom_id_strategy() {
+    let opts = Sc3mlWriteOptions::default().public_ids(PublicIdStrategy::custom(|req| {
+        format!("smi:example.org/{}/{}", req.kind.prefix(), req.nslc())
+    }));
+    let inv = fdsn_without_resource_ids();
+    let xml = Sc3ml::write_to_string_with(&inv, &opts).unwrap();
+    assert!(xml.contains(r#"publicID="smi:example.org/Network/XX""#));
+    assert!(xml.contains(r#"publicID="smi:example.org/Sensor/""#));
+}
+
+#[test]
+fn unused_datalogger_decimations_survive_roundtrip() {
+    // PB24_Z also decimates to 20 Hz, which no stream records
+    let xml = SC3ML_SAMPLE.replacen(
+        "</decimation>",
+        r#"</decimation>
+      <decimation sampleRateNumerator="20" sampleRateDenominator="1">
+        <digitalFilterChain>ResponseFIR#DECIM2</digitalFilterChain>
+      </decimation>"#,
+        1,
+    );
+    let inv = read_from_str(&xml).unwrap();
+    let dl = inv.networks[0].stations[0].channels[0]
+        .data_logger
+        .as_ref()
+        .unwrap();
+    assert_eq!(dl.decimations.len(), 1);
+    let decimation = &dl.decimations[0];
+    assert_eq!(decimation.sample_rate, 20.0);
+    assert_eq!(decimation.stages.len(), 1);
+    let stage = &decimation.stages[0];
+    assert_eq!(stage.decimation.as_ref().unwrap().input_sample_rate, 40.0);
+
+    let out = write_to_string::<Sc3ml>(&inv).unwrap();
+    assert!(out.contains(r#"<decimation sampleRateNumerator="20" sampleRateDenominator="1">"#));
+    let inv2 = read_from_str(&out).unwrap();
+    assert_eq!(
+        inv2.networks[0].stations[0].channels[0].data_logger,
+        inv.networks[0].stations[0].channels[0].data_logger
+    );
+}
+
+#[test]
+fn sensor_location_comments_roundtrip() {
+    let xml = SC3ML_SAMPLE.replace(
+        r#"<sensorLocation publicID="SensorLocation/XX/PBUMI/00" code="00">"#,
+        r#"<sensorLocation publicID="SensorLocation/XX/PBUMI/00" code="00">
+          <comment>
+            <text>Vault flooded</text>
+            <id>flood</id>
+          </comment>"#,
+    );
+    let inv = Sc3ml::read_from_str(&xml).unwrap();
+    let channels = &inv.networks[0].stations[0].channels;
+    assert!(channels.len() > 1);
+    for ch in channels {
+        assert_eq!(ch.sensor_location_comments.len(), 1);
+        assert_eq!(ch.sensor_location_comments[0].value, "Vault flooded");
+        assert!(ch.comments.iter().all(|c| c.value != "Vault flooded"));
+    }
+
+    let out = write_to_string::<Sc3ml>(&inv).unwrap();
+    assert_eq!(out.matches("Vault flooded").count(), 1, "{out}");
+    assert_eq!(Sc3ml::read_from_str(&out).unwrap().networks, inv.networks);
+
+    // FDSN has no sensor location, so each channel carries the comment
+    let fdsn = write_to_string::<Fdsn>(&inv).unwrap();
+    assert_eq!(fdsn.matches("Vault flooded").count(), channels.len());
+
+    let old = Sc3ml::write_to_string_with(
+        &inv,
+        &Sc3mlWriteOptions::default().version(Sc3mlVersion::V0_9),
+    )
+    .unwrap();
+    assert!(!old.contains("Vault flooded"));
+}
+
+#[test]
+fn write_sc3ml_09_drops_comments() {
+    let inv = read_from_str(SC3ML_SAMPLE).unwrap();
+    let opts = Sc3mlWriteOptions::default().version(Sc3mlVersion::V0_9);
+    let xml_out = Sc3ml::write_to_string_with(&inv, &opts).unwrap();
+    assert!(!xml_out.contains("<comment>"));
+    assert!(xml_out.contains("<netClass>p</netClass>"));
+}
+
 // ─── Namespace version tolerance ─────────────────────────────────────
 
 #[test]
@@ -345,4 +704,208 @@ fn read_sc3ml_version_09() {
         .replace(r#"version="0.13""#, r#"version="0.9""#);
     let inv = read_from_str(&xml).unwrap();
     assert_eq!(inv.networks[0].code, "XX");
+    assert_eq!(inv.schema_version.as_deref(), Some("0.9"));
+}
+
+#[test]
+fn write_pretty_sc3ml_reads_back_identically() {
+    let inv = read_from_str(SC3ML_SAMPLE).unwrap();
+    let compact = write_to_string::<Sc3ml>(&inv).unwrap();
+    let style = XmlStyle::pretty().attributes(AttributeOrder::Alphabetical);
+    let pretty =
+        Sc3ml::write_to_string_with(&inv, &Sc3mlWriteOptions::default().style(style)).unwrap();
+    assert!(pretty.lines().count() > 20);
+    assert!(pretty.contains("\n    <sensor "));
+    assert_eq!(
+        Sc3ml::read_from_str(&pretty).unwrap(),
+        Sc3ml::read_from_str(&compact).unwrap()
+    );
+}
+
+// ─── Error locations ─────────────────────────────────────────────────
+
+#[test]
+fn filter_error_reports_channel_stage() {
+    let xml = SC3ML_SAMPLE.replace("<symmetry>C</symmetry>", "<symmetry>X</symmetry>");
+    let err = Sc3ml::read_from_str(&xml).unwrap_err();
+    assert_eq!(err.path(), Some("XX.PBUMI.00.SHZ stage 3"));
+    // Located at the stream referencing the filter
+    let location = err.location().unwrap();
+    assert_eq!(location.line, 97);
+    assert!(xml[location.offset..].starts_with(r#"<stream code="SHZ""#));
+}
+
+#[test]
+fn conversion_error_reports_the_failing_stream_epoch() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<seiscomp xmlns="http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/0.13" version="0.13">
+  <Inventory>
+    <sensor publicID="Sensor#1"/>
+    <network publicID="Net/XX" code="XX">
+      <station publicID="Sta/XX/A" code="A">
+        <latitude>0</latitude><longitude>0</longitude><elevation>0</elevation>
+        <sensorLocation publicID="Loc/XX/A/00" code="00">
+          <stream code="HHZ"><start>2020-01-01T00:00:00Z</start></stream>
+          <stream code="HHZ"><start>2021-13-01T00:00:00Z</start></stream>
+        </sensorLocation>
+      </station>
+    </network>
+  </Inventory>
+</seiscomp>"#;
+    let err = Sc3ml::read_from_str(xml).unwrap_err();
+    assert_eq!(err.path(), Some("XX.A.00.HHZ"), "{err}");
+    assert_eq!(err.location().map(|l| l.line), Some(10), "{err}");
+}
+
+#[test]
+fn value_error_reports_station() {
+    let xml = SC3ML_SAMPLE.replacen("<latitude>", "<latitude>x", 1);
+    let err = Sc3ml::read_from_str(&xml).unwrap_err();
+    assert_eq!(err.path(), Some("XX.PBUMI"));
+    assert!(matches!(err.root_cause(), StationXmlError::XmlParse(_)));
+    assert!(err.location().is_some());
+}
+
+#[test]
+fn lenient_drops_unused_decimation_with_bad_filter() {
+    let xml = SC3ML_SAMPLE
+        .replace(
+            "      </decimation>\n    </datalogger>",
+            "      </decimation>\n      <decimation sampleRateNumerator=\"50\" sampleRateDenominator=\"1\">\n        <digitalFilterChain>ResponseFIR#BAD</digitalFilterChain>\n      </decimation>\n    </datalogger>",
+        )
+        .replace(
+            "    <network ",
+            "    <responseFIR publicID=\"ResponseFIR#BAD\">\n      <gain>1</gain>\n      <symmetry>X</symmetry>\n      <coefficients>1</coefficients>\n    </responseFIR>\n    <network ",
+        );
+    assert!(Sc3ml::read_from_str(&xml).is_err());
+
+    // Dropped as a whole, like a response; the stream's own decimation is kept
+    let outcome = read_from_str_with(&xml, &ReadOptions::default().lenient(true)).unwrap();
+    let paths: Vec<&str> = outcome.warnings.iter().filter_map(|w| w.path()).collect();
+    assert_eq!(paths, ["XX.PBUMI.00.SHZ datalogger decimation 50/1"]);
+    let shz = &outcome.inventory.networks[0].stations[0].channels[0];
+    assert!(shz.response.is_some());
+    assert!(shz.data_logger.as_ref().unwrap().decimations.is_empty());
+}
+
+#[test]
+fn lenient_keeps_stream_with_bad_end_date() {
+    let (head, tail) = SC3ML_SAMPLE.split_at(SC3ML_SAMPLE.find(r#"<stream code="SHN""#).unwrap());
+    let xml = format!(
+        "{head}{}",
+        tail.replacen("<start>", "<end>soon</end>\n            <start>", 1)
+    );
+    assert!(Sc3ml::read_from_str(&xml).is_err());
+
+    let outcome = read_from_str_with(&xml, &ReadOptions::default().lenient(true)).unwrap();
+    let paths: Vec<&str> = outcome.warnings.iter().filter_map(|w| w.path()).collect();
+    assert_eq!(paths, ["XX.PBUMI.00.SHN"]);
+    let original = Sc3ml::read_from_str(SC3ML_SAMPLE).unwrap();
+    assert_eq!(outcome.inventory, original);
+}
+
+#[test]
+fn lenient_keeps_calibration_with_bad_start_date() {
+    let xml = SC3ML_SAMPLE.replacen(
+        "      <unit>M/S</unit>\n    </sensor>",
+        "      <unit>M/S</unit>\n      <calibration serialNumber=\"1234\">\n        <start>soon</start>\n        <end>2030-01-01T00:00:00Z</end>\n        <gain>42</gain>\n      </calibration>\n    </sensor>",
+        1,
+    );
+    assert!(Sc3ml::read_from_str(&xml).is_err());
+
+    // Only the bad bound is lost, not the channel or the record
+    let outcome = read_from_str_with(&xml, &ReadOptions::default().lenient(true)).unwrap();
+    let paths: Vec<&str> = outcome.warnings.iter().filter_map(|w| w.path()).collect();
+    assert_eq!(paths, ["XX.PBUMI.00.SHZ sensor calibration 1234"]);
+    let channels = &outcome.inventory.networks[0].stations[0].channels;
+    assert_eq!(channels.len(), 3);
+    let calibration = &channels[0].sensor.as_ref().unwrap().calibrations[0];
+    assert_eq!(calibration.start, None);
+    assert_eq!(
+        calibration.end,
+        Some("2030-01-01T00:00:00Z".parse().unwrap())
+    );
+    assert_eq!(calibration.gain, Some(42.0));
+}
+
+#[test]
+fn lenient_drops_responses_with_bad_filters() {
+    let xml = SC3ML_SAMPLE.replace("<symmetry>C</symmetry>", "<symmetry>X</symmetry>");
+    let outcome = read_from_str_with(&xml, &ReadOptions::default().lenient(true)).unwrap();
+    let paths: Vec<&str> = outcome.warnings.iter().filter_map(|w| w.path()).collect();
+    assert_eq!(paths, ["XX.PBUMI.00.SHZ stage 3"]);
+    // Located at the stream, whose response is dropped as a whole
+    let location = outcome.warnings[0].location().unwrap();
+    assert!(xml[location.offset..].starts_with(r#"<stream code="SHZ""#));
+
+    let original = Sc3ml::read_from_str(SC3ML_SAMPLE).unwrap();
+    let channels = &outcome.inventory.networks[0].stations[0].channels;
+    assert_eq!(
+        channels.len(),
+        original.networks[0].stations[0].channels.len()
+    );
+    assert!(channels[0].response.is_none());
+    assert_eq!(channels[0].code, "SHZ");
+    assert!(channels[0].sensor.is_some());
+}
+
+#[test]
+fn roundtrip_keeps_fractional_seconds() {
+    let time = |s: &str| s.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+    let inv = Inventory::builder()
+        .source("Test")
+        .network("XX", |net| {
+            net.station("PBUMI", |sta| {
+                sta.channel("SHZ", "00", |ch| {
+                    ch.sample_rate(100.0)
+                        .start_date(time("2020-01-01T00:00:00.5Z"))
+                        .end_date(time("2020-01-01T00:00:00.75Z"))
+                })
+                .channel("SHZ", "00", |ch| {
+                    ch.sample_rate(100.0)
+                        .start_date(time("2020-01-01T00:00:00.75Z"))
+                })
+            })
+        })
+        .build();
+
+    let xml = Sc3ml::write_to_string(&inv).unwrap();
+    assert!(
+        xml.contains("<start>2020-01-01T00:00:00.750Z</start>"),
+        "{xml}"
+    );
+    let back = Sc3ml::read_from_str(&xml).unwrap();
+    let channels = &back.networks[0].stations[0].channels;
+    assert_eq!(channels.len(), 2);
+    assert_eq!(channels[0].end_date, channels[1].start_date);
+    assert_eq!(
+        channels[0].start_date,
+        inv.networks[0].stations[0].channels[0].start_date
+    );
+
+    let style = XmlStyle::default().times(TimeFormat::Microseconds);
+    let xml =
+        Sc3ml::write_to_string_with(&inv, &Sc3mlWriteOptions::default().style(style)).unwrap();
+    assert!(
+        xml.contains("<start>2020-01-01T00:00:00.500000Z</start>"),
+        "{xml}"
+    );
+}
+
+#[test]
+fn sentinel_end_times_roundtrip() {
+    let time = |s: &str| s.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+    let inv = read_from_str(SC3ML_SAMPLE).unwrap();
+    assert!(inv.networks[0].stations[0].channels[0].end_date.is_none());
+
+    let sentinel = time("3000-01-01T00:00:00Z");
+    let xml = Sc3ml::write_to_string_with(&inv, &Sc3mlWriteOptions::default().open_end(sentinel))
+        .unwrap();
+    assert!(xml.contains("<end>3000-01-01T00:00:00Z</end>"), "{xml}");
+
+    let opts = ReadOptions::default().open_end_after(time("2500-01-01T00:00:00Z"));
+    let back = Sc3ml::read_from_str_with(&xml, &opts).unwrap().inventory;
+    assert_eq!(back.networks[0].end_date, None);
+    let channels = &back.networks[0].stations[0].channels;
+    assert!(channels.iter().all(|ch| ch.end_date.is_none()));
 }